@@ -0,0 +1,24 @@
+#![feature(test)]
+
+extern crate mollusk_svm;
+extern crate solana_account;
+extern crate solana_instruction;
+extern crate solana_pubkey;
+extern crate test;
+
+mod setup;
+use setup::*;
+
+#[cfg(test)]
+mod all {
+
+    use super::*;
+    use test::Bencher;
+
+    /// Benchmarks every registered program and feature build, so a program
+    /// added to `programs::PROGRAMS` needs no bench target of its own.
+    #[bench]
+    fn run(_bencher: &mut Bencher) {
+        runner::run_all();
+    }
+}
@@ -0,0 +1,23 @@
+#![feature(test)]
+
+extern crate mollusk_svm;
+extern crate solana_account;
+extern crate solana_instruction;
+extern crate solana_pubkey;
+extern crate solana_sdk;
+extern crate test;
+
+mod setup;
+use setup::*;
+
+#[cfg(test)]
+mod chained {
+
+    use super::*;
+    use test::Bencher;
+
+    #[bench]
+    fn run(_bencher: &mut Bencher) {
+        runner::run_chained(&[&programs::Pinocchio, &programs::SolanaProgram]);
+    }
+}
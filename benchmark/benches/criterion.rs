@@ -0,0 +1,45 @@
+//! Host wall-clock benchmarks of the SVM execution of every benchmark case,
+//! measured with criterion. Compute units are measured by the per-program
+//! benches; this adapter lets criterion dashboards ingest the same cases.
+
+extern crate mollusk_svm;
+extern crate solana_account;
+extern crate solana_instruction;
+extern crate solana_pubkey;
+extern crate solana_sdk;
+
+// `runner::run` measures compute units and is not used here.
+#[allow(dead_code)]
+mod setup;
+use criterion::{criterion_group, criterion_main, Criterion};
+use setup::*;
+
+fn bench_program(c: &mut Criterion, program: &dyn programs::BenchProgram) {
+    let mollusk = runner::exit_on_error(setup(program));
+    let name = program.name();
+    let mut group = c.benchmark_group(name);
+
+    for case in runner::exit_on_error(runner::cases(&mollusk, program)) {
+        // Strip the program prefix, which is already the group name.
+        let id = case
+            .id
+            .strip_prefix(&format!("{}: ", name))
+            .unwrap_or(&case.id)
+            .to_string();
+        group.bench_function(id, |b| {
+            b.iter(|| mollusk.process_instruction(&case.instruction, &case.accounts))
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmarks every registered program and feature build.
+fn entrypoints(c: &mut Criterion) {
+    for program in programs::PROGRAMS {
+        bench_program(c, program);
+    }
+}
+
+criterion_group!(benches, entrypoints);
+criterion_main!(benches);
@@ -0,0 +1,71 @@
+//! Host benchmarks of the pure functions behind the SlotHashes benchmarks,
+//! measured with criterion: the searches of the programs on raw sysvar data
+//! and the generation of the mock entries. They need no program to be built
+//! nor the SVM, so algorithmic changes can be iterated on quickly before
+//! measuring their compute units with the per-program benches.
+
+extern crate mollusk_svm;
+extern crate solana_account;
+extern crate solana_instruction;
+extern crate solana_pubkey;
+extern crate solana_sdk;
+
+// Only the mock data generation is used here.
+#[allow(dead_code)]
+mod setup;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use eisodos_solana_nostd_entrypoint::processor::{
+    process_slot_hashes_bytes, process_slot_hashes_bytes_zerocopy, SlotHashOp,
+};
+use eisodos_solana_program::sorted_sysvar::SlotHashes;
+// The setup refers to its items from the crate root, like the other benches.
+use setup::*;
+
+fn mock_data(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate_mock_slot_hashes_data");
+    for slot_gaps in runner::exit_on_error(slot_gaps::from_env()) {
+        group.bench_function(slot_gaps.name, |b| {
+            b.iter(|| generate_mock_slot_hashes_data(black_box(&slot_gaps.distribution)))
+        });
+    }
+    group.finish();
+}
+
+/// Searches the entries of every slot gap strategy for the slots of the
+/// first, middle and last entries, by index, with the midpoint binary search
+/// of `solana-program` and the bytemuck and zerocopy lookups of
+/// `solana-nostd-entrypoint`.
+fn searches(c: &mut Criterion) {
+    for slot_gaps in runner::exit_on_error(slot_gaps::from_env()) {
+        let entries = generate_mock_slot_hashes_data(&slot_gaps.distribution);
+        let data = slot_hashes_data(&entries);
+        let mut group = c.benchmark_group(format!("slot_hashes_search/{}", slot_gaps.name));
+
+        let last = entries.len() - 1;
+        for index in [0, last / 2, last] {
+            let slot = entries[index].0;
+            group.bench_with_input(
+                BenchmarkId::new("binary_search", index),
+                &slot,
+                |b, &slot| b.iter(|| SlotHashes::new(black_box(&data)).binary_search(slot)),
+            );
+            group.bench_with_input(BenchmarkId::new("bytemuck", index), &slot, |b, &slot| {
+                b.iter(|| {
+                    process_slot_hashes_bytes(black_box(&data), SlotHashOp::GetPosition(slot))
+                })
+            });
+            group.bench_with_input(BenchmarkId::new("zerocopy", index), &slot, |b, &slot| {
+                b.iter(|| {
+                    process_slot_hashes_bytes_zerocopy(
+                        black_box(&data),
+                        SlotHashOp::GetPosition(slot),
+                    )
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, mock_data, searches);
+criterion_main!(benches);
@@ -1,7 +1,6 @@
 #![feature(test)]
 
 extern crate mollusk_svm;
-extern crate mollusk_svm_bencher;
 extern crate solana_account;
 extern crate solana_instruction;
 extern crate solana_pubkey;
@@ -18,6 +17,6 @@ mod pinocchio {
 
     #[bench]
     fn run(_bencher: &mut Bencher) {
-        runner::run(&eisodos_pinocchio::ID.into(), "eisodos_pinocchio");
+        runner::run(&programs::Pinocchio);
     }
 }
@@ -0,0 +1,22 @@
+#![feature(test)]
+
+extern crate mollusk_svm;
+extern crate solana_account;
+extern crate solana_instruction;
+extern crate solana_pubkey;
+extern crate test;
+
+mod setup;
+use setup::*;
+
+#[cfg(test)]
+mod pinocchio_verbose {
+
+    use super::*;
+    use test::Bencher;
+
+    #[bench]
+    fn run(_bencher: &mut Bencher) {
+        runner::run(&programs::PINOCCHIO_VERBOSE);
+    }
+}
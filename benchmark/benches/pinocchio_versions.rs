@@ -0,0 +1,43 @@
+#![feature(test)]
+
+extern crate mollusk_svm;
+extern crate solana_account;
+extern crate solana_instruction;
+extern crate solana_pubkey;
+extern crate test;
+
+mod setup;
+use setup::*;
+
+#[cfg(test)]
+mod pinocchio_versions {
+
+    use super::*;
+    use test::Bencher;
+
+    /// Benchmarks every version build of the pinocchio program in
+    /// `../target/deploy`, named `eisodos_pinocchio@<version>`, so results
+    /// are keyed by version.
+    #[bench]
+    fn run(_bencher: &mut Bencher) {
+        let mut names: Vec<String> = std::fs::read_dir("../target/deploy")
+            .expect("failed to read ../target/deploy, build the programs first")
+            .flatten()
+            .filter_map(|entry| {
+                let file = entry.file_name().into_string().ok()?;
+                let name = file.strip_suffix(".so")?;
+                name.starts_with("eisodos_pinocchio@")
+                    .then(|| name.to_string())
+            })
+            .collect();
+        names.sort();
+
+        for name in names {
+            // Program names are shared by every benchmark of the run.
+            runner::run(&programs::VersionBuild {
+                name: Box::leak(name.into_boxed_str()),
+                program: &programs::Pinocchio,
+            });
+        }
+    }
+}
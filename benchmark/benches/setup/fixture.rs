@@ -0,0 +1,220 @@
+//! Benchmarks of recorded real-world instructions.
+//!
+//! With `EISODOS_FIXTURES=<dir>`, the runner adds a benchmark for every
+//! `*.json` file in `<dir>`, executing the recorded instruction against the
+//! benchmarked program. A fixture holds either a base64 serialized (legacy)
+//! transaction and the index of the instruction to run, or the base64
+//! instruction data and its account metas:
+//!
+//! ```json
+//! {
+//!   "id": "Transfer (mainnet)",
+//!   "transaction": "AQAB...",
+//!   "instruction_index": 0,
+//!   "accounts": [{ "pubkey": "...", "account": { ... } }]
+//! }
+//! ```
+//!
+//! `accounts` are the input accounts of the instruction in the format of
+//! `solana account <PUBKEY> --output json`, so they can be fetched from any
+//! cluster. The program id of the recorded instruction is replaced by the id of
+//! the benchmarked program.
+
+use super::BenchCase;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use eisodos::error::EisodosError;
+use serde::Deserialize;
+use solana_account::Account;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use std::{fs, path::Path, str::FromStr};
+
+#[derive(Deserialize)]
+struct FixtureMeta {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+/// Account as output by `solana account --output json`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiAccount {
+    lamports: u64,
+    /// Data and its encoding, which must be `base64`.
+    data: (String, String),
+    owner: String,
+    executable: bool,
+    rent_epoch: u64,
+}
+
+#[derive(Deserialize)]
+struct KeyedUiAccount {
+    pubkey: String,
+    account: UiAccount,
+}
+
+#[derive(Deserialize)]
+struct Fixture {
+    id: String,
+    /// Base64 serialized transaction holding the instruction.
+    transaction: Option<String>,
+    /// Index of the instruction within `transaction`.
+    #[serde(default)]
+    instruction_index: usize,
+    /// Base64 instruction data, used when there is no `transaction`.
+    data: Option<String>,
+    /// Account metas of the instruction, used when there is no `transaction`.
+    #[serde(default)]
+    metas: Vec<FixtureMeta>,
+    accounts: Vec<KeyedUiAccount>,
+}
+
+fn pubkey(value: &str) -> Result<Pubkey, EisodosError> {
+    Pubkey::from_str(value)
+        .map_err(|_| EisodosError::Spec(format!("invalid fixture pubkey `{value}`")))
+}
+
+fn base64(value: &str) -> Result<Vec<u8>, EisodosError> {
+    STANDARD
+        .decode(value)
+        .map_err(|error| EisodosError::Spec(format!("invalid fixture base64: {error}")))
+}
+
+/// Returns the instruction at `index` of a serialized transaction, with the
+/// signer and writable flags derived from the message header.
+fn transaction_instruction(
+    transaction: &str,
+    index: usize,
+) -> Result<(Vec<u8>, Vec<AccountMeta>), EisodosError> {
+    let transaction: Transaction = bincode::deserialize(&base64(transaction)?)
+        .map_err(|error| EisodosError::Spec(format!("invalid fixture transaction: {error}")))?;
+    let message = &transaction.message;
+    let instruction = message.instructions.get(index).ok_or_else(|| {
+        EisodosError::Spec(format!("no instruction {index} in fixture transaction"))
+    })?;
+
+    let header = &message.header;
+    let signers = header.num_required_signatures as usize;
+    let writable_signers = signers - header.num_readonly_signed_accounts as usize;
+    let writable_unsigned =
+        message.account_keys.len() - header.num_readonly_unsigned_accounts as usize;
+
+    let metas = instruction
+        .accounts
+        .iter()
+        .map(|&key_index| {
+            let key_index = key_index as usize;
+            AccountMeta {
+                pubkey: message.account_keys[key_index],
+                is_signer: key_index < signers,
+                is_writable: if key_index < signers {
+                    key_index < writable_signers
+                } else {
+                    key_index < writable_unsigned
+                },
+            }
+        })
+        .collect();
+
+    Ok((instruction.data.clone(), metas))
+}
+
+fn load_fixture(path: &Path, program_id: &Pubkey) -> Result<BenchCase, EisodosError> {
+    let contents = fs::read(path).map_err(|error| EisodosError::read(path, error))?;
+    let fixture: Fixture = serde_json::from_slice(&contents).map_err(|error| {
+        EisodosError::Spec(format!("invalid fixture {}: {error}", path.display()))
+    })?;
+
+    let (data, metas) = match (&fixture.transaction, &fixture.data) {
+        (Some(transaction), _) => transaction_instruction(transaction, fixture.instruction_index)?,
+        (None, Some(data)) => (
+            base64(data)?,
+            fixture
+                .metas
+                .iter()
+                .map(|meta| {
+                    Ok(AccountMeta {
+                        pubkey: pubkey(&meta.pubkey)?,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                })
+                .collect::<Result<_, EisodosError>>()?,
+        ),
+        (None, None) => {
+            return Err(EisodosError::Spec(format!(
+                "fixture {} has neither a transaction nor instruction data",
+                path.display()
+            )))
+        }
+    };
+
+    let accounts: Vec<(Pubkey, Account)> = fixture
+        .accounts
+        .iter()
+        .map(|keyed| {
+            let (data, encoding) = &keyed.account.data;
+            if encoding != "base64" {
+                return Err(EisodosError::Spec(format!(
+                    "fixture account {} must be base64 encoded",
+                    keyed.pubkey
+                )));
+            }
+            Ok((
+                pubkey(&keyed.pubkey)?,
+                Account {
+                    lamports: keyed.account.lamports,
+                    data: base64(data)?,
+                    owner: pubkey(&keyed.account.owner)?,
+                    executable: keyed.account.executable,
+                    rent_epoch: keyed.account.rent_epoch,
+                },
+            ))
+        })
+        .collect::<Result<_, _>>()?;
+
+    if let Some(meta) = metas
+        .iter()
+        .find(|meta| !accounts.iter().any(|(key, _)| *key == meta.pubkey))
+    {
+        return Err(EisodosError::Spec(format!(
+            "fixture {} is missing account {}",
+            path.display(),
+            meta.pubkey
+        )));
+    }
+
+    Ok(BenchCase::new(
+        fixture.id,
+        Instruction {
+            program_id: *program_id,
+            accounts: metas,
+            data,
+        },
+        accounts,
+    ))
+}
+
+/// Loads every fixture of `dir` as a benchmark of `program_id`, in file name
+/// order.
+pub fn load(dir: &str, program_id: &Pubkey) -> Result<Vec<BenchCase>, EisodosError> {
+    let read = |error| EisodosError::read(dir, error);
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(read)? {
+        let path = entry.map_err(read)?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| load_fixture(path, program_id))
+        .collect()
+}
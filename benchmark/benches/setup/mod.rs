@@ -1,107 +1,591 @@
+pub mod fixture;
+pub mod programs;
+pub mod record;
+pub mod requirements;
 pub mod runner;
+pub mod scenarios;
+pub mod sink;
+pub mod slot_gaps;
+pub mod tag_fuzz;
 
 // Bring crates into scope
-use mollusk_svm::{program::keyed_account_for_system_program, Mollusk};
+use eisodos::error::EisodosError;
+pub use eisodos_instructions::Instruction as ProgramInstruction;
+use eisodos_instructions::VERSION_2;
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk};
+use slot_gaps::GapDistribution;
 use solana_account::Account;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_program;
 // Imports needed for SlotHashes construction
 // Use correct paths for 1.18
-use solana_program::clock::Slot;
+use solana_program::clock::Clock;
 use solana_program::hash::Hash;
+use solana_program::program_error::ProgramError;
+use solana_program::rent::Rent;
 // SlotHash is a type alias (Slot, Hash)
-use solana_program::slot_hashes::SlotHash;
+use solana_program::slot_hashes::{SlotHash, SlotHashes};
 // Use Sysvar ID from solana_program
 use solana_program::sysvar::ID as SYSVAR_PROGRAM_ID;
 use solana_pubkey::Pubkey;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::message::{v0, AddressLookupTableAccount, Message, VersionedMessage};
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
 use std::vec;
 
-// Enum to control slot decrement behavior in mock data generation
-#[derive(Clone, Copy, Debug)]
-enum DecrementStrategy {
-    Strictly1,
-    Average1_05,
-    Average2,
-}
-
-pub const BASE_LAMPORTS: u64 = 2_000_000_000u64;
+/// Lamports the `CreateAccount` benchmarks fund the created account with.
+const CREATE_ACCOUNT_LAMPORTS: u64 = 500_000_000;
+/// Lamports moved by the `Transfer` and `CpiSignedSeeds` benchmarks, the
+/// largest amount paid by a payer.
+const TRANSFER_LAMPORTS: u64 = 1_000_000_000;
 const NUM_BENCH_SLOT_HASH_ENTRIES: usize = 512;
 const BENCH_SLOT_HASH_START_SLOT: u64 = 10000;
+/// Fee payer of the transactions of benchmarks without signers.
+const FEE_PAYER: Pubkey = Pubkey::new_from_array([0xfe; 32]);
+/// Address of the lookup table of the v0 transactions of benchmarks.
+const LOOKUP_TABLE: Pubkey = Pubkey::new_from_array([0xfd; 32]);
+/// Number of accounts from which the transaction of a benchmark is also built
+/// as a v0 transaction with a lookup table.
+const LOOKUP_TABLE_MIN_ACCOUNTS: usize = 32;
+/// Slot gaps of the SlotHashes mock in the sysvar cache.
+pub const SYSVAR_CACHE_SLOT_GAPS: GapDistribution = GapDistribution::Fixed(1);
+/// Number of entries of the StakeHistory sysvar (its maximum).
+pub const NUM_BENCH_STAKE_HISTORY_ENTRIES: usize = 512;
+/// Epoch of the most recent StakeHistory entry.
+const BENCH_STAKE_HISTORY_START_EPOCH: u64 = 800;
+/// Data length of the accounts used by the field and data access benchmarks.
+const DATA_ACCOUNT_LEN: usize = 32;
+/// Size of the program state used by the `ReadModifyWrite` benchmark.
+///
+/// Layout: `counter: u64` + `timestamp: i64` + `authority: [u8; 32]` +
+/// `payload: [u8; 152]`.
+pub const STATE_LEN: usize = 200;
+/// Number of keys in the signer set of the `CheckMultisig` multisig account.
+const MAX_SIGNERS: usize = 11;
+/// Depth of the concurrent merkle tree of the `AppendLeaf` benchmark.
+const MERKLE_TREE_DEPTH: usize = 14;
+/// Number of entries of the changelog buffer of the `AppendLeaf` benchmark.
+const MERKLE_TREE_BUFFER_SIZE: usize = 64;
+/// Offset of the changelog buffer in the `AppendLeaf` tree account, after the
+/// sequence number, the active index and the frontier.
+const CHANGE_LOGS_OFFSET: usize = 16 + 32 * MERKLE_TREE_DEPTH;
+/// Length of a changelog entry: root, path, leaf index and padding.
+const CHANGE_LOG_LEN: usize = 32 + 32 * MERKLE_TREE_DEPTH + 8;
+/// Data length of the `AppendLeaf` tree account.
+const MERKLE_TREE_LEN: usize = CHANGE_LOGS_OFFSET + CHANGE_LOG_LEN * MERKLE_TREE_BUFFER_SIZE;
+/// Data length of the result account the programs write computed values to:
+/// a found flag followed by up to 32 bytes.
+pub const RESULT_ACCOUNT_LEN: usize = 33;
+/// Token-2022 program id, owner of the `ParseToken2022Extensions` account.
+const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+/// Withheld transfer fee of the `ParseToken2022Extensions` account.
+pub const TOKEN_2022_WITHHELD_AMOUNT: u64 = 42_000;
+/// Token Metadata program id, owner of the `ParseMetadata` account.
+const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+/// BPF upgradeable loader id, owner of the `ParseProgramData` account.
+const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    solana_program::pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+/// Feature program id, owner of the `CheckFeature` account.
+const FEATURE_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("Feature111111111111111111111111111111111111");
+/// Activation slot of the `CheckFeature` account.
+pub const FEATURE_ACTIVATION_SLOT: u64 = 1_000;
+/// Memo program id, the last of the program ids known to `MatchProgramId`.
+const MEMO_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+/// Position of `MEMO_PROGRAM_ID` among the program ids known to
+/// `MatchProgramId`.
+const MEMO_PROGRAM_ID_POSITION: usize = 7;
+/// Data length of Token Metadata accounts, which are zero-padded to it.
+const METADATA_ACCOUNT_LEN: usize = 679;
+
+/// Returns a system account paying for the CPIs of the benchmarks, holding
+/// `TRANSFER_LAMPORTS`, the largest payment, on top of the rent-exempt minimum
+/// it must keep.
+pub fn funded_payer() -> Account {
+    let lamports = Rent::default().minimum_balance(0) + TRANSFER_LAMPORTS;
+    Account::new(lamports, 0, &solana_system_interface::program::ID)
+}
 
-// Simple deterministic PRNG for varied decrements
-// Using a basic Lehmer / MINSTD generator approach
-fn simple_prng(seed: u64) -> u64 {
-    const A: u64 = 16807; // Multiplier
-    const M: u64 = 2147483647; // Modulus (2^31 - 1)
-    let initial_state = if seed == 0 { 1 } else { seed };
-    (A.wrapping_mul(initial_state)) % M
+/// Returns a rent-exempt account of `len` zeroed bytes owned by `owner`.
+///
+/// Mollusk uses `Rent::default()`, so the balance matches the rent checks of
+/// the programs and of the runtime.
+pub fn rent_exempt_account(len: usize, owner: &Pubkey) -> Account {
+    Account::new(Rent::default().minimum_balance(len), len, owner)
 }
 
-/// Create a new Mollusk instance for the given program ID and name.
-pub fn setup(program_id: &Pubkey, name: &'static str) -> Mollusk {
+/// Returns a system account without lamports or data, such as the recipient
+/// of a transfer or an account being created.
+pub fn empty_account() -> Account {
+    Account::new(0, 0, &solana_system_interface::program::ID)
+}
+
+/// Create a new Mollusk instance for the given program, failing if its
+/// prebuilt binary cannot be read or is empty.
+pub fn setup(program: &dyn programs::BenchProgram) -> Result<Mollusk, EisodosError> {
     std::env::set_var("SBF_OUT_DIR", "../target/deploy");
     solana_logger::setup_with("");
+    init_tracing();
+
+    let program_id = &program.id();
+    let name = program.name();
+    let mut mollusk = match program.artifact() {
+        Some(path) => {
+            let elf = std::fs::read(path).map_err(|error| EisodosError::read(path, error))?;
+            if elf.is_empty() {
+                return Err(EisodosError::Build(format!(
+                    "{name} was not built, set PLATFORM_TOOLS_DIR and rebuild it"
+                )));
+            }
+            let mut mollusk = Mollusk::default();
+            mollusk.add_program_with_elf_and_loader(
+                program_id,
+                &elf,
+                &mollusk_svm::program::loader_keys::LOADER_V3,
+            );
+            mollusk
+        }
+        None => Mollusk::new(program_id, name),
+    };
+    // Instructions fetching SlotHashes with `sol_get_sysvar` read the sysvar
+    // cache instead of a mock account.
+    let entries: Vec<SlotHash> = generate_mock_slot_hashes_data(&SYSVAR_CACHE_SLOT_GAPS)
+        .into_iter()
+        .map(|(slot, hash)| (slot, Hash::new_from_array(hash)))
+        .collect();
+    mollusk.sysvars.slot_hashes = SlotHashes::new(&entries);
+    Ok(mollusk)
+}
+
+/// Installs the subscriber of the runner's `tracing` events, printed to stderr:
+/// `info` and above, or `debug` with `EISODOS_VERBOSE`, as JSON lines with
+/// `EISODOS_LOG_FORMAT=json`.
+fn init_tracing() {
+    let level = if std::env::var_os("EISODOS_VERBOSE").is_some() {
+        tracing::Level::DEBUG
+    } else {
+        tracing::Level::INFO
+    };
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr);
 
-    Mollusk::new(program_id, name)
-}
-
-/// Instructions on the program to be executed.
-#[derive(Clone, Copy, Debug)]
-pub enum ProgramInstruction {
-    Ping,
-    Log,
-    Account { expected: u64 },
-    CreateAccount,
-    Transfer,
-    // --- SlotHashes (Safe/Checked Path - Primarily for SDK/Nostd now) ---
-    SlotHashesGetEntryChecked, // ID 5
-    SlotHashesGetHashChecked,  /* ID 6 (Treat Interpolated/Midpoint same for SDK/Nostd manual
-                                * search) */
-    SlotHashesPositionChecked, // ID 7
-    // --- SlotHashes (Unsafe/Unchecked Path - For Pinocchio Benchmarking) ---
-    SlotHashesGetEntryUnchecked,             // ID 8
-    SlotHashesGetHashInterpolatedUnchecked,  // ID 9
-    SlotHashesPositionInterpolatedUnchecked { target_slot: Slot }, // ID 10 <- Takes Slot
-    SlotHashesPositionNaiveUnchecked { target_slot: Slot },        // ID 11 <- Takes Slot
+    // Every program sets up its own Mollusk, but the subscriber is global, so
+    // only the first one is installed.
+    let _ = if std::env::var("EISODOS_LOG_FORMAT").as_deref() == Ok("json") {
+        tracing::subscriber::set_global_default(subscriber.json().finish())
+    } else {
+        tracing::subscriber::set_global_default(subscriber.finish())
+    };
 }
 
-/// Returns the instruction data for the given instruction.
-pub fn instruction_data(instruction: ProgramInstruction) -> Vec<u8> {
-    match instruction {
-        ProgramInstruction::Ping => vec![0],
-        ProgramInstruction::Log => vec![1],
-        ProgramInstruction::Account { expected } => {
-            let mut data = Vec::with_capacity(9);
-            data.push(2);
-            data.extend_from_slice(&expected.to_le_bytes());
-            data
+/// Fixture parameters of a benchmark that are not derived from its accounts,
+/// recorded as typed fields next to the display id.
+#[derive(Clone, Debug, Default)]
+pub struct BenchParams {
+    /// Number of entries of the sysvar passed to the benchmark.
+    pub sysvar_entries: Option<usize>,
+    /// Strategy of the benchmark, e.g. the SlotHashes decrement strategy or
+    /// the dispatch strategy.
+    pub strategy: Option<&'static str>,
+    /// Index of the looked up entry.
+    pub target_index: Option<usize>,
+    /// Number of variants of the dispatched instruction set.
+    pub variants: Option<u8>,
+}
+
+/// Category of a benchmark, to select and group related benchmarks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tag {
+    /// Invokes another program.
+    Cpi,
+    /// Reads a sysvar, through its account or a syscall.
+    Sysvar,
+    /// Hashes data.
+    Hash,
+    /// Parses token program accounts.
+    Token,
+    /// Measures a single operation of the entrypoint or program logic.
+    Micro,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 5] = [Tag::Cpi, Tag::Sysvar, Tag::Hash, Tag::Token, Tag::Micro];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Tag::Cpi => "cpi",
+            Tag::Sysvar => "sysvar",
+            Tag::Hash => "hash",
+            Tag::Token => "token",
+            Tag::Micro => "micro",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|tag| tag.name() == name)
+    }
+}
+
+/// Best or worst case fixture of a search or parsing benchmark, e.g. the
+/// target at the head or the tail of the searched data. Both are generated
+/// next to the standard fixture, with a ` (Best)` or ` (Worst)` suffix, so
+/// reports show the range of the operation instead of a single arbitrary
+/// point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    Best,
+    Worst,
+}
+
+impl Bound {
+    /// The standard fixture, then both bounds.
+    pub const FIXTURES: [Option<Bound>; 3] = [None, Some(Bound::Best), Some(Bound::Worst)];
+
+    /// Returns the suffix of the id of a benchmark with the `bound` fixture.
+    pub fn suffix(bound: Option<Bound>) -> &'static str {
+        match bound {
+            None => "",
+            Some(Bound::Best) => " (Best)",
+            Some(Bound::Worst) => " (Worst)",
+        }
+    }
+}
+
+/// A benchmark to execute: the instruction, its input accounts and, optionally,
+/// the expected data of accounts after execution or the expected error.
+#[derive(Clone)]
+pub struct BenchCase {
+    pub id: String,
+    pub instruction: Instruction,
+    pub accounts: Vec<(Pubkey, Account)>,
+    /// Expected account data after the instruction executes. Benchmarks with
+    /// expectations are validated with Mollusk checks before being measured.
+    pub expected_data: Vec<(Pubkey, Vec<u8>)>,
+    /// Expected account owners after the instruction executes.
+    pub expected_owners: Vec<(Pubkey, Pubkey)>,
+    /// Messages the program is expected to log (`true`) or not to log
+    /// (`false`), checked with a log collector apart from the measurement.
+    pub expected_logs: Vec<(&'static str, bool)>,
+    /// Error the instruction is expected to fail with, for benchmarks of
+    /// error paths.
+    pub expected_error: Option<ProgramError>,
+    /// Instruction invoked through CPI, measured on its own to split the
+    /// compute units of the benchmarked program from the invoked program's.
+    pub cpi: Option<Instruction>,
+    /// ComputeBudget instructions preceding the instruction in its
+    /// transaction. Their own compute units are deducted from the measurement.
+    pub compute_budget: Vec<Instruction>,
+    pub params: BenchParams,
+    /// Categories of the benchmark, selected with `EISODOS_TAGS`.
+    pub tags: Vec<Tag>,
+}
+
+impl BenchCase {
+    pub fn new(id: String, instruction: Instruction, accounts: Vec<(Pubkey, Account)>) -> Self {
+        Self {
+            id,
+            instruction,
+            accounts,
+            expected_data: Vec::new(),
+            expected_owners: Vec::new(),
+            expected_logs: Vec::new(),
+            expected_error: None,
+            cpi: None,
+            compute_budget: Vec::new(),
+            params: BenchParams::default(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Tags the benchmark. Benchmarks declaring a CPI are also tagged `cpi` by
+    /// [`Self::with_cpi`].
+    pub fn with_tags(mut self, tags: &[Tag]) -> Self {
+        self.tags.extend_from_slice(tags);
+        self
+    }
+
+    /// Records the fixture parameters of the benchmark.
+    pub fn with_params(mut self, params: BenchParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Returns the number of input accounts.
+    pub fn account_count(&self) -> usize {
+        self.instruction.accounts.len()
+    }
+
+    /// Returns the largest data length of the input accounts.
+    pub fn data_len(&self) -> usize {
+        self.accounts
+            .iter()
+            .map(|(_, account)| account.data.len())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Expects the instruction to fail with `error`.
+    pub fn with_expected_error(mut self, error: ProgramError) -> Self {
+        self.expected_error = Some(error);
+        self
+    }
+
+    /// Returns the fee payer of the transaction of the benchmark: the first
+    /// signer of the instruction, or a separate fee payer if it has none.
+    fn payer(&self) -> Pubkey {
+        self.instruction
+            .accounts
+            .iter()
+            .find(|meta| meta.is_signer)
+            .map_or(FEE_PAYER, |meta| meta.pubkey)
+    }
+
+    /// Returns the instructions of the transaction of the benchmark: its
+    /// ComputeBudget instructions followed by the instruction.
+    fn transaction_instructions(&self) -> Vec<Instruction> {
+        [&self.compute_budget[..], &[self.instruction.clone()]].concat()
+    }
+
+    /// Returns the unsigned legacy transaction of the benchmark.
+    pub fn transaction(&self) -> Transaction {
+        let message = Message::new(&self.transaction_instructions(), Some(&self.payer()));
+        Transaction::new_unsigned(message)
+    }
+
+    /// Returns the lookup table of the v0 transaction of the benchmark, holding
+    /// every account of the instruction that is not a signer.
+    fn lookup_table(&self) -> AddressLookupTableAccount {
+        AddressLookupTableAccount {
+            key: LOOKUP_TABLE,
+            addresses: self
+                .instruction
+                .accounts
+                .iter()
+                .filter(|meta| !meta.is_signer)
+                .map(|meta| meta.pubkey)
+                .collect(),
+        }
+    }
+
+    /// Returns the unsigned v0 transaction of the benchmark, loading the
+    /// accounts of the instruction from a lookup table, if it has at least
+    /// `LOOKUP_TABLE_MIN_ACCOUNTS` accounts.
+    pub fn v0_transaction(&self) -> Option<VersionedTransaction> {
+        if self.account_count() < LOOKUP_TABLE_MIN_ACCOUNTS {
+            return None;
+        }
+
+        let message = v0::Message::try_compile(
+            &self.payer(),
+            &self.transaction_instructions(),
+            &[self.lookup_table()],
+            Hash::default(),
+        )
+        .unwrap_or_else(|error| panic!("{}: failed to compile v0 message: {error}", self.id));
+        Some(VersionedTransaction {
+            signatures: vec![Signature::default(); message.header.num_required_signatures as usize],
+            message: VersionedMessage::V0(message),
+        })
+    }
+
+    /// Rebuilds the instruction of the benchmark from its v0 transaction,
+    /// resolving the lookup table as the runtime would, to check that loading
+    /// the accounts from the table does not change the execution.
+    pub fn v0_instruction(&self) -> Option<Instruction> {
+        let transaction = self.v0_transaction()?;
+        let VersionedMessage::V0(message) = &transaction.message else {
+            unreachable!("v0 transactions hold v0 messages")
+        };
+        let table = self.lookup_table();
+
+        // Static keys, then the writable and the readonly loaded addresses
+        let mut keys = message.account_keys.clone();
+        let static_len = keys.len();
+        for lookup in &message.address_table_lookups {
+            keys.extend(
+                lookup
+                    .writable_indexes
+                    .iter()
+                    .map(|&index| table.addresses[index as usize]),
+            );
+        }
+        let writable_len = keys.len();
+        for lookup in &message.address_table_lookups {
+            keys.extend(
+                lookup
+                    .readonly_indexes
+                    .iter()
+                    .map(|&index| table.addresses[index as usize]),
+            );
         }
-        ProgramInstruction::CreateAccount => vec![3],
-        ProgramInstruction::Transfer => vec![4],
-        // Checked path instructions (match SDK/Nostd implementation)
-        ProgramInstruction::SlotHashesGetEntryChecked => vec![5],
-        ProgramInstruction::SlotHashesGetHashChecked => vec![6],
-        ProgramInstruction::SlotHashesPositionChecked => vec![7],
-        // Unchecked path instructions (Pinocchio specific tests)
-        ProgramInstruction::SlotHashesGetEntryUnchecked => vec![8],
-        ProgramInstruction::SlotHashesGetHashInterpolatedUnchecked => vec![9],
-        ProgramInstruction::SlotHashesPositionInterpolatedUnchecked { target_slot } => {
-            let mut data = Vec::with_capacity(1 + 8);
-            data.push(10);
-            data.extend_from_slice(&target_slot.to_le_bytes());
-            data
+
+        let header = &message.header;
+        let signed = header.num_required_signatures as usize;
+        let is_writable = |index: usize| {
+            if index < signed {
+                index < signed - header.num_readonly_signed_accounts as usize
+            } else if index < static_len {
+                index < static_len - header.num_readonly_unsigned_accounts as usize
+            } else {
+                index < writable_len
+            }
+        };
+
+        let compiled = message.instructions.last()?;
+        Some(Instruction {
+            program_id: keys[compiled.program_id_index as usize],
+            accounts: compiled
+                .accounts
+                .iter()
+                .map(|&index| AccountMeta {
+                    pubkey: keys[index as usize],
+                    is_signer: (index as usize) < signed,
+                    is_writable: is_writable(index as usize),
+                })
+                .collect(),
+            data: compiled.data.clone(),
+        })
+    }
+
+    /// Returns whether the benchmark declares an expected outcome.
+    pub fn has_expectations(&self) -> bool {
+        !self.expected_data.is_empty()
+            || !self.expected_owners.is_empty()
+            || self.expected_error.is_some()
+    }
+
+    /// Appends a result account to the benchmark and expects the program to
+    /// write `output` (see [`position_output`] and [`hash_output`]) to it.
+    pub fn with_output(mut self, output: Vec<u8>) -> Self {
+        let key = Pubkey::new_unique();
+        self.accounts.push((
+            key,
+            rent_exempt_account(RESULT_ACCOUNT_LEN, &self.instruction.program_id),
+        ));
+        self.instruction.accounts.push(AccountMeta::new(key, false));
+
+        let mut data = output;
+        data.resize(RESULT_ACCOUNT_LEN, 0);
+        self.with_expected_data(key, data)
+    }
+
+    /// Declares the instruction the benchmarked program invokes. The system
+    /// program account is appended to the benchmark when it is the invoked
+    /// program.
+    pub fn with_cpi(mut self, instruction: Instruction) -> Self {
+        if instruction.program_id == solana_system_interface::program::ID {
+            append_system_program(&mut self.instruction, &mut self.accounts);
+        }
+        self.cpi = Some(instruction);
+        self.with_tags(&[Tag::Cpi])
+    }
+
+    /// Precedes the instruction with ComputeBudget instructions setting the
+    /// compute unit `limit` and `price`, if any.
+    pub fn with_compute_budget(mut self, limit: Option<u32>, price: Option<u64>) -> Self {
+        self.compute_budget
+            .extend(limit.map(ComputeBudgetInstruction::set_compute_unit_limit));
+        self.compute_budget
+            .extend(price.map(ComputeBudgetInstruction::set_compute_unit_price));
+        self
+    }
+
+    /// Expects `key` to hold exactly `data` after execution.
+    pub fn with_expected_data(mut self, key: Pubkey, data: Vec<u8>) -> Self {
+        self.expected_data.push((key, data));
+        self
+    }
+
+    /// Expects `key` to be owned by `owner` after execution.
+    pub fn with_expected_owner(mut self, key: Pubkey, owner: Pubkey) -> Self {
+        self.expected_owners.push((key, owner));
+        self
+    }
+
+    /// Expects the program to log `message`.
+    pub fn with_expected_log(mut self, message: &'static str) -> Self {
+        self.expected_logs.push((message, true));
+        self
+    }
+
+    /// Expects the program not to log `message`.
+    pub fn without_log(mut self, message: &'static str) -> Self {
+        self.expected_logs.push((message, false));
+        self
+    }
+
+    /// Returns the Mollusk checks validating this benchmark.
+    pub fn checks(&self) -> Vec<Check> {
+        let mut checks = match &self.expected_error {
+            Some(error) => vec![Check::err(error.clone())],
+            None => vec![Check::success()],
+        };
+        for (key, data) in &self.expected_data {
+            checks.push(Check::account(key).data(data).build());
         }
-        ProgramInstruction::SlotHashesPositionNaiveUnchecked { target_slot } => {
-            let mut data = Vec::with_capacity(1 + 8);
-            data.push(11);
-            data.extend_from_slice(&target_slot.to_le_bytes());
-            data
+        for (key, owner) in &self.expected_owners {
+            checks.push(Check::account(key).owner(owner).build());
         }
-        // Default/Error case for unused IDs - or handle in processor
-        _ => vec![255], // Or panic, or specific error instruction
+        checks
     }
 }
 
+/// Returns the result account output of an optional found index.
+pub fn position_output(position: Option<usize>) -> Vec<u8> {
+    value_output(position.map(|position| position as u64))
+}
+
+/// Returns the result account output of an optional value.
+pub fn value_output(value: Option<u64>) -> Vec<u8> {
+    match value {
+        Some(value) => [&[1], &value.to_le_bytes()[..]].concat(),
+        None => vec![0],
+    }
+}
+
+/// Returns the result account output of an optional found hash.
+pub fn hash_output(hash: Option<[u8; 32]>) -> Vec<u8> {
+    match hash {
+        Some(hash) => [&[1], &hash[..]].concat(),
+        None => vec![0],
+    }
+}
+
+/// Appends the system program account and its meta to `instruction` and
+/// `accounts`, unless already present.
+pub fn append_system_program(instruction: &mut Instruction, accounts: &mut Vec<(Pubkey, Account)>) {
+    let (system_program_id, system_program_account) = keyed_account_for_system_program();
+
+    if !instruction
+        .accounts
+        .iter()
+        .any(|meta| meta.pubkey == system_program_id)
+    {
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(system_program_id, false));
+    }
+    if !accounts.iter().any(|(key, _)| *key == system_program_id) {
+        accounts.push((system_program_id, system_program_account));
+    }
+}
+
+/// Returns the versioned (V2) instruction data for the given instruction: the
+/// version byte followed by the raw instruction data.
+pub fn instruction_data_v2(instruction: ProgramInstruction) -> Vec<u8> {
+    instruction.pack_v2()
+}
+
+/// Returns the instruction data for the given instruction.
+pub fn instruction_data(instruction: ProgramInstruction) -> Vec<u8> {
+    instruction.pack()
+}
+
 /// Generate a set of unique public keys.
 pub fn generate_pubkeys(count: usize) -> Vec<Pubkey> {
     let mut keys = Vec::with_capacity(count);
@@ -112,7 +596,7 @@ pub fn generate_pubkeys(count: usize) -> Vec<Pubkey> {
 }
 
 /// Helper function to generate more realistic SlotHashes data
-fn generate_mock_slot_hashes_data(strategy: DecrementStrategy) -> Vec<(u64, [u8; 32])> {
+pub fn generate_mock_slot_hashes_data(strategy: &GapDistribution) -> Vec<(u64, [u8; 32])> {
     let mut entries = Vec::with_capacity(NUM_BENCH_SLOT_HASH_ENTRIES);
     let mut current_slot = BENCH_SLOT_HASH_START_SLOT;
 
@@ -121,24 +605,7 @@ fn generate_mock_slot_hashes_data(strategy: DecrementStrategy) -> Vec<(u64, [u8;
         let hash = [hash_byte; 32];
         entries.push((current_slot, hash));
 
-        let random_val = simple_prng(i as u64);
-        let decrement = match strategy {
-            DecrementStrategy::Strictly1 => 1,
-            DecrementStrategy::Average1_05 => {
-                if random_val % 20 == 0 {
-                    2
-                } else {
-                    1
-                }
-            }
-            DecrementStrategy::Average2 => {
-                if random_val % 2 == 0 {
-                    1
-                } else {
-                    3
-                }
-            }
-        };
+        let decrement = strategy.gap(i);
         
         // Calculate next slot and check for saturation/no change
         let next_slot = current_slot.saturating_sub(decrement);
@@ -152,6 +619,18 @@ fn generate_mock_slot_hashes_data(strategy: DecrementStrategy) -> Vec<(u64, [u8;
     entries
 }
 
+/// Serializes SlotHashes entries in the layout of the sysvar account: a `u64`
+/// entry count followed by the slot and hash of each entry.
+pub fn slot_hashes_data(entries: &[(u64, [u8; 32])]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(8 + entries.len() * (8 + 32));
+    data.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+    for (slot, hash) in entries {
+        data.extend_from_slice(&slot.to_le_bytes());
+        data.extend_from_slice(hash);
+    }
+    data
+}
+
 /// Generates the instruction data and accounts for the
 /// `ProgramInstruction::Account` instruction.
 fn generate_account(program_id: Pubkey, expected: u64) -> (Instruction, Vec<(Pubkey, Account)>) {
@@ -164,7 +643,7 @@ fn generate_account(program_id: Pubkey, expected: u64) -> (Instruction, Vec<(Pub
         let key = keys.pop().unwrap();
         accounts.push((
             key,
-            Account::new(BASE_LAMPORTS, 0, &solana_system_interface::program::ID),
+            rent_exempt_account(0, &solana_system_interface::program::ID),
         ));
         account_metas.push(AccountMeta::new_readonly(key, false));
     }
@@ -179,34 +658,774 @@ fn generate_account(program_id: Pubkey, expected: u64) -> (Instruction, Vec<(Pub
     )
 }
 
+/// Generates `count` accounts holding `DATA_ACCOUNT_LEN` bytes of data for
+/// the instructions that read account fields or data
+/// (`ProgramInstruction::AccountFields`, `ProgramInstruction::ReadData*`).
+///
+/// Accounts alternate between signer/writable and readonly so that every
+/// flag accessor sees both values.
+fn generate_data_accounts(
+    program_id: Pubkey,
+    instruction: ProgramInstruction,
+    count: u64,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let keys = generate_pubkeys(count as usize);
+
+    let mut accounts = Vec::with_capacity(keys.len());
+    let mut account_metas = Vec::with_capacity(keys.len());
+
+    for (i, key) in keys.into_iter().enumerate() {
+        let mut account =
+            rent_exempt_account(DATA_ACCOUNT_LEN, &solana_system_interface::program::ID);
+        account.data.fill(i as u8);
+        accounts.push((key, account));
+        if i % 2 == 0 {
+            account_metas.push(AccountMeta::new(key, true));
+        } else {
+            account_metas.push(AccountMeta::new_readonly(key, false));
+        }
+    }
+
+    (
+        Instruction {
+            program_id,
+            accounts: account_metas,
+            data: instruction_data(instruction),
+        },
+        accounts,
+    )
+}
+
 /// Generates the instruction data and accounts for the
-/// `ProgramInstruction::CreateAccount` instruction.
+/// `ProgramInstruction::ValidateAccounts` instruction.
+///
+/// Every account passes all checks: it is owned by the program, signer,
+/// writable and rent exempt.
+fn generate_validate_accounts(
+    program_id: Pubkey,
+    count: u8,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let keys = generate_pubkeys(count as usize);
+    let lamports = Rent::default().minimum_balance(DATA_ACCOUNT_LEN);
+
+    let mut accounts = Vec::with_capacity(keys.len());
+    let mut account_metas = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        accounts.push((key, Account::new(lamports, DATA_ACCOUNT_LEN, &program_id)));
+        account_metas.push(AccountMeta::new(key, true));
+    }
+
+    (
+        Instruction {
+            program_id,
+            accounts: account_metas,
+            data: instruction_data(crate::ProgramInstruction::ValidateAccounts { count }),
+        },
+        accounts,
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::ParseProgramData` instruction: a `ProgramData` account
+/// of the upgradeable loader holding a 1KB program, the upgrade authority of
+/// which is returned.
+fn generate_program_data(program_id: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>, Pubkey) {
+    let keys = generate_pubkeys(2);
+    let (program_data_key, authority) = (keys[0], keys[1]);
+
+    // bincode layout of `UpgradeableLoaderState::ProgramData`: u32
+    // discriminant + u64 slot + Option<Pubkey> authority, then the program
+    let mut data = Vec::with_capacity(45 + 1024);
+    data.extend_from_slice(&3u32.to_le_bytes());
+    data.extend_from_slice(&42u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(authority.as_ref());
+    data.resize(45 + 1024, 0);
+
+    let mut account = rent_exempt_account(data.len(), &BPF_LOADER_UPGRADEABLE_ID);
+    account.data = data;
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new_readonly(program_data_key, false)],
+            data: instruction_data(crate::ProgramInstruction::ParseProgramData),
+        },
+        vec![(program_data_key, account)],
+        authority,
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::CheckFeature` instruction: a runtime feature account
+/// activated at `FEATURE_ACTIVATION_SLOT`.
+fn generate_feature_account(program_id: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let feature_key = Pubkey::new_unique();
+
+    // bincode layout of `Feature`: Option<u64> activation slot
+    let data = [&[1], &FEATURE_ACTIVATION_SLOT.to_le_bytes()[..]].concat();
+    let mut account = rent_exempt_account(data.len(), &FEATURE_PROGRAM_ID);
+    account.data = data;
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new_readonly(feature_key, false)],
+            data: instruction_data(crate::ProgramInstruction::CheckFeature),
+        },
+        vec![(feature_key, account)],
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::ReadAlignment` instruction: an account holding a
+/// `u128` and a `u64` field at `offset`, and returns the value written by the
+/// instruction.
+fn generate_read_alignment(
+    program_id: Pubkey,
+    offset: u8,
+    strategy: u8,
+) -> (Instruction, Vec<(Pubkey, Account)>, u64) {
+    let data_key = Pubkey::new_unique();
+    let large = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+    let small = 0x0f1e_2d3c_4b5a_6978u64;
+
+    let data = [
+        &vec![0; offset as usize][..],
+        &large.to_le_bytes(),
+        &small.to_le_bytes(),
+    ]
+    .concat();
+    let mut account = rent_exempt_account(data.len(), &program_id);
+    account.data = data;
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new_readonly(data_key, false)],
+            data: instruction_data(ProgramInstruction::ReadAlignment { offset, strategy }),
+        },
+        vec![(data_key, account)],
+        (large as u64).wrapping_add(small),
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::ZeroData` instruction: a program-owned account of `len`
+/// non-zero bytes.
+fn generate_zero_data(
+    program_id: Pubkey,
+    len: u32,
+    strategy: u8,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let key = Pubkey::new_unique();
+    let lamports = Rent::default().minimum_balance(len as usize);
+    let mut account = Account::new(lamports, len as usize, &program_id);
+    account.data.fill(0xff);
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(key, false)],
+            data: instruction_data(ProgramInstruction::ZeroData { len, strategy }),
+        },
+        vec![(key, account)],
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::CopyAccountData` instruction: a source account of `len`
+/// patterned bytes and a zeroed, program-owned destination account of the same
+/// length. Returns the copied bytes as well.
+fn generate_copy_account_data(
+    program_id: Pubkey,
+    len: u32,
+    strategy: u8,
+) -> (Instruction, Vec<(Pubkey, Account)>, Vec<u8>) {
+    let source_key = Pubkey::new_unique();
+    let destination_key = Pubkey::new_unique();
+    let lamports = Rent::default().minimum_balance(len as usize);
+
+    let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+    let mut source = Account::new(lamports, len as usize, &program_id);
+    source.data = data.clone();
+    let destination = Account::new(lamports, len as usize, &program_id);
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(source_key, false),
+                AccountMeta::new(destination_key, false),
+            ],
+            data: instruction_data(ProgramInstruction::CopyAccountData { len, strategy }),
+        },
+        vec![(source_key, source), (destination_key, destination)],
+        data,
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::GetEpochStake` instruction: no account for the total
+/// stake (`vote_account` 0), or a vote account.
+fn generate_epoch_stake(
+    program_id: Pubkey,
+    vote_account: u8,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let instruction = Instruction {
+        program_id,
+        accounts: Vec::new(),
+        data: instruction_data(ProgramInstruction::GetEpochStake { vote_account }),
+    };
+    if vote_account == 0 {
+        return (instruction, Vec::new());
+    }
+
+    let vote_key = Pubkey::new_unique();
+    let account = rent_exempt_account(0, &solana_program::vote::program::ID);
+    (
+        Instruction {
+            accounts: vec![AccountMeta::new_readonly(vote_key, false)],
+            ..instruction
+        },
+        vec![(vote_key, account)],
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::MatchProgramId` instruction: the Memo program account,
+/// whose key is the last known program id to be compared.
+fn generate_match_program_id(
+    program_id: Pubkey,
+    strategy: u8,
+    bound: Option<Bound>,
+) -> (Instruction, Vec<(Pubkey, Account)>, Option<usize>) {
+    // The first known program id at best, and an unknown one, compared to
+    // every known id, at worst.
+    let (key, position) = match bound {
+        None => (MEMO_PROGRAM_ID, Some(MEMO_PROGRAM_ID_POSITION)),
+        Some(Bound::Best) => (solana_system_interface::program::ID, Some(0)),
+        Some(Bound::Worst) => (Pubkey::new_unique(), None),
+    };
+    let accounts = vec![(key, rent_exempt_account(0, &program_id))];
+    let account_metas = vec![AccountMeta::new_readonly(key, false)];
+
+    (
+        Instruction {
+            program_id,
+            accounts: account_metas,
+            data: instruction_data(crate::ProgramInstruction::MatchProgramId { strategy }),
+        },
+        accounts,
+        position,
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::ProcessRemaining` instruction: `fixed` accounts
+/// followed by `remaining` accounts, all owned by the program.
+fn generate_process_remaining(
+    program_id: Pubkey,
+    fixed: u8,
+    remaining: u8,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let keys = generate_pubkeys(fixed as usize + remaining as usize);
+    let lamports = Rent::default().minimum_balance(DATA_ACCOUNT_LEN);
+
+    let mut accounts = Vec::with_capacity(keys.len());
+    let mut account_metas = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        accounts.push((key, Account::new(lamports, DATA_ACCOUNT_LEN, &program_id)));
+        account_metas.push(AccountMeta::new_readonly(key, false));
+    }
+
+    (
+        Instruction {
+            program_id,
+            accounts: account_metas,
+            data: instruction_data(crate::ProgramInstruction::ProcessRemaining { fixed }),
+        },
+        accounts,
+    )
+}
+
+/// Returns the keyed Rent sysvar account holding `Rent::default()`, which is
+/// also the value Mollusk uses for its sysvar cache.
+pub fn keyed_account_for_rent_sysvar() -> (Pubkey, Account) {
+    let rent = Rent::default();
+
+    // bincode layout: u64 lamports_per_byte_year + f64 exemption_threshold +
+    // u8 burn_percent
+    let mut data = Vec::with_capacity(17);
+    data.extend_from_slice(&rent.lamports_per_byte_year.to_le_bytes());
+    data.extend_from_slice(&rent.exemption_threshold.to_le_bytes());
+    data.push(rent.burn_percent);
+
+    let mut account = Account::new(1, data.len(), &SYSVAR_PROGRAM_ID);
+    account.data = data;
+
+    (solana_program::sysvar::rent::ID, account)
+}
+
+/// Returns the keyed Clock sysvar account holding `clock`.
+pub fn keyed_account_for_clock_sysvar(clock: &Clock) -> (Pubkey, Account) {
+    // bincode layout: u64 slot + i64 epoch_start_timestamp + u64 epoch +
+    // u64 leader_schedule_epoch + i64 unix_timestamp
+    let mut data = Vec::with_capacity(40);
+    data.extend_from_slice(&clock.slot.to_le_bytes());
+    data.extend_from_slice(&clock.epoch_start_timestamp.to_le_bytes());
+    data.extend_from_slice(&clock.epoch.to_le_bytes());
+    data.extend_from_slice(&clock.leader_schedule_epoch.to_le_bytes());
+    data.extend_from_slice(&clock.unix_timestamp.to_le_bytes());
+
+    let mut account = Account::new(1, data.len(), &SYSVAR_PROGRAM_ID);
+    account.data = data;
+
+    (solana_program::sysvar::clock::ID, account)
+}
+
+/// Generates the instruction data and accounts for the sysvar instructions:
+/// the sysvar account for the `*FromAccount` variants, none otherwise.
+pub fn generate_sysvar(
+    program_id: Pubkey,
+    instruction: ProgramInstruction,
+    clock: &Clock,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let accounts = match instruction {
+        ProgramInstruction::ClockFromAccount => vec![keyed_account_for_clock_sysvar(clock)],
+        ProgramInstruction::RentFromAccount => vec![keyed_account_for_rent_sysvar()],
+        _ => Vec::new(),
+    };
+    let account_metas = accounts
+        .iter()
+        .map(|(key, _)| AccountMeta::new_readonly(*key, false))
+        .collect();
+
+    (
+        Instruction {
+            program_id,
+            accounts: account_metas,
+            data: instruction_data(instruction),
+        },
+        accounts,
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::CheckRentExempt` instruction.
+fn generate_check_rent_exempt(
+    program_id: Pubkey,
+    count: u64,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let keys = generate_pubkeys(count as usize);
+    let lamports = Rent::default().minimum_balance(DATA_ACCOUNT_LEN);
+
+    let (rent_id, rent_account) = keyed_account_for_rent_sysvar();
+
+    let mut accounts = Vec::with_capacity(keys.len() + 1);
+    let mut account_metas = Vec::with_capacity(keys.len() + 1);
+    accounts.push((rent_id, rent_account));
+    account_metas.push(AccountMeta::new_readonly(rent_id, false));
+
+    for key in keys {
+        accounts.push((
+            key,
+            Account::new(
+                lamports,
+                DATA_ACCOUNT_LEN,
+                &solana_system_interface::program::ID,
+            ),
+        ));
+        account_metas.push(AccountMeta::new_readonly(key, false));
+    }
+
+    (
+        Instruction {
+            program_id,
+            accounts: account_metas,
+            data: instruction_data(crate::ProgramInstruction::CheckRentExempt),
+        },
+        accounts,
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::ReadModifyWrite` instruction.
+fn generate_read_modify_write(program_id: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let keys = generate_pubkeys(2);
+    let [state_key, authority] = keys.as_slice() else {
+        panic!()
+    };
+
+    let mut data = Vec::with_capacity(STATE_LEN);
+    data.extend_from_slice(&41u64.to_le_bytes());
+    data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+    data.extend_from_slice(authority.as_ref());
+    data.resize(STATE_LEN, 7);
+
+    let lamports = Rent::default().minimum_balance(STATE_LEN);
+    let mut state_account = Account::new(lamports, STATE_LEN, &program_id);
+    state_account.data = data;
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(*state_key, false)],
+            data: instruction_data(crate::ProgramInstruction::ReadModifyWrite),
+        },
+        vec![(*state_key, state_account)],
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::VerifyMerkleProof` instruction: a tree account storing
+/// the root and a proof account holding a leaf and `depth` sibling nodes,
+/// hashed up in sorted pairs.
+fn generate_merkle_proof(program_id: Pubkey, depth: u8) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let [tree_key, proof_key] = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    let leaf = solana_program::hash::hash(b"eisodos").to_bytes();
+    let mut proof = leaf.to_vec();
+    let mut node = leaf;
+    for sibling in generate_pubkeys(depth as usize) {
+        let sibling = sibling.to_bytes();
+        let (left, right) = if node <= sibling {
+            (node, sibling)
+        } else {
+            (sibling, node)
+        };
+        node = solana_program::hash::hashv(&[&left, &right]).to_bytes();
+        proof.extend_from_slice(&sibling);
+    }
+
+    let lamports = Rent::default().minimum_balance(32);
+    let mut tree_account = Account::new(lamports, 32, &program_id);
+    tree_account.data = node.to_vec();
+    let lamports = Rent::default().minimum_balance(proof.len());
+    let mut proof_account = Account::new(lamports, proof.len(), &program_id);
+    proof_account.data = proof;
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(tree_key, false),
+                AccountMeta::new_readonly(proof_key, false),
+            ],
+            data: instruction_data(crate::ProgramInstruction::VerifyMerkleProof { depth }),
+        },
+        vec![(tree_key, tree_account), (proof_key, proof_account)],
+    )
+}
+
+/// Appends `leaf` to the concurrent merkle tree in `data`, as the programs'
+/// `AppendLeaf` does.
+pub fn append_leaf(data: &mut [u8], leaf: [u8; 32]) {
+    let read_u64 = |data: &[u8], offset: usize| {
+        u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+    };
+    let index = read_u64(data, 0);
+    let active_index = (read_u64(data, 8) as usize + 1) % MERKLE_TREE_BUFFER_SIZE;
+    let change_log = CHANGE_LOGS_OFFSET + active_index * CHANGE_LOG_LEN;
+
+    let mut node = leaf;
+    let mut empty = [0u8; 32];
+    for level in 0..MERKLE_TREE_DEPTH {
+        data[change_log + 32 * (level + 1)..][..32].copy_from_slice(&node);
+        let sibling = 16 + 32 * level;
+        if (index >> level) & 1 == 1 {
+            node = solana_program::hash::hashv(&[&data[sibling..sibling + 32], &node]).to_bytes();
+        } else {
+            data[sibling..sibling + 32].copy_from_slice(&node);
+            node = solana_program::hash::hashv(&[&node, &empty]).to_bytes();
+        }
+        empty = solana_program::hash::hashv(&[&empty, &empty]).to_bytes();
+    }
+
+    data[change_log..change_log + 32].copy_from_slice(&node);
+    data[change_log + 32 * (MERKLE_TREE_DEPTH + 1)..][..4]
+        .copy_from_slice(&(index as u32).to_le_bytes());
+    data[8..16].copy_from_slice(&(active_index as u64).to_le_bytes());
+    data[0..8].copy_from_slice(&(index + 1).to_le_bytes());
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::AppendLeaf` instruction, appending to a concurrent
+/// merkle tree already holding `leaves` leaves.
+fn generate_append_leaf(program_id: Pubkey, leaves: u64) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let tree_key = Pubkey::new_unique();
+
+    let mut data = vec![0; MERKLE_TREE_LEN];
+    for index in 0..leaves {
+        let leaf = solana_program::hash::hash(&index.to_le_bytes()).to_bytes();
+        append_leaf(&mut data, leaf);
+    }
+
+    let lamports = Rent::default().minimum_balance(MERKLE_TREE_LEN);
+    let mut tree_account = Account::new(lamports, MERKLE_TREE_LEN, &program_id);
+    tree_account.data = data;
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(tree_key, false)],
+            data: instruction_data(crate::ProgramInstruction::AppendLeaf {
+                leaf: solana_program::hash::hash(&leaves.to_le_bytes()).to_bytes(),
+            }),
+        },
+        vec![(tree_key, tree_account)],
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::CheckMultisig` instruction: a multisig account storing
+/// a full signer set, followed by the last `m` of its first `n` signers, which
+/// are the last to be matched.
+fn generate_check_multisig(
+    program_id: Pubkey,
+    m: u8,
+    n: u8,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let multisig_key = Pubkey::new_unique();
+    let signer_set = generate_pubkeys(MAX_SIGNERS);
+
+    let data_len = MAX_SIGNERS * 32;
+    let lamports = Rent::default().minimum_balance(data_len);
+    let mut multisig_account = Account::new(lamports, data_len, &program_id);
+    multisig_account.data = signer_set.iter().flat_map(|key| key.to_bytes()).collect();
+
+    let mut accounts = vec![(multisig_key, multisig_account)];
+    let mut account_metas = vec![AccountMeta::new_readonly(multisig_key, false)];
+
+    for key in &signer_set[(n - m) as usize..n as usize] {
+        accounts.push((
+            *key,
+            rent_exempt_account(0, &solana_system_interface::program::ID),
+        ));
+        account_metas.push(AccountMeta::new_readonly(*key, true));
+    }
+
+    (
+        Instruction {
+            program_id,
+            accounts: account_metas,
+            data: instruction_data(crate::ProgramInstruction::CheckMultisig { m, n }),
+        },
+        accounts,
+    )
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::ParseToken2022Extensions` instruction: an initialized
+/// Token-2022 token account with the `ImmutableOwner`, `TransferFeeAmount`,
+/// `MemoTransfer` and `CpiGuard` extensions.
+fn generate_token_2022_account(
+    program_id: Pubkey,
+    bound: Option<Bound>,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let keys = generate_pubkeys(3);
+    let [token_key, mint, owner] = keys.as_slice() else {
+        panic!()
+    };
+
+    // Base token account: mint, owner, amount, delegate, state (initialized),
+    // is_native, delegated_amount and close_authority.
+    let mut data = Vec::with_capacity(256);
+    data.extend_from_slice(mint.as_ref());
+    data.extend_from_slice(owner.as_ref());
+    data.extend_from_slice(&1_000_000u64.to_le_bytes());
+    data.resize(108, 0);
+    data.push(1);
+    data.resize(165, 0);
+    // Account type
+    data.push(2);
+
+    let withheld_amount = TOKEN_2022_WITHHELD_AMOUNT.to_le_bytes();
+    let extensions: Vec<(u16, &[u8])> = match bound {
+        None => vec![(7, &[]), (2, &withheld_amount), (8, &[1]), (11, &[0])],
+        // Only the `TransferFeeAmount` extension
+        Some(Bound::Best) => vec![(2, &withheld_amount)],
+        // Every account extension, `TransferFeeAmount` last
+        Some(Bound::Worst) => vec![
+            (5, &[0; 295]),
+            (7, &[]),
+            (8, &[1]),
+            (11, &[0]),
+            (13, &[]),
+            (15, &[0]),
+            (16, &[0; 64]),
+            (2, &withheld_amount),
+        ],
+    };
+    for (extension_type, value) in extensions {
+        data.extend_from_slice(&extension_type.to_le_bytes());
+        data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        data.extend_from_slice(value);
+    }
+
+    let lamports = Rent::default().minimum_balance(data.len());
+    let mut token_account = Account::new(lamports, data.len(), &TOKEN_2022_PROGRAM_ID);
+    token_account.data = data;
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new_readonly(*token_key, false)],
+            data: instruction_data(crate::ProgramInstruction::ParseToken2022Extensions),
+        },
+        vec![(*token_key, token_account)],
+    )
+}
+
+/// Appends a borsh string padded to `len` bytes with null characters, as
+/// Token Metadata stores names, symbols and URIs.
+fn push_padded_string(data: &mut Vec<u8>, value: &str, len: usize) {
+    data.extend_from_slice(&(len as u32).to_le_bytes());
+    data.extend_from_slice(value.as_bytes());
+    data.resize(data.len() + len - value.len(), 0);
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::ParseMetadata` instruction: a `MetadataV1` account
+/// with three creators and a verified collection, the key of which is
+/// returned. The best case has none of the optional fields, and the worst case
+/// all of them, with the maximum of five creators.
+fn generate_metadata_account(
+    program_id: Pubkey,
+    bound: Option<Bound>,
+) -> (Instruction, Vec<(Pubkey, Account)>, Option<Pubkey>) {
+    let keys = generate_pubkeys(9);
+    let [metadata_key, update_authority, mint, collection, creators @ ..] = keys.as_slice() else {
+        panic!()
+    };
+    let (creators, shares, collection): (&[Pubkey], &[u8], _) = match bound {
+        None => (&creators[..3], &[50, 30, 20], Some(*collection)),
+        Some(Bound::Best) => (&[], &[], None),
+        Some(Bound::Worst) => (creators, &[20; 5], Some(*collection)),
+    };
+
+    let mut data = Vec::with_capacity(METADATA_ACCOUNT_LEN);
+    // Key (MetadataV1), update authority and mint
+    data.push(4);
+    data.extend_from_slice(update_authority.as_ref());
+    data.extend_from_slice(mint.as_ref());
+    // Name, symbol, URI and seller fee basis points
+    push_padded_string(&mut data, "Eisodos #1", 32);
+    push_padded_string(&mut data, "EIS", 10);
+    push_padded_string(&mut data, "https://example.com/eisodos/1.json", 200);
+    data.extend_from_slice(&500u16.to_le_bytes());
+    // Creators: address, verified and share
+    if creators.is_empty() {
+        data.push(0);
+    } else {
+        data.push(1);
+        data.extend_from_slice(&(creators.len() as u32).to_le_bytes());
+        for (creator, share) in creators.iter().zip(shares) {
+            data.extend_from_slice(creator.as_ref());
+            data.push(1);
+            data.push(*share);
+        }
+    }
+    // Primary sale happened, is mutable, edition nonce and token standard
+    // (NonFungible)
+    match bound {
+        Some(Bound::Best) => data.extend_from_slice(&[1, 1, 0, 0]),
+        _ => data.extend_from_slice(&[1, 1, 1, 255, 1, 0]),
+    }
+    // Verified collection
+    match collection {
+        Some(collection) => {
+            data.extend_from_slice(&[1, 1]);
+            data.extend_from_slice(collection.as_ref());
+        }
+        None => data.push(0),
+    }
+    // Uses: method (Multiple), remaining and total
+    match bound {
+        Some(Bound::Worst) => {
+            data.extend_from_slice(&[1, 1]);
+            data.extend_from_slice(&5u64.to_le_bytes());
+            data.extend_from_slice(&10u64.to_le_bytes());
+        }
+        _ => data.push(0),
+    }
+    data.resize(METADATA_ACCOUNT_LEN, 0);
+
+    let lamports = Rent::default().minimum_balance(METADATA_ACCOUNT_LEN);
+    let mut metadata_account =
+        Account::new(lamports, METADATA_ACCOUNT_LEN, &TOKEN_METADATA_PROGRAM_ID);
+    metadata_account.data = data;
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new_readonly(*metadata_key, false)],
+            data: instruction_data(crate::ProgramInstruction::ParseMetadata),
+        },
+        vec![(*metadata_key, metadata_account)],
+        collection,
+    )
+}
+
+/// Generates the instruction data and accounts for the `WriteState`
+/// instructions: a zeroed program state account.
+fn generate_write_state(
+    program_id: Pubkey,
+    ix_variant: ProgramInstruction,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let state_key = Pubkey::new_unique();
+    let lamports = Rent::default().minimum_balance(STATE_LEN);
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(state_key, false)],
+            data: instruction_data(ix_variant),
+        },
+        vec![(state_key, Account::new(lamports, STATE_LEN, &program_id))],
+    )
+}
+
+/// Returns the sample state the `WriteState` instructions write: counter `42`,
+/// timestamp `1_700_000_000`, the program id as authority and a payload of
+/// `7`s.
+pub fn sample_state(program_id: &Pubkey) -> Vec<u8> {
+    let mut data = Vec::with_capacity(STATE_LEN);
+    data.extend_from_slice(&42u64.to_le_bytes());
+    data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+    data.extend_from_slice(program_id.as_ref());
+    data.resize(STATE_LEN, 7);
+    data
+}
+
+/// Returns the state expected after `ReadModifyWrite` executes on `data`: the
+/// counter is incremented and the timestamp set to the clock's.
+pub fn modified_state(data: &[u8], unix_timestamp: i64) -> Vec<u8> {
+    let mut expected = data.to_vec();
+    let counter = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    expected[0..8].copy_from_slice(&(counter + 1).to_le_bytes());
+    expected[8..16].copy_from_slice(&unix_timestamp.to_le_bytes());
+    expected
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::CreateAccount` instruction, without the system program
+/// account appended by [`BenchCase::with_cpi`].
 fn generate_create_account(program_id: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>) {
     let keys = generate_pubkeys(2);
     let [key1, key2] = keys.as_slice() else {
         panic!()
     };
 
-    let (system_program_id, system_program_account) = keyed_account_for_system_program();
-
     let accounts = vec![
-        (
-            *key1,
-            Account::new(BASE_LAMPORTS, 0, &solana_system_interface::program::ID),
-        ),
+        (*key1, funded_payer()),
         // account being created, starts with 0 lamports and no data
-        (
-            *key2,
-            Account::new(0, 0, &solana_system_interface::program::ID),
-        ),
-        (system_program_id, system_program_account),
+        (*key2, empty_account()),
     ];
 
-    let account_metas = vec![
-        AccountMeta::new(*key1, true),
-        AccountMeta::new(*key2, true),
-        AccountMeta::new_readonly(system_program_id, false),
-    ];
+    let account_metas = vec![AccountMeta::new(*key1, true), AccountMeta::new(*key2, true)];
 
     (
         Instruction {
@@ -218,34 +1437,40 @@ fn generate_create_account(program_id: Pubkey) -> (Instruction, Vec<(Pubkey, Acc
     )
 }
 
+/// Returns the system program instruction invoked by the `CreateAccount`
+/// benchmark of `program_id` on its two accounts.
+pub fn create_account_cpi(program_id: &Pubkey, from: &Pubkey, to: &Pubkey) -> Instruction {
+    solana_system_interface::instruction::create_account(
+        from,
+        to,
+        CREATE_ACCOUNT_LAMPORTS,
+        10,
+        program_id,
+    )
+}
+
+/// Returns the system program instruction invoked by the `Transfer` benchmark
+/// on its two accounts.
+pub fn transfer_cpi(from: &Pubkey, to: &Pubkey) -> Instruction {
+    solana_system_interface::instruction::transfer(from, to, TRANSFER_LAMPORTS)
+}
+
 /// Generates the instruction data and accounts for the
-/// `ProgramInstruction::Transfer` instruction.
+/// `ProgramInstruction::Transfer` instruction, without the system program
+/// account appended by [`BenchCase::with_cpi`].
 fn generate_transfer(program_id: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>) {
     let keys = generate_pubkeys(2);
     let [key1, key2] = keys.as_slice() else {
         panic!()
     };
 
-    let (system_program_id, system_program_account) = keyed_account_for_system_program();
-
     let accounts = vec![
-        (
-            *key1,
-            Account::new(BASE_LAMPORTS, 0, &solana_system_interface::program::ID),
-        ),
+        (*key1, funded_payer()),
         // account receiving the transfer, so it starts with 0 lamports
-        (
-            *key2,
-            Account::new(0, 0, &solana_system_interface::program::ID),
-        ),
-        (system_program_id, system_program_account),
+        (*key2, empty_account()),
     ];
 
-    let account_metas = vec![
-        AccountMeta::new(*key1, true),
-        AccountMeta::new(*key2, true),
-        AccountMeta::new_readonly(system_program_id, false),
-    ];
+    let account_metas = vec![AccountMeta::new(*key1, true), AccountMeta::new(*key2, true)];
 
     (
         Instruction {
@@ -257,12 +1482,78 @@ fn generate_transfer(program_id: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>
     )
 }
 
+/// Data length of the `ProgramInstruction::OwnerRoundTrip` account.
+pub const ROUND_TRIP_DATA_LEN: usize = 1024;
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::OwnerRoundTrip` instruction: a system-owned account
+/// with zeroed data, without the system program account appended by
+/// [`BenchCase::with_cpi`].
+pub fn generate_owner_round_trip(program_id: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let key = Pubkey::new_unique();
+    let accounts = vec![(
+        key,
+        rent_exempt_account(ROUND_TRIP_DATA_LEN, &solana_system_interface::program::ID),
+    )];
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(key, true)],
+            data: instruction_data(ProgramInstruction::OwnerRoundTrip),
+        },
+        accounts,
+    )
+}
+
+/// Byte of every seed of the `ProgramInstruction::CpiSignedSeeds` signer,
+/// whose seeds only differ by length.
+const SIGNER_SEED_BYTE: u8 = 0x5e;
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::CpiSignedSeeds` instruction: the program derived
+/// address of `num_seeds` seeds of `seed_len` bytes transferring to a new
+/// account, without the system program account appended by
+/// [`BenchCase::with_cpi`].
+pub fn generate_cpi_signed_seeds(
+    program_id: Pubkey,
+    num_seeds: u8,
+    seed_len: u8,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let seed = vec![SIGNER_SEED_BYTE; seed_len as usize];
+    let seeds = vec![seed.as_slice(); num_seeds as usize];
+    let (from, bump) = Pubkey::find_program_address(&seeds, &program_id);
+    let to = Pubkey::new_unique();
+
+    let accounts = vec![
+        (from, funded_payer()),
+        // account receiving the transfer, so it starts with 0 lamports
+        (to, empty_account()),
+    ];
+
+    // The address signs through its seeds, not the transaction.
+    let account_metas = vec![AccountMeta::new(from, false), AccountMeta::new(to, false)];
+
+    (
+        Instruction {
+            program_id,
+            accounts: account_metas,
+            data: instruction_data(ProgramInstruction::CpiSignedSeeds {
+                num_seeds,
+                seed_len,
+                bump,
+            }),
+        },
+        accounts,
+    )
+}
+
 /// Generates the instruction data and accounts for the SlotHashes instructions
 /// (SDK version).
 fn generate_sdk_slot_hashes_ix(
     program_id: Pubkey,
     ix_type: ProgramInstruction,
-    strategy: DecrementStrategy,
+    strategy: &GapDistribution,
 ) -> (Instruction, Vec<(Pubkey, Account)>) {
     // Use the well-known ID directly to avoid sdk dependency
     let sysvar_id = solana_pubkey::Pubkey::new_from_array([
@@ -273,16 +1564,7 @@ fn generate_sdk_slot_hashes_ix(
     // Generate realistic mock SlotHashes data
     let mock_entries_raw = generate_mock_slot_hashes_data(strategy);
 
-    // Manually serialize mock data according to layout: u64 len + [(u64 slot, [u8;
-    // 32] hash)] (Using u64 len for consistency, as prefix didn't cause the
-    // UnsupportedSysvar error)
-    let num_entries = mock_entries_raw.len() as u64;
-    let mut data = Vec::with_capacity(8 + mock_entries_raw.len() * (8 + 32)); // Use 8 for u64 len
-    data.extend_from_slice(&(num_entries as u64).to_le_bytes());
-    for (slot, hash) in &mock_entries_raw {
-        data.extend_from_slice(&slot.to_le_bytes());
-        data.extend_from_slice(hash);
-    }
+    let data = slot_hashes_data(&mock_entries_raw);
 
     // Create the sysvar account owned by the Sysvar Program ID
     let mut sysvar_account = Account::new(1, data.len(), &SYSVAR_PROGRAM_ID);
@@ -309,7 +1591,7 @@ fn generate_pinocchio_slot_hashes_ix(
     program_id: Pubkey,
     // ix_type now includes the target slot if needed
     ix_variant: ProgramInstruction, 
-    strategy: DecrementStrategy,
+    strategy: &GapDistribution,
     // Pass the specific target_slot value IF the instruction needs it.
     // We'll determine this target_slot in the runner.rs logic.
     // Let's keep the function signature simpler for now and derive target inside if needed,
@@ -324,13 +1606,7 @@ fn generate_pinocchio_slot_hashes_ix(
     // Generate realistic mock SlotHashes data - needed for account setup
     let mock_entries = generate_mock_slot_hashes_data(strategy);
 
-    let num_entries = mock_entries.len() as u64;
-    let mut account_data = Vec::with_capacity(8 + mock_entries.len() * (8 + 32)); 
-    account_data.extend_from_slice(&(num_entries as u64).to_le_bytes());
-    for (slot, hash) in &mock_entries {
-        account_data.extend_from_slice(&slot.to_le_bytes());
-        account_data.extend_from_slice(hash);
-    }
+    let account_data = slot_hashes_data(&mock_entries);
 
     // Create the sysvar account 
     let mut sysvar_account = Account::new(1, account_data.len(), &SYSVAR_PROGRAM_ID);
@@ -352,3 +1628,64 @@ fn generate_pinocchio_slot_hashes_ix(
         accounts,
     )
 }
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::StakeHistoryPosition` instruction: a full StakeHistory
+/// sysvar with one entry per epoch, counting down from
+/// `BENCH_STAKE_HISTORY_START_EPOCH`, in which `epoch` is looked up.
+fn generate_stake_history(program_id: Pubkey, epoch: u64) -> (Instruction, Vec<(Pubkey, Account)>) {
+    // Layout: u64 len + [(u64 epoch, u64 effective, u64 activating, u64
+    // deactivating)]
+    let num_entries = NUM_BENCH_STAKE_HISTORY_ENTRIES as u64;
+    let mut data = Vec::with_capacity(8 + NUM_BENCH_STAKE_HISTORY_ENTRIES * 32);
+    data.extend_from_slice(&num_entries.to_le_bytes());
+    for i in 0..num_entries {
+        let entry_epoch = BENCH_STAKE_HISTORY_START_EPOCH - i;
+        data.extend_from_slice(&entry_epoch.to_le_bytes());
+        data.extend_from_slice(&(entry_epoch * 1_000_000).to_le_bytes());
+        data.extend_from_slice(&(entry_epoch * 1_000).to_le_bytes());
+        data.extend_from_slice(&entry_epoch.to_le_bytes());
+    }
+
+    let mut sysvar_account = Account::new(1, data.len(), &SYSVAR_PROGRAM_ID);
+    sysvar_account.data = data;
+    let sysvar_id = solana_program::sysvar::stake_history::ID;
+
+    (
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new_readonly(sysvar_id, false)],
+            data: instruction_data(ProgramInstruction::StakeHistoryPosition { epoch }),
+        },
+        vec![(sysvar_id, sysvar_account)],
+    )
+}
+
+/// Returns the epoch of the StakeHistory entry at `index`, as generated by
+/// `generate_stake_history`.
+pub fn stake_history_epoch(index: usize) -> u64 {
+    BENCH_STAKE_HISTORY_START_EPOCH - index as u64
+}
+
+/// Generates the instruction data and accounts for the
+/// `ProgramInstruction::PseudoRandom` instruction, reusing the SlotHashes
+/// fixture, and returns the last value drawn by the instruction.
+fn generate_pseudo_random(
+    program_id: Pubkey,
+    count: u8,
+) -> (Instruction, Vec<(Pubkey, Account)>, Option<u64>) {
+    let strategy = &GapDistribution::Fixed(1);
+    let (instruction, accounts) = generate_sdk_slot_hashes_ix(
+        program_id,
+        ProgramInstruction::PseudoRandom { count },
+        strategy,
+    );
+
+    let (_, seed) = generate_mock_slot_hashes_data(strategy)[0];
+    let value = count.checked_sub(1).map(|index| {
+        let hash = solana_keccak_hasher::hashv(&[&seed, &[index]]).to_bytes();
+        u64::from_le_bytes(hash[..8].try_into().unwrap())
+    });
+
+    (instruction, accounts, value)
+}
@@ -0,0 +1,466 @@
+//! Registry of the benchmarked programs.
+//!
+//! Every program is described by an implementation of [`BenchProgram`] listed
+//! in [`PROGRAMS`]: the benchmarks the runner generates for it follow from the
+//! instructions it declares, so adding a framework only takes an impl block.
+//! Builds of a program with other features are registered as
+//! [`FeatureBuild`]s of it, and builds against other versions of its framework
+//! (`<program>@<version>`) are benchmarked as [`VersionBuild`]s of it.
+
+use super::ProgramInstruction;
+use solana_pubkey::Pubkey;
+
+/// SlotHashes lookup benchmarked for a program, once per slot gap strategy.
+pub struct SlotHashesLookup {
+    pub instruction: ProgramInstruction,
+    /// Name of the benchmark, e.g. `GetHash (zerocopy)`.
+    pub name: &'static str,
+    /// Whether the lookup is benchmarked for a sweep of target indices, with
+    /// the target slot in its instruction data, rather than for slot 0.
+    pub indexed: bool,
+    /// Whether the lookup reads the sysvar account without checking it, with
+    /// the fixture of pinocchio's unchecked instructions.
+    pub unchecked: bool,
+}
+
+impl SlotHashesLookup {
+    const fn checked(instruction: ProgramInstruction, name: &'static str) -> Self {
+        Self {
+            instruction,
+            name,
+            indexed: false,
+            unchecked: false,
+        }
+    }
+
+    const fn unchecked(instruction: ProgramInstruction, name: &'static str, indexed: bool) -> Self {
+        Self {
+            instruction,
+            name,
+            indexed,
+            unchecked: true,
+        }
+    }
+}
+
+/// The SlotHashes lookups reading the raw sysvar data, shared by the programs
+/// implementing the checked instructions.
+const CHECKED_LOOKUPS: &[SlotHashesLookup] = &[
+    SlotHashesLookup::checked(ProgramInstruction::SlotHashesGetEntry, "GetEntry"),
+    SlotHashesLookup::checked(ProgramInstruction::SlotHashesGetHashInterpolated, "GetHash"),
+    SlotHashesLookup::checked(
+        ProgramInstruction::SlotHashesPositionInterpolated,
+        "Position",
+    ),
+];
+
+/// A benchmarked program.
+pub trait BenchProgram: Sync {
+    /// Name of the program binary in `../target/deploy`, e.g.
+    /// `eisodos_pinocchio`, which prefixes the ids of its benchmarks.
+    fn name(&self) -> &'static str;
+
+    /// Address the program is deployed at.
+    fn id(&self) -> Pubkey;
+
+    /// Whether the program implements every instruction shared by the
+    /// programs, rather than only the entrypoint and CPI ones (and its
+    /// SlotHashes lookups).
+    fn full_suite(&self) -> bool {
+        true
+    }
+
+    /// Whether the program accepts empty (`EmptyCall`) and versioned (V2)
+    /// instruction data, rather than dispatching on the tag only.
+    fn untagged_data(&self) -> bool {
+        true
+    }
+
+    /// Whether the program writes its state with borsh and bincode, not only
+    /// by hand.
+    fn serializes(&self) -> bool {
+        true
+    }
+
+    /// Whether the program implements the `Dispatch` strategies.
+    fn dispatches(&self) -> bool {
+        false
+    }
+
+    /// SlotHashes lookups implemented by the program.
+    fn slot_hashes_lookups(&self) -> &'static [SlotHashesLookup] {
+        &[]
+    }
+
+    /// Whether the program implements the lookups through the sysvar cache
+    /// (`SlotHashesPositionSysvar`) and the `StakeHistoryPosition` lookups.
+    fn sysvar_lookups(&self) -> bool {
+        false
+    }
+
+    /// Whether the program declares an allocator, so it can run `Allocate`.
+    fn allocates(&self) -> bool {
+        false
+    }
+
+    /// Whether the unchecked SlotHashes lookups log whether the slot was found.
+    fn logs_lookups(&self) -> bool {
+        false
+    }
+
+    /// Path of the program binary, if it is not built by `cargo build-sbf`
+    /// into `../target/deploy`.
+    fn artifact(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// Build of a program with other features, benchmarked under its own name
+/// with the benchmarks of its program.
+pub struct FeatureBuild {
+    pub name: &'static str,
+    pub program: &'static dyn BenchProgram,
+    pub allocates: bool,
+    pub logs_lookups: bool,
+}
+
+impl BenchProgram for FeatureBuild {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn id(&self) -> Pubkey {
+        self.program.id()
+    }
+
+    fn full_suite(&self) -> bool {
+        self.program.full_suite()
+    }
+
+    fn untagged_data(&self) -> bool {
+        self.program.untagged_data()
+    }
+
+    fn serializes(&self) -> bool {
+        self.program.serializes()
+    }
+
+    fn dispatches(&self) -> bool {
+        self.program.dispatches()
+    }
+
+    fn slot_hashes_lookups(&self) -> &'static [SlotHashesLookup] {
+        self.program.slot_hashes_lookups()
+    }
+
+    fn sysvar_lookups(&self) -> bool {
+        self.program.sysvar_lookups()
+    }
+
+    fn allocates(&self) -> bool {
+        self.allocates
+    }
+
+    fn logs_lookups(&self) -> bool {
+        self.logs_lookups
+    }
+}
+
+/// Build of a program against another version of its framework, named
+/// `<program>@<version>`, benchmarked with the benchmarks of its program
+/// except its SlotHashes lookups, which version builds leave out.
+pub struct VersionBuild {
+    pub name: &'static str,
+    pub program: &'static dyn BenchProgram,
+}
+
+impl BenchProgram for VersionBuild {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn id(&self) -> Pubkey {
+        self.program.id()
+    }
+
+    fn full_suite(&self) -> bool {
+        self.program.full_suite()
+    }
+
+    fn untagged_data(&self) -> bool {
+        self.program.untagged_data()
+    }
+
+    fn serializes(&self) -> bool {
+        self.program.serializes()
+    }
+
+    fn dispatches(&self) -> bool {
+        self.program.dispatches()
+    }
+
+    fn slot_hashes_lookups(&self) -> &'static [SlotHashesLookup] {
+        &[]
+    }
+
+    fn sysvar_lookups(&self) -> bool {
+        self.program.sysvar_lookups()
+    }
+
+    fn allocates(&self) -> bool {
+        self.program.allocates()
+    }
+
+    fn logs_lookups(&self) -> bool {
+        false
+    }
+}
+
+/// Implements the entrypoint and CPI instructions, with Anchor's account
+/// validation.
+pub struct Anchor;
+
+impl BenchProgram for Anchor {
+    fn name(&self) -> &'static str {
+        "eisodos_anchor"
+    }
+
+    fn id(&self) -> Pubkey {
+        eisodos_anchor::ID
+    }
+
+    fn full_suite(&self) -> bool {
+        false
+    }
+}
+
+/// Implements the entrypoint and CPI instructions and the SlotHashes lookups,
+/// reading the raw sysvar data like `solana-program`.
+pub struct Jiminy;
+
+impl BenchProgram for Jiminy {
+    fn name(&self) -> &'static str {
+        "eisodos_jiminy"
+    }
+
+    fn id(&self) -> Pubkey {
+        eisodos_jiminy::ID.into()
+    }
+
+    fn full_suite(&self) -> bool {
+        false
+    }
+
+    fn slot_hashes_lookups(&self) -> &'static [SlotHashesLookup] {
+        CHECKED_LOOKUPS
+    }
+}
+
+/// Has no serializer dependencies, and reads the SlotHashes account without
+/// checking it.
+pub struct Pinocchio;
+
+impl BenchProgram for Pinocchio {
+    fn name(&self) -> &'static str {
+        "eisodos_pinocchio"
+    }
+
+    fn id(&self) -> Pubkey {
+        eisodos_pinocchio::ID.into()
+    }
+
+    fn serializes(&self) -> bool {
+        false
+    }
+
+    fn dispatches(&self) -> bool {
+        true
+    }
+
+    fn slot_hashes_lookups(&self) -> &'static [SlotHashesLookup] {
+        const LOOKUPS: &[SlotHashesLookup] = &[
+            SlotHashesLookup::unchecked(
+                ProgramInstruction::SlotHashesGetEntryUnchecked,
+                "GetEntry",
+                false,
+            ),
+            SlotHashesLookup::unchecked(
+                ProgramInstruction::SlotHashesGetHashInterpolatedUnchecked,
+                "GetHashInterpolated",
+                false,
+            ),
+            SlotHashesLookup::unchecked(
+                ProgramInstruction::SlotHashesPositionInterpolatedUnchecked { target_slot: 0 },
+                "PositionInterpolated",
+                true,
+            ),
+            SlotHashesLookup::unchecked(
+                ProgramInstruction::SlotHashesPositionNaiveUnchecked { target_slot: 0 },
+                "PositionNaive",
+                true,
+            ),
+        ];
+        LOOKUPS
+    }
+}
+
+/// Pinocchio's bump allocator in place of `no_allocator!()`.
+pub const PINOCCHIO_ALLOC: FeatureBuild = FeatureBuild {
+    name: "eisodos_pinocchio_alloc",
+    program: &Pinocchio,
+    allocates: true,
+    logs_lookups: false,
+};
+
+/// Without the `bench-silent` feature, so the unchecked SlotHashes lookups log
+/// whether the slot was found.
+pub const PINOCCHIO_VERBOSE: FeatureBuild = FeatureBuild {
+    name: "eisodos_pinocchio_verbose",
+    program: &Pinocchio,
+    allocates: false,
+    logs_lookups: true,
+};
+
+/// Implements the same instructions as `anchor` in handwritten sBPF assembly,
+/// assembled by its build script instead of `cargo build-sbf`.
+pub struct SbpfAsm;
+
+impl BenchProgram for SbpfAsm {
+    fn name(&self) -> &'static str {
+        "eisodos_sbpf_asm"
+    }
+
+    fn id(&self) -> Pubkey {
+        eisodos_sbpf_asm::ID.into()
+    }
+
+    fn full_suite(&self) -> bool {
+        false
+    }
+
+    fn artifact(&self) -> Option<&'static str> {
+        Some(eisodos_sbpf_asm::ELF_PATH)
+    }
+}
+
+/// Compares its bytemuck (checked) and zerocopy SlotHashes lookups.
+pub struct SolanaNostdEntrypoint;
+
+impl BenchProgram for SolanaNostdEntrypoint {
+    fn name(&self) -> &'static str {
+        "eisodos_solana_nostd_entrypoint"
+    }
+
+    fn id(&self) -> Pubkey {
+        Pubkey::new_from_array(eisodos_solana_nostd_entrypoint::ID.to_bytes())
+    }
+
+    fn slot_hashes_lookups(&self) -> &'static [SlotHashesLookup] {
+        const LOOKUPS: &[SlotHashesLookup] = &[
+            SlotHashesLookup::checked(
+                ProgramInstruction::SlotHashesGetEntry,
+                "GetEntry (bytemuck)",
+            ),
+            SlotHashesLookup::checked(
+                ProgramInstruction::SlotHashesGetHashInterpolated,
+                "GetHash (bytemuck)",
+            ),
+            SlotHashesLookup::checked(
+                ProgramInstruction::SlotHashesPositionInterpolated,
+                "Position (bytemuck)",
+            ),
+            SlotHashesLookup::checked(
+                ProgramInstruction::SlotHashesGetEntryZerocopy,
+                "GetEntry (zerocopy)",
+            ),
+            SlotHashesLookup::checked(
+                ProgramInstruction::SlotHashesGetHashZerocopy,
+                "GetHash (zerocopy)",
+            ),
+            SlotHashesLookup::checked(
+                ProgramInstruction::SlotHashesPositionZerocopy,
+                "Position (zerocopy)",
+            ),
+        ];
+        LOOKUPS
+    }
+}
+
+/// Also implements the lookups through the sysvar cache.
+pub struct SolanaProgram;
+
+impl BenchProgram for SolanaProgram {
+    fn name(&self) -> &'static str {
+        "eisodos_solana_program"
+    }
+
+    fn id(&self) -> Pubkey {
+        eisodos_solana_program::ID
+    }
+
+    fn slot_hashes_lookups(&self) -> &'static [SlotHashesLookup] {
+        CHECKED_LOOKUPS
+    }
+
+    fn sysvar_lookups(&self) -> bool {
+        true
+    }
+}
+
+/// Implements the same instructions as `jiminy`, validating the sysvar
+/// address with Steel's account checks.
+pub struct Steel;
+
+impl BenchProgram for Steel {
+    fn name(&self) -> &'static str {
+        "eisodos_steel"
+    }
+
+    fn id(&self) -> Pubkey {
+        eisodos_steel::ID
+    }
+
+    fn full_suite(&self) -> bool {
+        false
+    }
+
+    fn slot_hashes_lookups(&self) -> &'static [SlotHashesLookup] {
+        CHECKED_LOOKUPS
+    }
+}
+
+/// Implements the entrypoint and CPI instructions as Typhoon handlers,
+/// dispatched by their tag.
+pub struct Typhoon;
+
+impl BenchProgram for Typhoon {
+    fn name(&self) -> &'static str {
+        "eisodos_typhoon"
+    }
+
+    fn id(&self) -> Pubkey {
+        eisodos_typhoon::ID.into()
+    }
+
+    fn full_suite(&self) -> bool {
+        false
+    }
+
+    fn untagged_data(&self) -> bool {
+        false
+    }
+}
+
+/// Every registered program and feature build.
+pub static PROGRAMS: [&dyn BenchProgram; 10] = [
+    &Anchor,
+    &Jiminy,
+    &Pinocchio,
+    &PINOCCHIO_ALLOC,
+    &PINOCCHIO_VERBOSE,
+    &SbpfAsm,
+    &SolanaNostdEntrypoint,
+    &SolanaProgram,
+    &Steel,
+    &Typhoon,
+];
@@ -0,0 +1,179 @@
+//! Recording and replay of benchmark scenarios.
+//!
+//! With `EISODOS_RECORD=<dir>`, the runner writes every benchmark of a program
+//! (instruction, accounts and outcome) to `<dir>/<program>.json`. With
+//! `EISODOS_REPLAY=<dir>`, it benchmarks the recorded scenarios instead of
+//! generating them, reporting any compute unit difference with the recording,
+//! so that exact benchmark inputs can be shared.
+
+use super::BenchCase;
+use eisodos::error::EisodosError;
+use mollusk_svm::Mollusk;
+use serde::{Deserialize, Serialize};
+use solana_account::Account;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use std::{fs, io, path::Path, str::FromStr};
+
+#[derive(Serialize, Deserialize)]
+struct RecordedMeta {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordedAccount {
+    pubkey: String,
+    lamports: u64,
+    data: Vec<u8>,
+    owner: String,
+    executable: bool,
+    rent_epoch: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordedScenario {
+    id: String,
+    program_id: String,
+    data: Vec<u8>,
+    metas: Vec<RecordedMeta>,
+    accounts: Vec<RecordedAccount>,
+    /// Outcome of the execution, `Ok(())` or the instruction error.
+    result: String,
+    compute_units: u64,
+}
+
+fn pubkey(value: &str) -> Result<Pubkey, EisodosError> {
+    Pubkey::from_str(value)
+        .map_err(|_| EisodosError::Spec(format!("invalid recorded pubkey `{value}`")))
+}
+
+fn path(dir: &str, name: &str) -> std::path::PathBuf {
+    Path::new(dir).join(format!("{name}.json"))
+}
+
+/// Executes every benchmark and writes it, with its outcome, to
+/// `<dir>/<name>.json`.
+pub fn record(
+    dir: &str,
+    name: &str,
+    mollusk: &Mollusk,
+    benchmark_data: &[BenchCase],
+) -> Result<(), EisodosError> {
+    let scenarios: Vec<RecordedScenario> = benchmark_data
+        .iter()
+        .map(|case| {
+            let result = mollusk.process_instruction(&case.instruction, &case.accounts);
+            RecordedScenario {
+                id: case.id.clone(),
+                program_id: case.instruction.program_id.to_string(),
+                data: case.instruction.data.clone(),
+                metas: case
+                    .instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| RecordedMeta {
+                        pubkey: meta.pubkey.to_string(),
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                    .collect(),
+                accounts: case
+                    .accounts
+                    .iter()
+                    .map(|(key, account)| RecordedAccount {
+                        pubkey: key.to_string(),
+                        lamports: account.lamports,
+                        data: account.data.clone(),
+                        owner: account.owner.to_string(),
+                        executable: account.executable,
+                        rent_epoch: account.rent_epoch,
+                    })
+                    .collect(),
+                result: format!("{:?}", result.raw_result),
+                compute_units: result.compute_units_consumed,
+            }
+        })
+        .collect();
+
+    let path = path(dir, name);
+    let write = |error| EisodosError::write(&path, error);
+    fs::create_dir_all(dir).map_err(|error| EisodosError::write(dir, error))?;
+    let json =
+        serde_json::to_vec_pretty(&scenarios).map_err(|error| write(io::Error::other(error)))?;
+    fs::write(&path, json).map_err(write)
+}
+
+/// Loads the scenarios recorded in `<dir>/<name>.json` as benchmarks, with the
+/// recorded outcome of each.
+pub fn replay(dir: &str, name: &str) -> Result<Vec<(BenchCase, String, u64)>, EisodosError> {
+    let path = path(dir, name);
+    let contents = fs::read(&path).map_err(|error| EisodosError::read(&path, error))?;
+    let scenarios: Vec<RecordedScenario> = serde_json::from_slice(&contents).map_err(|error| {
+        EisodosError::Spec(format!("invalid recording {}: {error}", path.display()))
+    })?;
+
+    scenarios
+        .into_iter()
+        .map(|scenario| {
+            let instruction = Instruction {
+                program_id: pubkey(&scenario.program_id)?,
+                accounts: scenario
+                    .metas
+                    .iter()
+                    .map(|meta| {
+                        Ok(AccountMeta {
+                            pubkey: pubkey(&meta.pubkey)?,
+                            is_signer: meta.is_signer,
+                            is_writable: meta.is_writable,
+                        })
+                    })
+                    .collect::<Result<_, EisodosError>>()?,
+                data: scenario.data,
+            };
+            let accounts = scenario
+                .accounts
+                .into_iter()
+                .map(|account| {
+                    Ok((
+                        pubkey(&account.pubkey)?,
+                        Account {
+                            lamports: account.lamports,
+                            data: account.data,
+                            owner: pubkey(&account.owner)?,
+                            executable: account.executable,
+                            rent_epoch: account.rent_epoch,
+                        },
+                    ))
+                })
+                .collect::<Result<_, EisodosError>>()?;
+
+            Ok((
+                BenchCase::new(scenario.id, instruction, accounts),
+                scenario.result,
+                scenario.compute_units,
+            ))
+        })
+        .collect()
+}
+
+/// Re-executes replayed benchmarks, reporting those whose outcome or compute
+/// units differ from the recording.
+pub fn compare(mollusk: &Mollusk, replayed: &[(BenchCase, String, u64)]) {
+    for (case, recorded_result, recorded_cus) in replayed {
+        let result = mollusk.process_instruction(&case.instruction, &case.accounts);
+        let replayed_result = format!("{:?}", result.raw_result);
+
+        if &replayed_result != recorded_result || result.compute_units_consumed != *recorded_cus {
+            tracing::warn!(
+                id = %case.id,
+                recorded_result = %recorded_result,
+                recorded_cus,
+                replayed_result = %replayed_result,
+                replayed_cus = result.compute_units_consumed,
+                "replayed benchmark differs from its recording"
+            );
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Accounts required by the instructions of the benchmarked programs.
+//!
+//! The accounts of every generated benchmark are checked against the
+//! requirements of its instruction before it is executed, so a benchmark
+//! passing the wrong accounts fails with an error naming the account instead of
+//! an unrelated program error (or, worse, a cheaper early return being
+//! measured). Requirements only cover the leading accounts with a fixed role;
+//! variable accounts and the optional result account are not checked.
+
+use super::BenchCase;
+use eisodos::error::EisodosError;
+use eisodos_instructions::VERSION_2;
+use solana_program::sysvar;
+use solana_pubkey::Pubkey;
+
+/// Role of an account of an instruction.
+pub struct AccountRole {
+    pub name: &'static str,
+    pub writable: bool,
+    pub signer: bool,
+    /// Address the account must have, e.g. a sysvar's.
+    pub address: Option<Pubkey>,
+}
+
+const fn readonly(name: &'static str) -> AccountRole {
+    AccountRole {
+        name,
+        writable: false,
+        signer: false,
+        address: None,
+    }
+}
+
+const fn writable(name: &'static str) -> AccountRole {
+    AccountRole {
+        name,
+        writable: true,
+        signer: false,
+        address: None,
+    }
+}
+
+const fn writable_signer(name: &'static str) -> AccountRole {
+    AccountRole {
+        name,
+        writable: true,
+        signer: true,
+        address: None,
+    }
+}
+
+const fn sysvar_account(name: &'static str, address: Pubkey) -> AccountRole {
+    AccountRole {
+        name,
+        writable: false,
+        signer: false,
+        address: Some(address),
+    }
+}
+
+const CREATE_ACCOUNT: &[AccountRole] = &[writable_signer("payer"), writable_signer("new account")];
+const TRANSFER: &[AccountRole] = &[writable_signer("from"), writable("to")];
+const SLOT_HASHES: &[AccountRole] = &[sysvar_account("slot hashes", sysvar::slot_hashes::ID)];
+const STATE: &[AccountRole] = &[writable("state")];
+const CLOCK: &[AccountRole] = &[sysvar_account("clock", sysvar::clock::ID)];
+const RENT: &[AccountRole] = &[sysvar_account("rent", sysvar::rent::ID)];
+const TOKEN_ACCOUNT: &[AccountRole] = &[readonly("token account")];
+const METADATA: &[AccountRole] = &[readonly("metadata")];
+const MULTISIG: &[AccountRole] = &[readonly("multisig")];
+const MERKLE_PROOF: &[AccountRole] = &[readonly("tree"), readonly("proof")];
+const APPEND_LEAF: &[AccountRole] = &[writable("tree")];
+const PROGRAM: &[AccountRole] = &[readonly("program")];
+const PROGRAM_DATA: &[AccountRole] = &[readonly("program data")];
+const FEATURE: &[AccountRole] = &[readonly("feature")];
+const STAKE_HISTORY: &[AccountRole] = &[sysvar_account("stake history", sysvar::stake_history::ID)];
+const ALIGNMENT_DATA: &[AccountRole] = &[readonly("data")];
+const OWNER_ROUND_TRIP: &[AccountRole] = &[writable_signer("account")];
+const ZERO_DATA: &[AccountRole] = &[writable("account")];
+const COPY_ACCOUNT_DATA: &[AccountRole] = &[readonly("source"), writable("destination")];
+const CPI_SIGNED_SEEDS: &[AccountRole] = &[writable("program derived address"), writable("to")];
+
+/// Returns the accounts required by the instruction of `data`, by its
+/// discriminator.
+pub fn requirements(data: &[u8]) -> &'static [AccountRole] {
+    let discriminator = match data {
+        [VERSION_2, discriminator, ..] | [discriminator, ..] => *discriminator,
+        [] => return &[],
+    };
+
+    match discriminator {
+        3 => CREATE_ACCOUNT,
+        4 => TRANSFER,
+        5..=11 | 33..=35 | 39 => SLOT_HASHES,
+        17 | 30..=32 => STATE,
+        22 => CLOCK,
+        24 => RENT,
+        25 => TOKEN_ACCOUNT,
+        26 => METADATA,
+        27 => MULTISIG,
+        28 => MERKLE_PROOF,
+        29 => APPEND_LEAF,
+        42 => PROGRAM,
+        43 => PROGRAM_DATA,
+        44 => FEATURE,
+        45 => STAKE_HISTORY,
+        46 => ALIGNMENT_DATA,
+        50 => CPI_SIGNED_SEEDS,
+        51 => OWNER_ROUND_TRIP,
+        52 => ZERO_DATA,
+        53 => COPY_ACCOUNT_DATA,
+        _ => &[],
+    }
+}
+
+/// Checks the accounts of `case` against the requirements of its instruction.
+pub fn validate(case: &BenchCase) -> Result<(), EisodosError> {
+    let metas = &case.instruction.accounts;
+
+    for (index, role) in requirements(&case.instruction.data).iter().enumerate() {
+        let Some(meta) = metas.get(index) else {
+            return Err(EisodosError::Check(format!(
+                "{}: missing account {index} ({})",
+                case.id, role.name
+            )));
+        };
+        let problem = if role.writable && !meta.is_writable {
+            Some("must be writable".to_string())
+        } else if role.signer && !meta.is_signer {
+            Some("must be a signer".to_string())
+        } else if let Some(address) = role.address.filter(|address| *address != meta.pubkey) {
+            Some(format!("must be {address}"))
+        } else if !case.accounts.iter().any(|(key, _)| *key == meta.pubkey) {
+            Some("is not provided".to_string())
+        } else {
+            None
+        };
+
+        if let Some(problem) = problem {
+            return Err(EisodosError::Check(format!(
+                "{}: account {index} ({}, {}) {problem}",
+                case.id, role.name, meta.pubkey
+            )));
+        }
+    }
+
+    Ok(())
+}
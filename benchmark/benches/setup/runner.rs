@@ -1,23 +1,800 @@
-use super::DecrementStrategy;
+use super::programs::BenchProgram;
 use super::{
-    generate_account, generate_create_account, generate_pinocchio_slot_hashes_ix,
-    generate_sdk_slot_hashes_ix, generate_transfer, instruction_data, setup, ProgramInstruction,
-    generate_mock_slot_hashes_data
+    append_leaf, create_account_cpi, empty_account, fixture, funded_payer, generate_account,
+    generate_append_leaf, generate_check_multisig, generate_check_rent_exempt,
+    generate_copy_account_data, generate_cpi_signed_seeds, generate_create_account,
+    generate_data_accounts, generate_epoch_stake, generate_feature_account,
+    generate_match_program_id, generate_merkle_proof, generate_metadata_account,
+    generate_mock_slot_hashes_data, generate_owner_round_trip, generate_pinocchio_slot_hashes_ix,
+    generate_process_remaining, generate_program_data, generate_pseudo_random,
+    generate_read_alignment, generate_read_modify_write, generate_sdk_slot_hashes_ix,
+    generate_stake_history, generate_sysvar, generate_token_2022_account, generate_transfer,
+    generate_validate_accounts, generate_write_state, generate_zero_data, hash_output,
+    instruction_data, instruction_data_v2, modified_state, position_output, programs, record,
+    rent_exempt_account, requirements, sample_state, scenarios, setup, sink, slot_gaps,
+    stake_history_epoch, transfer_cpi, value_output, BenchCase, BenchParams, Bound,
+    ProgramInstruction, Tag, FEATURE_ACTIVATION_SLOT, NUM_BENCH_STAKE_HISTORY_ENTRIES,
+    ROUND_TRIP_DATA_LEN, SYSVAR_CACHE_SLOT_GAPS, TOKEN_2022_WITHHELD_AMOUNT,
 };
-use mollusk_svm_bencher::MolluskComputeUnitBencher;
-use solana_account::Account;
+use eisodos::error::EisodosError;
+use eisodos::history;
+use eisodos::results::BenchResult;
+use mollusk_svm::{result::InstructionResult, Mollusk};
 use solana_instruction::Instruction;
-use solana_pubkey::Pubkey;
+use solana_log_collector::LogCollector;
 use solana_program::clock::Slot;
-use std::collections::HashMap;
+use solana_program::program_error::ProgramError;
+use solana_pubkey::Pubkey;
+use solana_system_interface::error::SystemError;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::Hasher;
+use std::path::Path;
+
+/// Benchmarks every registered program and feature build, in the order of
+/// [`programs::PROGRAMS`].
+pub fn run_all() {
+    for program in programs::PROGRAMS {
+        run(program);
+    }
+}
+
+/// Benchmarks `program`, exiting with the exit code of the error if its
+/// configuration, its inputs, its outputs or the history are invalid or cannot
+/// be accessed.
+pub fn run(program: &dyn BenchProgram) {
+    exit_on_error(try_run(program));
+}
+
+/// Returns `result`'s value, or prints its error and exits with its exit code
+/// (see [`EisodosError::exit_code`]), like `coyote`.
+pub fn exit_on_error<T>(result: Result<T, EisodosError>) -> T {
+    result.unwrap_or_else(|error| {
+        eprintln!("{error}");
+        std::process::exit(error.exit_code().into());
+    })
+}
+
+fn try_run(program: &dyn BenchProgram) -> Result<(), EisodosError> {
+    let program_id = &program.id();
+    let name = program.name();
+    let mut mollusk = setup(program)?;
+    let _span = tracing::info_span!("run", program = name).entered();
+
+    // Replayed scenarios replace the generated ones and are compared with
+    // their recording instead of being validated.
+    let replay = std::env::var("EISODOS_REPLAY")
+        .ok()
+        .map(|dir| record::replay(&dir, name))
+        .transpose()?;
+    let benchmark_data = match &replay {
+        Some(replayed) => {
+            record::compare(&mollusk, replayed);
+            replayed.iter().map(|(case, _, _)| case.clone()).collect()
+        }
+        None => {
+            let mut benchmark_data = cases(&mollusk, program)?;
+            if let Ok(dir) = std::env::var("EISODOS_FIXTURES") {
+                benchmark_data.extend(fixture::load(&dir, program_id)?);
+            }
+            benchmark_data
+        }
+    };
+    let benchmark_data = select_benchmark(name, benchmark_data)?;
+    let benchmark_data = shard(select_tags(scenarios::select(name, benchmark_data)?)?)?;
+    tracing::info!(
+        benchmarks = benchmark_data.len(),
+        replay = replay.is_some(),
+        "generated benchmarks"
+    );
+
+    // Programs built with the `heap-stats` feature report their heap usage
+    // instead, since the instrumentation skews their compute units.
+    if std::env::var_os("EISODOS_HEAP").is_some() {
+        return write_heap_usage(&mollusk, &benchmark_data);
+    }
+
+    if let Ok(dir) = std::env::var("EISODOS_RECORD") {
+        record::record(&dir, name, &mollusk, &benchmark_data)?;
+    }
+
+    check_unique_ids(&benchmark_data);
+    write_params(&benchmark_data)?;
+    write_cpi_split(&mollusk, &benchmark_data)?;
+    write_program_cache(&mollusk, &benchmark_data)?;
+    check_repeatable(&mollusk, &benchmark_data);
+    check_lookup_tables(&mollusk, &benchmark_data);
+    check_logs(&mut mollusk, &benchmark_data);
+    let fingerprints = fingerprint(&benchmark_data);
+
+    // Benchmarks of error paths (and replayed ones, which may fail) cannot run
+    // with `must_pass`, so every benchmark is validated beforehand instead of
+    // only those declaring an expected outcome.
+    let must_pass = replay.is_none()
+        && benchmark_data
+            .iter()
+            .all(|case| case.expected_error.is_none());
+
+    if replay.is_none() {
+        for case in benchmark_data
+            .iter()
+            .filter(|case| !must_pass || case.has_expectations())
+        {
+            mollusk.process_and_validate_instruction(
+                &case.instruction,
+                &case.accounts,
+                &case.checks(),
+            );
+        }
+    }
+
+    let timestamp = chrono::Utc::now()
+        .format("%Y-%m-%d %H:%M:%S UTC")
+        .to_string();
+    let results: Vec<BenchResult> = benchmark_data
+        .iter()
+        .map(|case| {
+            let result = process(&mollusk, case);
+            tracing::debug!(
+                id = %case.id,
+                cus = result.compute_units_consumed,
+                result = ?result.raw_result,
+                "measured benchmark"
+            );
+            assert!(
+                !must_pass || result.raw_result.is_ok(),
+                "{}: execution failed: {:?}",
+                case.id,
+                result.raw_result
+            );
+            BenchResult {
+                program: name.to_string(),
+                // Fixture ids are not prefixed with the program name.
+                scenario: case
+                    .id
+                    .strip_prefix(&format!("{name}: "))
+                    .unwrap_or(&case.id)
+                    .to_string(),
+                cus: result.compute_units_consumed,
+            }
+        })
+        .collect();
+    check_results_order(&results);
+
+    for mut result_sink in sink::from_env()? {
+        result_sink.write(&timestamp, &results)?;
+    }
+    tracing::info!(benchmarks = results.len(), "wrote results");
+    compare_baseline(&results)?;
+    write_slot_hashes_sources(&results)?;
+    write_transaction_sizes(&benchmark_data, &results)?;
+
+    assert_eq!(
+        fingerprints,
+        fingerprint(&benchmark_data),
+        "benchmark accounts were mutated while benchmarking"
+    );
+    Ok(())
+}
+
+/// Compares the results with the latest results of the commit
+/// `EISODOS_BASELINE` in the history of the `sqlite` sink, if set, reporting
+/// every benchmark consuming more than `EISODOS_BASELINE_THRESHOLD` percent
+/// (1 by default) more CUs than its baseline. Regressions fail the run if
+/// `EISODOS_BASELINE_MODE` is `fail`, and are only warned about otherwise.
+fn compare_baseline(results: &[BenchResult]) -> Result<(), EisodosError> {
+    let Ok(commit) = std::env::var("EISODOS_BASELINE") else {
+        return Ok(());
+    };
+    let threshold = match std::env::var("EISODOS_BASELINE_THRESHOLD") {
+        Ok(threshold) => threshold.parse::<f64>().map_err(|_| {
+            EisodosError::Spec(format!(
+                "invalid EISODOS_BASELINE_THRESHOLD `{threshold}`, expected a percentage"
+            ))
+        })?,
+        Err(_) => 1.0,
+    };
+    let fail = match std::env::var("EISODOS_BASELINE_MODE").as_deref() {
+        Ok("fail") => true,
+        Ok("warn") | Err(_) => false,
+        Ok(mode) => {
+            return Err(EisodosError::Spec(format!(
+                "invalid EISODOS_BASELINE_MODE `{mode}`, expected `warn` or `fail`"
+            )))
+        }
+    };
+
+    if commit.len() < history::MIN_COMMIT_LEN {
+        return Err(EisodosError::Spec(format!(
+            "EISODOS_BASELINE `{commit}` is too short, expected at least {} characters",
+            history::MIN_COMMIT_LEN
+        )));
+    }
+
+    let connection = history::open(history::HISTORY_PATH)?;
+    let baseline = history::results_at(&connection, &commit)?;
+    // Otherwise nothing would be compared, e.g. for a mistyped or compacted
+    // commit, and a failing check would pass.
+    let compared = results
+        .iter()
+        .any(|result| baseline.iter().any(|base| base.id() == result.id()));
+    if !compared {
+        if fail {
+            return Err(EisodosError::Check(format!(
+                "no baseline results at {commit}, run the baseline with the sqlite sink"
+            )));
+        }
+        tracing::warn!(%commit, "no baseline results, run the baseline with the sqlite sink");
+        return Ok(());
+    }
+
+    let regressions = history::regressions(&baseline, results, threshold);
+    for regression in &regressions {
+        tracing::warn!(
+            id = %regression.id,
+            baseline = regression.baseline,
+            cus = regression.cus,
+            "regressed by {:.2}% from {commit}",
+            regression.percent()
+        );
+    }
+    if fail && !regressions.is_empty() {
+        return Err(EisodosError::Check(format!(
+            "{} benchmarks regressed by more than {threshold}% from {commit}",
+            regressions.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Executes the instruction of `case`, preceded by its ComputeBudget
+/// instructions if any, whose compute units are deducted from the result so
+/// that only the benchmarked program is measured.
+fn process(mollusk: &Mollusk, case: &BenchCase) -> InstructionResult {
+    if case.compute_budget.is_empty() {
+        return mollusk.process_instruction(&case.instruction, &case.accounts);
+    }
+
+    let budget = mollusk
+        .process_instruction_chain(&case.compute_budget, &case.accounts)
+        .compute_units_consumed;
+    let chain = [&case.compute_budget[..], &[case.instruction.clone()]].concat();
+    let mut result = mollusk.process_instruction_chain(&chain, &case.accounts);
+    result.compute_units_consumed = result.compute_units_consumed.saturating_sub(budget);
+    result
+}
+
+/// Scenarios run back to back by [`run_chained`], by id.
+const CHAINED_SCENARIOS: [&str; 2] = ["Ping", "Transfer"];
+
+/// Executes the instructions of two different programs back to back in the
+/// same transaction, for every ordered pair of `programs` and every scenario
+/// of `CHAINED_SCENARIOS`. Every program is loaded in the same program cache,
+/// and each chain is checked to consume the compute units of its instructions
+/// executed on their own. Prepends the compute units to
+/// `../target/benches/chained.md`.
+pub fn run_chained(programs: &[&dyn BenchProgram]) {
+    let [first, ..] = programs else {
+        return;
+    };
+    let mut mollusk = exit_on_error(setup(*first));
+    for program in &programs[1..] {
+        mollusk.add_program(
+            &program.id(),
+            program.name(),
+            &mollusk_svm::program::loader_keys::LOADER_V3,
+        );
+    }
+    let _span = tracing::info_span!("run_chained").entered();
+
+    let cases: Vec<Vec<BenchCase>> = programs
+        .iter()
+        .map(|program| {
+            let cases = exit_on_error(cases(&mollusk, *program));
+            CHAINED_SCENARIOS
+                .iter()
+                .map(|scenario| {
+                    let id = format!("{}: {scenario}", program.name());
+                    cases
+                        .iter()
+                        .find(|case| case.id == id)
+                        .unwrap_or_else(|| panic!("no benchmark {id}"))
+                        .clone()
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut rows = String::new();
+    for (first, first_program) in cases.iter().zip(programs) {
+        for (second, second_program) in cases.iter().zip(programs) {
+            let (first_name, second_name) = (first_program.name(), second_program.name());
+            if first_name == second_name {
+                continue;
+            }
+            for (scenario, (first, second)) in
+                CHAINED_SCENARIOS.iter().zip(first.iter().zip(second))
+            {
+                let mut accounts = first.accounts.clone();
+                for (key, account) in &second.accounts {
+                    if !accounts.iter().any(|(existing, _)| existing == key) {
+                        accounts.push((*key, account.clone()));
+                    }
+                }
+
+                let alone = [first, second].map(|case| {
+                    let result = mollusk.process_instruction(&case.instruction, &accounts);
+                    assert!(
+                        result.raw_result.is_ok(),
+                        "{}: execution failed: {:?}",
+                        case.id,
+                        result.raw_result
+                    );
+                    result.compute_units_consumed
+                });
+                let chained = mollusk.process_instruction_chain(
+                    &[first.instruction.clone(), second.instruction.clone()],
+                    &accounts,
+                );
+                let id = format!("{first_name} + {second_name}: {scenario}");
+                assert!(
+                    chained.raw_result.is_ok(),
+                    "{id}: execution failed: {:?}",
+                    chained.raw_result
+                );
+                assert_eq!(
+                    chained.compute_units_consumed,
+                    alone[0] + alone[1],
+                    "{id}: compute units differ from the instructions executed on their own"
+                );
+                tracing::debug!(%id, cus = chained.compute_units_consumed, "measured chain");
+
+                rows.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    id, alone[0], alone[1], chained.compute_units_consumed
+                ));
+            }
+        }
+    }
+    exit_on_error(prepend_table(
+        "chained.md",
+        "Chained Compute Units",
+        "| Name | First | Second | Chained |",
+        &rows,
+    ));
+}
+
+/// Returns a hash of the accounts of every benchmark, to check that
+/// benchmarking leaves the fixtures untouched.
+fn fingerprint(benchmark_data: &[BenchCase]) -> Vec<u64> {
+    benchmark_data
+        .iter()
+        .map(|case| {
+            let mut hasher = DefaultHasher::new();
+            for (key, account) in &case.accounts {
+                hasher.write(key.as_ref());
+                hasher.write_u64(account.lamports);
+                hasher.write(&account.data);
+                hasher.write(account.owner.as_ref());
+                hasher.write_u8(account.executable as u8);
+                hasher.write_u64(account.rent_epoch);
+            }
+            hasher.finish()
+        })
+        .collect()
+}
+
+/// Checks that no two benchmarks share an id, since results are keyed by id:
+/// reports would only keep the first of them.
+fn check_unique_ids(benchmark_data: &[BenchCase]) {
+    let mut ids = HashSet::new();
+    for case in benchmark_data {
+        assert!(ids.insert(&case.id), "{}: duplicate benchmark id", case.id);
+    }
+}
+
+/// Checks that the results of the run parse back from the markdown table in
+/// the order they were measured, e.g. that no scenario contains a `|`.
+fn check_results_order(results: &[BenchResult]) {
+    let parsed = eisodos::results::parse_markdown(&eisodos::results::to_markdown(results));
+    assert_eq!(
+        parsed, results,
+        "results do not parse back from the markdown table in order"
+    );
+}
+
+/// Executes every benchmark twice from its fixtures and checks that both runs
+/// consume the same compute units and leave the same accounts, so that no
+/// benchmark depends on state left by a previous execution.
+fn check_repeatable(mollusk: &Mollusk, benchmark_data: &[BenchCase]) {
+    for case in benchmark_data {
+        let first = process(mollusk, case);
+        let second = process(mollusk, case);
+        assert_eq!(
+            first.compute_units_consumed, second.compute_units_consumed,
+            "{}: compute units differ between executions",
+            case.id
+        );
+        assert!(
+            first.resulting_accounts == second.resulting_accounts,
+            "{}: resulting accounts differ between executions",
+            case.id
+        );
+        tracing::debug!(id = %case.id, "checked repeatability");
+    }
+}
+
+/// Keeps the single benchmark selected by `EISODOS_BENCHMARK=<id>`, with or
+/// without the program prefix, if set. Ids contain commas, so unlike the other
+/// selections this is not a list.
+fn select_benchmark(
+    program: &str,
+    benchmark_data: Vec<BenchCase>,
+) -> Result<Vec<BenchCase>, EisodosError> {
+    let Ok(id) = std::env::var("EISODOS_BENCHMARK") else {
+        return Ok(benchmark_data);
+    };
+    let prefix = format!("{program}: ");
+    let id = id.strip_prefix(&prefix).unwrap_or(&id);
+
+    let selected: Vec<BenchCase> = benchmark_data
+        .into_iter()
+        .filter(|case| case.id.strip_prefix(&prefix).unwrap_or(&case.id) == id)
+        .collect();
+    // Otherwise a mistyped id would pass without running anything.
+    if selected.is_empty() {
+        return Err(EisodosError::Spec(format!(
+            "no benchmark `{id}` of {program} selected by EISODOS_BENCHMARK"
+        )));
+    }
+    Ok(selected)
+}
+
+/// Keeps the benchmarks with any of the tags selected by
+/// `EISODOS_TAGS=tag,...`, if set.
+fn select_tags(benchmark_data: Vec<BenchCase>) -> Result<Vec<BenchCase>, EisodosError> {
+    let Ok(names) = std::env::var("EISODOS_TAGS") else {
+        return Ok(benchmark_data);
+    };
+    let tags: Vec<Tag> = names
+        .split(',')
+        .map(|name| {
+            Tag::parse(name.trim()).ok_or_else(|| {
+                EisodosError::Spec(format!(
+                    "invalid EISODOS_TAGS `{names}`, unknown tag `{}`",
+                    name.trim()
+                ))
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(benchmark_data
+        .into_iter()
+        .filter(|case| case.tags.iter().any(|tag| tags.contains(tag)))
+        .collect())
+}
+
+/// Keeps the benchmarks of the shard selected by `EISODOS_SHARD=i/n` (1-based),
+/// if set. Benchmarks are assigned round-robin so that shards are
+/// deterministic and of similar size.
+fn shard(benchmark_data: Vec<BenchCase>) -> Result<Vec<BenchCase>, EisodosError> {
+    let Ok(shard) = std::env::var("EISODOS_SHARD") else {
+        return Ok(benchmark_data);
+    };
+    let (index, count) = shard
+        .split_once('/')
+        .and_then(|(index, count)| {
+            Some((index.parse::<usize>().ok()?, count.parse::<usize>().ok()?))
+        })
+        .filter(|&(index, count)| index >= 1 && index <= count)
+        .ok_or_else(|| {
+            EisodosError::Spec(format!(
+                "invalid EISODOS_SHARD `{shard}`, expected `i/n` with 1 <= i <= n"
+            ))
+        })?;
+
+    // A shard past the number of benchmarks would pass without running
+    // anything, unlike a program without any benchmark selected.
+    if !benchmark_data.is_empty() && index > benchmark_data.len() {
+        return Err(EisodosError::Spec(format!(
+            "EISODOS_SHARD `{shard}` is empty, only {} benchmarks are selected",
+            benchmark_data.len()
+        )));
+    }
+
+    tracing::info!(index, count, "selected shard");
+    Ok(benchmark_data
+        .into_iter()
+        .enumerate()
+        .filter(|(position, _)| position % count == index - 1)
+        .map(|(_, case)| case)
+        .collect())
+}
+
+/// Prepends the heap high-water mark and the allocation count of the
+/// benchmarks reporting them (as their return data) to
+/// `../target/benches/heap_usage.md` and `../target/benches/allocations.md`.
+fn write_heap_usage(mollusk: &Mollusk, benchmark_data: &[BenchCase]) -> Result<(), EisodosError> {
+    let mut rows = String::new();
+    let mut allocation_rows = String::new();
+    for case in benchmark_data {
+        let result = mollusk.process_instruction(&case.instruction, &case.accounts);
+        let Ok(stats) = <[u8; 16]>::try_from(result.return_data.as_slice()) else {
+            continue;
+        };
+        let [peak, allocations] =
+            [&stats[..8], &stats[8..]].map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()));
+        rows.push_str(&format!("| {} | {} |\n", case.id, peak));
+        allocation_rows.push_str(&format!("| {} | {} |\n", case.id, allocations));
+    }
+    prepend_table("heap_usage.md", "Heap Usage", "| Name | Bytes |", &rows)?;
+    prepend_table(
+        "allocations.md",
+        "Allocations",
+        "| Name | Allocations |",
+        &allocation_rows,
+    )
+}
+
+/// Prepends the fixture parameters of every benchmark to
+/// `../target/benches/params.md`, so results can be filtered and plotted by
+/// parameter.
+fn write_params(benchmark_data: &[BenchCase]) -> Result<(), EisodosError> {
+    let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+
+    let mut rows = String::new();
+    for case in benchmark_data {
+        let params = &case.params;
+        rows.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            case.id,
+            case.account_count(),
+            case.data_len(),
+            optional(params.sysvar_entries.map(|entries| entries.to_string())),
+            optional(params.strategy.map(str::to_string)),
+            optional(params.target_index.map(|index| index.to_string())),
+            optional(params.variants.map(|variants| variants.to_string())),
+            optional((!case.tags.is_empty()).then(|| {
+                case.tags
+                    .iter()
+                    .map(|tag| tag.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })),
+        ));
+    }
+    prepend_table(
+        "params.md",
+        "Benchmark Parameters",
+        "| Name | Accounts | Data | Entries | Strategy | Index | Variants | Tags |",
+        &rows,
+    )
+}
+
+/// Prepends the compute units of the benchmarks invoking a program through
+/// CPI, split into the benchmarked program's own ("outer") and the invoked
+/// program's, to `../target/benches/cpi.md`.
+///
+/// The split is an estimate: Mollusk reports neither the inner instructions
+/// nor their compute units, and builtin programs do not log what they
+/// consume, so the invoked cost is that of the invoked instruction executed on
+/// its own and the outer cost is the remainder.
+fn write_cpi_split(mollusk: &Mollusk, benchmark_data: &[BenchCase]) -> Result<(), EisodosError> {
+    let mut rows = String::new();
+    for case in benchmark_data {
+        let Some(cpi) = &case.cpi else {
+            continue;
+        };
+        let total = mollusk
+            .process_instruction(&case.instruction, &case.accounts)
+            .compute_units_consumed;
+        let invoked = mollusk
+            .process_instruction(cpi, &case.accounts)
+            .compute_units_consumed;
+        rows.push_str(&format!(
+            "| {} | {} | {} |\n",
+            case.id,
+            total.saturating_sub(invoked),
+            invoked
+        ));
+    }
+    prepend_table(
+        "cpi.md",
+        "CPI Compute Units (Estimated Split)",
+        "| Name | Outer (estimate) | Invoked (estimate) |",
+        &rows,
+    )
+}
+
+/// Prepends the compute units of every benchmark invoking the system program
+/// through CPI when no earlier instruction of its transaction invoked the
+/// system program (cold) and after one did (warm), with the difference, to
+/// `../target/benches/program_cache.md`. The earlier instruction is a transfer
+/// of no lamports between two other accounts, whose own compute units are
+/// deducted from the warm measurement.
+fn write_program_cache(
+    mollusk: &Mollusk,
+    benchmark_data: &[BenchCase],
+) -> Result<(), EisodosError> {
+    let (payer, recipient) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let warmup = solana_system_interface::instruction::transfer(&payer, &recipient, 0);
+
+    let mut rows = String::new();
+    for case in benchmark_data {
+        if !case
+            .cpi
+            .as_ref()
+            .is_some_and(|cpi| cpi.program_id == solana_system_interface::program::ID)
+        {
+            continue;
+        }
+        let mut accounts = case.accounts.clone();
+        accounts.push((payer, funded_payer()));
+        accounts.push((recipient, empty_account()));
+
+        let cold = process(mollusk, case).compute_units_consumed;
+        let warmup_chain = [&case.compute_budget[..], &[warmup.clone()]].concat();
+        let chain = [&warmup_chain[..], &[case.instruction.clone()]].concat();
+        let warm = mollusk
+            .process_instruction_chain(&chain, &accounts)
+            .compute_units_consumed
+            .saturating_sub(
+                mollusk
+                    .process_instruction_chain(&warmup_chain, &accounts)
+                    .compute_units_consumed,
+            );
+        rows.push_str(&format!(
+            "| {} | {} | {} | {:+} |\n",
+            case.id,
+            cold,
+            warm,
+            warm as i64 - cold as i64
+        ));
+    }
+    prepend_table(
+        "program_cache.md",
+        "Program Cache Compute Units",
+        "| Name | Cold | Warm | Delta |",
+        &rows,
+    )
+}
+
+/// Prepends the compute units of the SlotHashes `Position` lookups reading the
+/// sysvar account next to those fetching it from the sysvar cache, to
+/// `../target/benches/slot_hashes_sources.md`, if the program has both.
+fn write_slot_hashes_sources(results: &[BenchResult]) -> Result<(), EisodosError> {
+    if !results
+        .iter()
+        .any(|result| result.scenario.starts_with("Position (SysvarCache"))
+    {
+        return Ok(());
+    }
+    let rows: String = results
+        .iter()
+        .filter(|result| result.scenario.starts_with("Position ("))
+        .map(|result| format!("| {} | {} |\n", result.scenario, result.cus))
+        .collect();
+    prepend_table(
+        "slot_hashes_sources.md",
+        "SlotHashes Sources",
+        "| Lookup | CUs |",
+        &rows,
+    )
+}
+
+/// Prepends the size of the serialized legacy transaction of every benchmark
+/// (see [`BenchCase::transaction`]) and of its v0 transaction with a lookup
+/// table, if any, and the number of account keys of the legacy transaction,
+/// next to its compute units, to `../target/benches/tx_size.md`.
+fn write_transaction_sizes(
+    benchmark_data: &[BenchCase],
+    results: &[BenchResult],
+) -> Result<(), EisodosError> {
+    let mut rows = String::new();
+    for (case, result) in benchmark_data.iter().zip(results) {
+        let transaction = case.transaction();
+        let v0_size = case
+            .v0_transaction()
+            .map(|transaction| serialized_size(&case.id, &transaction).to_string());
+        rows.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            case.id,
+            result.cus,
+            serialized_size(&case.id, &transaction),
+            v0_size.unwrap_or_else(|| "-".to_string()),
+            transaction.message.account_keys.len()
+        ));
+    }
+    prepend_table(
+        "tx_size.md",
+        "Transaction Sizes",
+        "| Name | CUs | Legacy | V0 | Account Keys |",
+        &rows,
+    )
+}
+
+/// Returns the serialized size of the transaction of the benchmark `id`.
+fn serialized_size(id: &str, transaction: &impl serde::Serialize) -> u64 {
+    bincode::serialized_size(transaction)
+        .unwrap_or_else(|error| panic!("{id}: failed to serialize transaction: {error}"))
+}
+
+/// Executes the instruction of every benchmark with a v0 transaction as
+/// rebuilt from it (see [`BenchCase::v0_instruction`]) and checks that it
+/// consumes the compute units of the instruction, as loading the accounts
+/// from a lookup table must not change the execution.
+fn check_lookup_tables(mollusk: &Mollusk, benchmark_data: &[BenchCase]) {
+    for case in benchmark_data {
+        let Some(instruction) = case.v0_instruction() else {
+            continue;
+        };
+        let expected = mollusk.process_instruction(&case.instruction, &case.accounts);
+        let result = mollusk.process_instruction(&instruction, &case.accounts);
+        assert_eq!(
+            expected.compute_units_consumed, result.compute_units_consumed,
+            "{}: compute units differ with a lookup table",
+            case.id
+        );
+        tracing::debug!(id = %case.id, "checked lookup table");
+    }
+}
+
+/// Executes the benchmarks declaring expected logs with a log collector and
+/// checks that each of their messages was, or was not, logged.
+fn check_logs(mollusk: &mut Mollusk, benchmark_data: &[BenchCase]) {
+    for case in benchmark_data
+        .iter()
+        .filter(|case| !case.expected_logs.is_empty())
+    {
+        let logger = LogCollector::new_ref();
+        let previous = mollusk.logger.replace(logger.clone());
+        process(mollusk, case);
+        mollusk.logger = previous;
+
+        let logs = logger.borrow().get_recorded_content().to_vec();
+        for &(message, expected) in &case.expected_logs {
+            let logged = logs.contains(&format!("Program log: {message}"));
+            assert!(
+                logged == expected,
+                "{}: `{message}` {} logged, logs:\n{}",
+                case.id,
+                if logged { "was" } else { "was not" },
+                logs.join("\n")
+            );
+        }
+        tracing::debug!(id = %case.id, "checked logs");
+    }
+}
+
+/// Prepends a markdown table of `rows` to `file` in `../target/benches`,
+/// unless there are none.
+fn prepend_table(file: &str, title: &str, header: &str, rows: &str) -> Result<(), EisodosError> {
+    if rows.is_empty() {
+        return Ok(());
+    }
 
-pub fn run(program_id: &Pubkey, name: &'static str) {
-    let mollusk = setup(program_id, name);
-    let mut bencher = MolluskComputeUnitBencher::new(mollusk)
-        .must_pass(true)
-        .out_dir("../target/benches");
+    let dir = Path::new("../target/benches");
+    let path = dir.join(file);
+    let previous = fs::read_to_string(&path).unwrap_or_default();
+    let separator = header.replace(|c| c != '|', "-");
+    fs::create_dir_all(dir).map_err(|error| EisodosError::write(dir, error))?;
+    fs::write(
+        &path,
+        format!("#### {title}\n\n{header}\n{separator}\n{rows}\n{previous}"),
+    )
+    .map_err(|error| EisodosError::write(&path, error))
+}
 
-    let mut benchmark_data: Vec<(String, Instruction, Vec<(Pubkey, Account)>)> = Vec::new();
+/// Builds every benchmark case of `program`, failing if a slot gap
+/// distribution is invalid or a case passes the wrong accounts.
+pub fn cases(
+    mollusk: &Mollusk,
+    program: &dyn BenchProgram,
+) -> Result<Vec<BenchCase>, EisodosError> {
+    let mut benchmark_data: Vec<BenchCase> = Vec::new();
+    let program_id = &program.id();
+    let name = program.name();
 
     // Ping
     let instruction = Instruction {
@@ -25,7 +802,45 @@ pub fn run(program_id: &Pubkey, name: &'static str) {
         accounts: vec![],
         data: instruction_data(ProgramInstruction::Ping),
     };
-    benchmark_data.push((format!("{}: Ping", name), instruction, Vec::new()));
+    benchmark_data.push(
+        BenchCase::new(format!("{}: Ping", name), instruction, Vec::new()).with_tags(&[Tag::Micro]),
+    );
+
+    // EmptyCall, without the discriminator of Ping
+    if program.untagged_data() {
+        let instruction = Instruction {
+            program_id: *program_id,
+            accounts: vec![],
+            data: instruction_data(ProgramInstruction::EmptyCall),
+        };
+        benchmark_data.push(
+            BenchCase::new(format!("{}: EmptyCall", name), instruction, Vec::new())
+                .with_tags(&[Tag::Micro]),
+        );
+    }
+
+    // Ping preceded by ComputeBudget instructions, which must not change the
+    // compute units of the program
+    for (budget_name, limit, price) in [
+        ("Limit", Some(200_000), None),
+        ("Price", None, Some(10_000)),
+        ("Limit+Price", Some(200_000), Some(10_000)),
+    ] {
+        let instruction = Instruction {
+            program_id: *program_id,
+            accounts: vec![],
+            data: instruction_data(ProgramInstruction::Ping),
+        };
+        benchmark_data.push(
+            BenchCase::new(
+                format!("{}: Ping (ComputeBudget {})", name, budget_name),
+                instruction,
+                Vec::new(),
+            )
+            .with_tags(&[Tag::Micro])
+            .with_compute_budget(limit, price),
+        );
+    }
 
     // Log
     let instruction = Instruction {
@@ -33,70 +848,638 @@ pub fn run(program_id: &Pubkey, name: &'static str) {
         accounts: vec![],
         data: instruction_data(ProgramInstruction::Log),
     };
-    benchmark_data.push((format!("{}: Log", name), instruction, Vec::new()));
+    benchmark_data.push(
+        BenchCase::new(format!("{}: Log", name), instruction, Vec::new()).with_tags(&[Tag::Micro]),
+    );
 
     // Account Benchmarks
     for &num_accounts in &[1u64, 3, 5, 10, 20, 32, 64] {
         let (instruction, accounts) = generate_account(*program_id, num_accounts);
-        benchmark_data.push((
-            format!("{}: Account ({})", name, num_accounts),
+        benchmark_data.push(
+            BenchCase::new(
+                format!("{}: Account ({})", name, num_accounts),
+                instruction,
+                accounts,
+            )
+            .with_tags(&[Tag::Micro]),
+        );
+    }
+
+    // Versioned (V2) instruction data
+    if program.untagged_data() {
+        let instruction = Instruction {
+            program_id: *program_id,
+            accounts: vec![],
+            data: instruction_data_v2(ProgramInstruction::Ping),
+        };
+        benchmark_data.push(
+            BenchCase::new(format!("{}: Ping V2", name), instruction, Vec::new())
+                .with_tags(&[Tag::Micro]),
+        );
+
+        for &num_accounts in &[1u64, 32] {
+            let (mut instruction, accounts) = generate_account(*program_id, num_accounts);
+            instruction.data = instruction_data_v2(ProgramInstruction::Account {
+                expected: num_accounts,
+            });
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: Account V2 ({})", name, num_accounts),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Micro]),
+            );
+        }
+    }
+
+    // Error paths, which every entrypoint must reject with the same error
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![],
+        data: vec![u8::MAX],
+    };
+    benchmark_data.push(
+        BenchCase::new(
+            format!("{}: InvalidInstruction", name),
             instruction,
-            accounts,
-        ));
+            Vec::new(),
+        )
+        .with_tags(&[Tag::Micro])
+        .with_expected_error(ProgramError::InvalidInstructionData),
+    );
+
+    let (mut instruction, accounts) = generate_account(*program_id, 1);
+    instruction.data = instruction_data(ProgramInstruction::Account { expected: 2 });
+    benchmark_data.push(
+        BenchCase::new(format!("{}: AccountMismatch", name), instruction, accounts)
+            .with_tags(&[Tag::Micro])
+            .with_expected_error(ProgramError::InvalidArgument),
+    );
+
+    // Account field, data and validation benchmarks (not implemented by the
+    // subset programs)
+    if program.full_suite() {
+        let data_benchmarks = [
+            (ProgramInstruction::AccountFields, "AccountFields"),
+            (ProgramInstruction::ReadDataChecked, "ReadDataChecked"),
+            (ProgramInstruction::ReadDataUnchecked, "ReadDataUnchecked"),
+        ];
+        for (ix_variant, base_name) in data_benchmarks {
+            for &num_accounts in &[1u64, 8, 32] {
+                let (instruction, accounts) =
+                    generate_data_accounts(*program_id, ix_variant, num_accounts);
+                benchmark_data.push(
+                    BenchCase::new(
+                        format!("{}: {} ({})", name, base_name, num_accounts),
+                        instruction,
+                        accounts,
+                    )
+                    .with_tags(&[Tag::Micro]),
+                );
+            }
+        }
+
+        for &count in &[1u8, 8, 32] {
+            let (instruction, accounts) = generate_validate_accounts(*program_id, count);
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: ValidateAccounts ({})", name, count),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Micro]),
+            );
+        }
+
+        for (strategy, strategy_name) in [(0u8, "Eq"), (1, "Slice"), (2, "Syscall")] {
+            for bound in Bound::FIXTURES {
+                let (instruction, accounts, position) =
+                    generate_match_program_id(*program_id, strategy, bound);
+                benchmark_data.push(
+                    BenchCase::new(
+                        format!(
+                            "{}: MatchProgramId {}{}",
+                            name,
+                            strategy_name,
+                            Bound::suffix(bound)
+                        ),
+                        instruction,
+                        accounts,
+                    )
+                    .with_tags(&[Tag::Micro])
+                    .with_output(position_output(position))
+                    .with_params(BenchParams {
+                        strategy: Some(strategy_name),
+                        ..BenchParams::default()
+                    }),
+                );
+            }
+        }
+
+        for remaining in [0u8, 8, 32] {
+            let (instruction, accounts) = generate_process_remaining(*program_id, 2, remaining);
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: ProcessRemaining ({})", name, remaining),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Micro]),
+            );
+        }
+
+        for (strategy, strategy_name) in [(0u8, "Nested"), (1, "Sorted")] {
+            for count in [2u8, 8, 32] {
+                let (instruction, accounts) = generate_data_accounts(
+                    *program_id,
+                    ProgramInstruction::DedupAccounts { count, strategy },
+                    count as u64,
+                );
+                benchmark_data.push(
+                    BenchCase::new(
+                        format!("{}: DedupAccounts {} ({})", name, strategy_name, count),
+                        instruction,
+                        accounts,
+                    )
+                    .with_tags(&[Tag::Micro])
+                    .with_params(BenchParams {
+                        strategy: Some(strategy_name),
+                        ..BenchParams::default()
+                    }),
+                );
+            }
+        }
+
+        for &num_accounts in &[1u64, 8, 32] {
+            let (instruction, accounts) = generate_check_rent_exempt(*program_id, num_accounts);
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: CheckRentExempt ({})", name, num_accounts),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Sysvar]),
+            );
+        }
+
+        let (instruction, accounts) = generate_read_modify_write(*program_id);
+        let state_key = accounts[0].0;
+        let expected = modified_state(&accounts[0].1.data, mollusk.sysvars.clock.unix_timestamp);
+        benchmark_data.push(
+            BenchCase::new(format!("{}: ReadModifyWrite", name), instruction, accounts)
+                .with_tags(&[Tag::Sysvar])
+                .with_expected_data(state_key, expected),
+        );
+
+        for (m, n) in [(1u8, 1u8), (2, 3), (6, 11)] {
+            let (instruction, accounts) = generate_check_multisig(*program_id, m, n);
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: CheckMultisig ({}/{})", name, m, n),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Micro]),
+            );
+        }
+
+        for depth in [8u8, 16, 24] {
+            let (instruction, accounts) = generate_merkle_proof(*program_id, depth);
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: VerifyMerkleProof ({})", name, depth),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Hash]),
+            );
+        }
+
+        let (instruction, accounts) = generate_append_leaf(*program_id, 1_000);
+        let tree_key = accounts[0].0;
+        let mut expected = accounts[0].1.data.clone();
+        append_leaf(&mut expected, instruction.data[1..].try_into().unwrap());
+        benchmark_data.push(
+            BenchCase::new(format!("{}: AppendLeaf", name), instruction, accounts)
+                .with_tags(&[Tag::Hash])
+                .with_expected_data(tree_key, expected),
+        );
+
+        for bound in Bound::FIXTURES {
+            let (instruction, accounts) = generate_token_2022_account(*program_id, bound);
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: ParseToken2022Extensions{}", name, Bound::suffix(bound)),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Token])
+                .with_output(value_output(Some(TOKEN_2022_WITHHELD_AMOUNT))),
+            );
+
+            let (instruction, accounts, collection) = generate_metadata_account(*program_id, bound);
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: ParseMetadata{}", name, Bound::suffix(bound)),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Token])
+                .with_output(hash_output(
+                    collection.map(|collection| collection.to_bytes()),
+                )),
+            );
+        }
+
+        let (instruction, accounts, authority) = generate_program_data(*program_id);
+        benchmark_data.push(
+            BenchCase::new(format!("{}: ParseProgramData", name), instruction, accounts)
+                .with_tags(&[Tag::Micro])
+                .with_output(hash_output(Some(authority.to_bytes()))),
+        );
+
+        let (instruction, accounts) = generate_feature_account(*program_id);
+        benchmark_data.push(
+            BenchCase::new(format!("{}: CheckFeature", name), instruction, accounts)
+                .with_tags(&[Tag::Micro])
+                .with_output(position_output(Some(FEATURE_ACTIVATION_SLOT as usize))),
+        );
+
+        for (vote_account, variant_name) in [(0u8, "Total"), (1, "VoteAccount")] {
+            let (instruction, accounts) = generate_epoch_stake(*program_id, vote_account);
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: GetEpochStake {}", name, variant_name),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Sysvar]),
+            );
+        }
+
+        // Casting requires the aligned offset, the others read any offset.
+        for (strategy, strategy_name, offsets) in [
+            (0u8, "ReadUnaligned", &[0u8, 1, 4][..]),
+            (1, "FromLeBytes", &[0, 1, 4]),
+            (2, "Cast", &[0]),
+        ] {
+            for &offset in offsets {
+                let (instruction, accounts, value) =
+                    generate_read_alignment(*program_id, offset, strategy);
+                benchmark_data.push(
+                    BenchCase::new(
+                        format!(
+                            "{}: ReadAlignment {} (Offset {})",
+                            name, strategy_name, offset
+                        ),
+                        instruction,
+                        accounts,
+                    )
+                    .with_tags(&[Tag::Micro])
+                    .with_output(position_output(Some(value as usize)))
+                    .with_params(BenchParams {
+                        strategy: Some(strategy_name),
+                        ..BenchParams::default()
+                    }),
+                );
+            }
+        }
+
+        // 10KB to 1MB, as cleared when closing or reinitializing accounts
+        for (strategy, strategy_name) in [(0u8, "VolatileLoop"), (1, "Fill"), (2, "Memset")] {
+            for len in [10_240u32, 102_400, 1_048_576] {
+                let (instruction, accounts) = generate_zero_data(*program_id, len, strategy);
+                let key = accounts[0].0;
+                benchmark_data.push(
+                    BenchCase::new(
+                        format!("{}: ZeroData {} (Len {})", name, strategy_name, len),
+                        instruction,
+                        accounts,
+                    )
+                    .with_tags(&[Tag::Micro])
+                    .with_expected_data(key, vec![0; len as usize])
+                    .with_params(BenchParams {
+                        strategy: Some(strategy_name),
+                        ..BenchParams::default()
+                    }),
+                );
+            }
+        }
+
+        // Up to 100KB, as the byte loop costs a few CUs per byte
+        for (strategy, strategy_name) in
+            [(0u8, "VolatileLoop"), (1, "CopyFromSlice"), (2, "Memcpy")]
+        {
+            for len in [1_024u32, 10_240, 102_400] {
+                let (instruction, accounts, data) =
+                    generate_copy_account_data(*program_id, len, strategy);
+                let destination_key = accounts[1].0;
+                benchmark_data.push(
+                    BenchCase::new(
+                        format!("{}: CopyAccountData {} (Len {})", name, strategy_name, len),
+                        instruction,
+                        accounts,
+                    )
+                    .with_tags(&[Tag::Micro])
+                    .with_expected_data(destination_key, data)
+                    .with_params(BenchParams {
+                        strategy: Some(strategy_name),
+                        ..BenchParams::default()
+                    }),
+                );
+            }
+        }
+
+        for count in [1u8, 8, 32] {
+            let (instruction, accounts, value) = generate_pseudo_random(*program_id, count);
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: PseudoRandom ({})", name, count),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Hash, Tag::Sysvar])
+                .with_output(position_output(value.map(|value| value as usize))),
+            );
+        }
+
+        // Pinocchio has no serializer dependencies, so it only writes by hand
+        let write_benchmarks = [
+            (ProgramInstruction::WriteStateBorsh, "WriteStateBorsh"),
+            (ProgramInstruction::WriteStateBincode, "WriteStateBincode"),
+            (ProgramInstruction::WriteStateManual, "WriteStateManual"),
+        ];
+        for (ix_variant, base_name) in write_benchmarks {
+            if !program.serializes() && !matches!(ix_variant, ProgramInstruction::WriteStateManual)
+            {
+                continue;
+            }
+            let (instruction, accounts) = generate_write_state(*program_id, ix_variant);
+            let state_key = accounts[0].0;
+            benchmark_data.push(
+                BenchCase::new(format!("{}: {}", name, base_name), instruction, accounts)
+                    .with_tags(&[Tag::Micro])
+                    .with_expected_data(state_key, sample_state(program_id)),
+            );
+        }
+
+        let event_benchmarks = [
+            (ProgramInstruction::EmitEvent, "EmitEvent"),
+            (ProgramInstruction::EmitEventBase64, "EmitEventBase64"),
+        ];
+        for (ix_variant, base_name) in event_benchmarks {
+            let instruction = Instruction {
+                program_id: *program_id,
+                accounts: vec![],
+                data: instruction_data(ix_variant),
+            };
+            benchmark_data.push(
+                BenchCase::new(format!("{}: {}", name, base_name), instruction, Vec::new())
+                    .with_tags(&[Tag::Micro]),
+            );
+        }
+
+        for iterations in [1u16, 10, 100] {
+            let instruction = Instruction {
+                program_id: *program_id,
+                accounts: vec![],
+                data: instruction_data(ProgramInstruction::DivSqrt { iterations }),
+            };
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: DivSqrt ({})", name, iterations),
+                    instruction,
+                    Vec::new(),
+                )
+                .with_tags(&[Tag::Micro]),
+            );
+        }
+
+        for periods in [1u8, 12, 120] {
+            let accrual_benchmarks = [
+                (
+                    ProgramInstruction::AccrueInterestQ64 { periods },
+                    "AccrueInterestQ64",
+                ),
+                (
+                    ProgramInstruction::AccrueInterestFixed { periods },
+                    "AccrueInterestFixed",
+                ),
+            ];
+            for (ix_variant, base_name) in accrual_benchmarks {
+                let instruction = Instruction {
+                    program_id: *program_id,
+                    accounts: vec![],
+                    data: instruction_data(ix_variant),
+                };
+                benchmark_data.push(
+                    BenchCase::new(
+                        format!("{}: {} ({})", name, base_name, periods),
+                        instruction,
+                        Vec::new(),
+                    )
+                    .with_tags(&[Tag::Micro]),
+                );
+            }
+        }
+    }
+
+    // Dispatch strategies, dispatching to the last variant of each instruction
+    // set (Pinocchio only)
+    if program.dispatches() {
+        let strategies = [(0u8, "EnumMatch"), (1, "JumpTable")];
+        for (strategy, strategy_name) in strategies {
+            for variants in [4u8, 16, 64] {
+                let instruction = Instruction {
+                    program_id: *program_id,
+                    accounts: vec![],
+                    data: instruction_data(ProgramInstruction::Dispatch {
+                        strategy,
+                        variants,
+                        tag: variants - 1,
+                    }),
+                };
+                benchmark_data.push(
+                    BenchCase::new(
+                        format!("{}: Dispatch {} ({})", name, strategy_name, variants),
+                        instruction,
+                        Vec::new(),
+                    )
+                    .with_tags(&[Tag::Micro])
+                    .with_params(BenchParams {
+                        strategy: Some(strategy_name),
+                        variants: Some(variants),
+                        ..BenchParams::default()
+                    }),
+                );
+            }
+        }
+
+        // Clock and Rent, through their syscall and from their account
+        let sysvar_benchmarks = [
+            (ProgramInstruction::ClockGet, "ClockGet"),
+            (ProgramInstruction::ClockFromAccount, "ClockFromAccount"),
+            (ProgramInstruction::RentGet, "RentGet"),
+            (ProgramInstruction::RentFromAccount, "RentFromAccount"),
+        ];
+        for (ix_variant, base_name) in sysvar_benchmarks {
+            let (instruction, accounts) =
+                generate_sysvar(*program_id, ix_variant, &mollusk.sysvars.clock);
+            benchmark_data.push(
+                BenchCase::new(format!("{}: {}", name, base_name), instruction, accounts)
+                    .with_tags(&[Tag::Sysvar]),
+            );
+        }
     }
 
     // CreateAccount
     let (instruction, accounts) = generate_create_account(*program_id);
-    benchmark_data.push((format!("{}: CreateAccount", name), instruction, accounts));
+    let cpi = create_account_cpi(program_id, &accounts[0].0, &accounts[1].0);
+    benchmark_data.push(
+        BenchCase::new(format!("{}: CreateAccount", name), instruction, accounts).with_cpi(cpi),
+    );
 
     // Transfer
     let (instruction, accounts) = generate_transfer(*program_id);
-    benchmark_data.push((format!("{}: Transfer", name), instruction, accounts));
+    let cpi = transfer_cpi(&accounts[0].0, &accounts[1].0);
+    benchmark_data
+        .push(BenchCase::new(format!("{}: Transfer", name), instruction, accounts).with_cpi(cpi));
+
+    // Transfer failing in the system program: the payer only holds its
+    // rent-exempt minimum. The runtime aborts the invoking program with the
+    // error of the invoked one, so no framework can handle it, and this
+    // measures the CPI up to the failure.
+    let (instruction, mut accounts) = generate_transfer(*program_id);
+    accounts[0].1 = rent_exempt_account(0, &solana_system_interface::program::ID);
+    let cpi = transfer_cpi(&accounts[0].0, &accounts[1].0);
+    benchmark_data.push(
+        BenchCase::new(format!("{}: TransferFailed", name), instruction, accounts)
+            .with_cpi(cpi)
+            .with_expected_error(ProgramError::Custom(
+                SystemError::ResultWithNegativeLamports as u32,
+            )),
+    );
+
+    // Ownership lifecycle: assigned to the program, written, zeroed and
+    // assigned back to the system program
+    if program.full_suite() {
+        let (instruction, accounts) = generate_owner_round_trip(*program_id);
+        let key = accounts[0].0;
+        let cpi = solana_system_interface::instruction::assign(&key, program_id);
+        benchmark_data.push(
+            BenchCase::new(format!("{}: OwnerRoundTrip", name), instruction, accounts)
+                .with_cpi(cpi)
+                .with_expected_data(key, vec![0; ROUND_TRIP_DATA_LEN])
+                .with_expected_owner(key, solana_system_interface::program::ID),
+        );
+    }
+
+    // Transfer signed for through seeds, sweeping their count and length
+    if program.full_suite() {
+        for num_seeds in [1u8, 4, 15] {
+            for seed_len in [8u8, 32] {
+                let (instruction, accounts) =
+                    generate_cpi_signed_seeds(*program_id, num_seeds, seed_len);
+                let cpi = transfer_cpi(&accounts[0].0, &accounts[1].0);
+                benchmark_data.push(
+                    BenchCase::new(
+                        format!(
+                            "{}: CpiSignedSeeds (Seeds {}, Len {})",
+                            name, num_seeds, seed_len
+                        ),
+                        instruction,
+                        accounts,
+                    )
+                    .with_cpi(cpi),
+                );
+            }
+        }
+    }
+
+    // StakeHistory lookups, sharing the SlotHashes search, and SlotHashes
+    // lookups through the sysvar cache (SDK only)
+    if program.sysvar_lookups() {
+        let entries = generate_mock_slot_hashes_data(&SYSVAR_CACHE_SLOT_GAPS);
+        for (strategy, strategy_name) in [(0u8, "Fetch"), (1, "Probe")] {
+            // Slot 0, like the lookups reading the account
+            let instruction = Instruction {
+                program_id: *program_id,
+                accounts: vec![],
+                data: instruction_data(ProgramInstruction::SlotHashesPositionSysvar {
+                    slot: 0,
+                    strategy,
+                }),
+            };
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: Position (SysvarCache {})", name, strategy_name),
+                    instruction,
+                    Vec::new(),
+                )
+                .with_tags(&[Tag::Sysvar])
+                .with_output(position_output(
+                    entries.iter().position(|(slot, _)| *slot == 0),
+                ))
+                .with_params(BenchParams {
+                    sysvar_entries: Some(entries.len()),
+                    strategy: Some(strategy_name),
+                    ..BenchParams::default()
+                }),
+            );
+        }
+
+        for target_index in [0usize, 255, 511] {
+            let (instruction, accounts) =
+                generate_stake_history(*program_id, stake_history_epoch(target_index));
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: StakeHistoryPosition (Idx {})", name, target_index),
+                    instruction,
+                    accounts,
+                )
+                .with_tags(&[Tag::Sysvar])
+                .with_output(position_output(Some(target_index)))
+                .with_params(BenchParams {
+                    sysvar_entries: Some(NUM_BENCH_STAKE_HISTORY_ENTRIES),
+                    target_index: Some(target_index),
+                    ..BenchParams::default()
+                }),
+            );
+        }
+    }
+
+    // Heap allocations, only possible with pinocchio's bump allocator
+    if program.allocates() {
+        for size in [32u16, 1024, 16384] {
+            let instruction = Instruction {
+                program_id: *program_id,
+                accounts: vec![],
+                data: instruction_data(ProgramInstruction::Allocate { size }),
+            };
+            benchmark_data.push(
+                BenchCase::new(
+                    format!("{}: Allocate ({})", name, size),
+                    instruction,
+                    Vec::new(),
+                )
+                .with_tags(&[Tag::Micro]),
+            );
+        }
+    }
 
     // --- Generate data for SlotHashes benchmarks with different strategies ---
-    let strategies = [
-        (DecrementStrategy::Strictly1, "Strictly1"),
-        (DecrementStrategy::Average1_05, "Avg1.05"),
-        (DecrementStrategy::Average2, "Avg2"),
-    ];
-
-    // Define base instruction types and base names for SlotHashes
-    // We will expand these with specific target indices
-    let base_slot_hash_benchmarks = [
-        // Use UNCHECKED instructions for Pinocchio
-        (
-            "eisodos_pinocchio",
-            ProgramInstruction::SlotHashesGetEntryUnchecked, // Placeholder, won't be used directly
-            "GetEntry",
-            None // Indicates no target slot needed
-        ),
-        (
-            "eisodos_pinocchio",
-            ProgramInstruction::SlotHashesGetHashInterpolatedUnchecked, // Placeholder
-            "GetHashInterpolated",
-            None // For simplicity, keep GetHash searching for slot 0 for now
-        ),
-        (
-            "eisodos_pinocchio",
-            ProgramInstruction::SlotHashesPositionInterpolatedUnchecked { target_slot: 0 }, // Placeholder
-            "PositionInterpolated",
-            Some([0, 50, 100, 150, 255, 300, 350, 400, 450, 511]) // Expanded target indices
-        ),
-        (
-            "eisodos_pinocchio",
-            ProgramInstruction::SlotHashesPositionNaiveUnchecked { target_slot: 0 }, // Placeholder
-            "PositionNaive",
-            Some([0, 50, 100, 150, 255, 300, 350, 400, 450, 511]) // Expanded target indices
-        ),
-        // Use CHECKED instructions for SDK / Nostd - Add similar structure if needed
-        // ... (SDK/Nostd entries unchanged for now) ...
-    ];
-
-    for (strategy, strategy_name) in strategies {
+    let strategies = slot_gaps::from_env()?;
+
+    for slot_gaps in &strategies {
+        let (strategy, strategy_name) = (&slot_gaps.distribution, slot_gaps.name);
         // Generate mock data once per strategy
         let mock_entries = generate_mock_slot_hashes_data(strategy);
         let actual_len = mock_entries.len();
-        if actual_len == 0 { continue; } // Skip if no entries generated
+        // Skip if no entries generated
+        if actual_len == 0 {
+            continue;
+        }
 
         // Define target indices relative to actual length
         let target_indices_to_get = [
@@ -112,7 +1495,8 @@ pub fn run(program_id: &Pubkey, name: &'static str) {
             actual_len.saturating_sub(1), // Last
         ];
         // Deduplicate indices, necessary if actual_len is small
-        let unique_target_indices: std::collections::HashSet<usize> = target_indices_to_get.iter().cloned().collect();
+        let unique_target_indices: std::collections::HashSet<usize> =
+            target_indices_to_get.iter().cloned().collect();
 
         // Get the slot values for the unique target indices
         let target_slot_values: HashMap<usize, Slot> = unique_target_indices
@@ -120,50 +1504,115 @@ pub fn run(program_id: &Pubkey, name: &'static str) {
             .map(|&idx| (idx, mock_entries[idx].0))
             .collect();
 
-        let generate_fn = if name == "eisodos_pinocchio" {
-            generate_pinocchio_slot_hashes_ix
-        } else {
-            generate_sdk_slot_hashes_ix // This would also need modification if SDK benches target specific slots
-        };
-
-        // Filter benchmarks relevant to the current program (`name`)
-        for &(prog_name_filter, base_ix_variant, base_name, target_indices_opt) in base_slot_hash_benchmarks.iter() {
-            if prog_name_filter != name {
-                continue;
-            }
+        for lookup in program.slot_hashes_lookups() {
+            let (base_ix_variant, base_name) = (lookup.instruction, lookup.name);
+            let generate_fn = if lookup.unchecked {
+                generate_pinocchio_slot_hashes_ix
+            } else {
+                generate_sdk_slot_hashes_ix
+            };
 
-            match target_indices_opt {
-                Some(_) => { // Modified: Use unique_target_indices derived from actual_len
-                    // Create benchmarks for specific indices
-                    for &target_index in &unique_target_indices {
-                        let target_slot = target_slot_values[&target_index]; // Get slot value from map
-                        let ix_variant = match base_ix_variant {
-                            ProgramInstruction::SlotHashesPositionInterpolatedUnchecked { .. } => 
-                                ProgramInstruction::SlotHashesPositionInterpolatedUnchecked { target_slot },
-                            ProgramInstruction::SlotHashesPositionNaiveUnchecked { .. } => 
-                                ProgramInstruction::SlotHashesPositionNaiveUnchecked { target_slot },
-                            _ => panic!("Unexpected instruction type for indexed target")
-                        };
-                        // Pass actual_len to generate_fn if it needs it (it currently doesn't, uses mock_entries.len() indirectly)
-                        let (instruction, accounts) = generate_fn(*program_id, ix_variant, strategy);
-                        let bench_id = format!("{}: {} (Idx {}) ({})", name, base_name, target_index, strategy_name);
-                        benchmark_data.push((bench_id, instruction, accounts));
-                    }
-                }
-                None => {
-                    // Create a single benchmark (like GetEntry, GetHash)
-                    let ix_variant = base_ix_variant; // Use the placeholder directly
+            if lookup.indexed {
+                // Create benchmarks for specific indices
+                for &target_index in &unique_target_indices {
+                    let target_slot = target_slot_values[&target_index]; // Get slot value from map
+                    let ix_variant = match base_ix_variant {
+                        ProgramInstruction::SlotHashesPositionInterpolatedUnchecked { .. } => {
+                            ProgramInstruction::SlotHashesPositionInterpolatedUnchecked {
+                                target_slot,
+                            }
+                        }
+                        ProgramInstruction::SlotHashesPositionNaiveUnchecked { .. } => {
+                            ProgramInstruction::SlotHashesPositionNaiveUnchecked { target_slot }
+                        }
+                        _ => panic!("Unexpected instruction type for indexed target"),
+                    };
+                    // Pass actual_len to generate_fn if it needs it (it currently doesn't, uses
+                    // mock_entries.len() indirectly)
                     let (instruction, accounts) = generate_fn(*program_id, ix_variant, strategy);
-                    let bench_id = format!("{}: {} ({})", name, base_name, strategy_name);
-                    benchmark_data.push((bench_id, instruction, accounts));
+                    let bench_id = format!(
+                        "{}: {} (Idx {}) ({})",
+                        name, base_name, target_index, strategy_name
+                    );
+                    let case = BenchCase::new(bench_id, instruction, accounts)
+                        .with_tags(&[Tag::Sysvar])
+                        .with_output(position_output(Some(target_index)))
+                        .with_params(BenchParams {
+                            sysvar_entries: Some(actual_len),
+                            strategy: Some(strategy_name),
+                            target_index: Some(target_index),
+                            ..BenchParams::default()
+                        });
+                    // The unchecked searches of the verbose build log whether
+                    // the slot was found
+                    let case = if program.logs_lookups() {
+                        let (found, not_found) = match ix_variant {
+                            ProgramInstruction::SlotHashesPositionInterpolatedUnchecked {
+                                ..
+                            } => ("IP found", "IP not found"),
+                            _ => ("NP found", "NP not found"),
+                        };
+                        case.with_expected_log(found).without_log(not_found)
+                    } else {
+                        case
+                    };
+                    benchmark_data.push(case);
                 }
+            } else {
+                // Create a single benchmark (like GetEntry, GetHash)
+                let ix_variant = base_ix_variant; // Use the placeholder directly
+                let (instruction, accounts) = generate_fn(*program_id, ix_variant, strategy);
+                let bench_id = format!("{}: {} ({})", name, base_name, strategy_name);
+                let case = BenchCase::new(bench_id, instruction, accounts)
+                    .with_tags(&[Tag::Sysvar])
+                    .with_params(BenchParams {
+                        sysvar_entries: Some(actual_len),
+                        strategy: Some(strategy_name),
+                        ..BenchParams::default()
+                    });
+                // GetHash and Position look up slot 0 and write the hash or
+                // position, if found
+                let case = match ix_variant {
+                    ProgramInstruction::SlotHashesGetHashInterpolatedUnchecked => {
+                        let hash = mock_entries
+                            .iter()
+                            .find(|(slot, _)| *slot == 0)
+                            .map(|(_, hash)| *hash);
+                        let case = if !program.logs_lookups() {
+                            case
+                        } else if hash.is_some() {
+                            case.with_expected_log("GH found")
+                                .without_log("GH not found")
+                        } else {
+                            case.with_expected_log("GH not found")
+                                .without_log("GH found")
+                        };
+                        case.with_output(hash_output(hash))
+                    }
+                    ProgramInstruction::SlotHashesGetHashInterpolated
+                    | ProgramInstruction::SlotHashesGetHashZerocopy => {
+                        let hash = mock_entries
+                            .iter()
+                            .find(|(slot, _)| *slot == 0)
+                            .map(|(_, hash)| *hash);
+                        case.with_output(hash_output(hash))
+                    }
+                    ProgramInstruction::SlotHashesPositionInterpolated
+                    | ProgramInstruction::SlotHashesPositionZerocopy => {
+                        let position = mock_entries.iter().position(|(slot, _)| *slot == 0);
+                        case.with_output(position_output(position))
+                    }
+                    _ => case,
+                };
+                benchmark_data.push(case);
             }
         }
     }
 
-    for (id, instruction, accounts) in &benchmark_data {
-        bencher = bencher.bench((id.as_str(), instruction, accounts));
+    // Catch benchmarks passing the wrong accounts before they are measured
+    for case in &benchmark_data {
+        requirements::validate(case)?;
     }
 
-    bencher.execute();
+    Ok(benchmark_data)
 }
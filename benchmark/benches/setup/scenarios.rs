@@ -0,0 +1,109 @@
+//! Registry of named, ready-to-run scenarios.
+//!
+//! A scenario names a set of benchmarks generated by the runner, accounts
+//! included, so standard cases can be run by name with
+//! `EISODOS_SCENARIOS=<name>,...` instead of reconstructing their accounts (and
+//! recorded with `EISODOS_RECORD` to start a custom scenario from).
+
+use super::BenchCase;
+use eisodos::error::EisodosError;
+
+/// A named set of benchmarks.
+pub struct Scenario {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Whether the benchmark with the given id (without the program prefix)
+    /// belongs to the scenario.
+    pub includes: fn(&str) -> bool,
+}
+
+/// Every scenario, by name.
+pub const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        name: "ping",
+        description: "Empty instruction, the bare entrypoint cost",
+        includes: |id| id == "Ping",
+    },
+    Scenario {
+        name: "empty-call",
+        description: "Empty instruction data, the entrypoint floor",
+        includes: |id| id == "EmptyCall",
+    },
+    Scenario {
+        name: "compute-budget",
+        description: "Ping preceded by ComputeBudget instructions",
+        includes: |id| id.starts_with("Ping (ComputeBudget"),
+    },
+    Scenario {
+        name: "account-sweep",
+        description: "Entrypoint cost of parsing 1 to 64 accounts",
+        includes: |id| id.starts_with("Account ("),
+    },
+    Scenario {
+        name: "transfer-default",
+        description: "System program transfer through CPI",
+        includes: |id| id == "Transfer",
+    },
+    Scenario {
+        name: "create-account-default",
+        description: "System program account creation through CPI",
+        includes: |id| id == "CreateAccount",
+    },
+    Scenario {
+        name: "owner-round-trip",
+        description: "Account assigned to the program, written, zeroed and assigned back",
+        includes: |id| id == "OwnerRoundTrip",
+    },
+    Scenario {
+        name: "slot-hashes-sweep",
+        description: "SlotHashes lookups for every slot gap distribution",
+        includes: |id| {
+            ["GetEntry", "GetHash", "Position"]
+                .iter()
+                .any(|lookup| id.starts_with(lookup))
+        },
+    },
+    Scenario {
+        name: "token-extensions",
+        description: "Token-2022 account extension parsing",
+        includes: |id| id == "ParseToken2022Extensions",
+    },
+];
+
+/// Keeps the benchmarks of `program` belonging to any of the scenarios
+/// selected by `EISODOS_SCENARIOS`, if set, failing on an unknown scenario.
+pub fn select(
+    program: &str,
+    benchmark_data: Vec<BenchCase>,
+) -> Result<Vec<BenchCase>, EisodosError> {
+    let Ok(names) = std::env::var("EISODOS_SCENARIOS") else {
+        return Ok(benchmark_data);
+    };
+    let scenarios: Vec<&Scenario> = names
+        .split(',')
+        .map(|name| {
+            let name = name.trim();
+            SCENARIOS
+                .iter()
+                .find(|scenario| scenario.name == name)
+                .ok_or_else(|| {
+                    let available: String = SCENARIOS
+                        .iter()
+                        .map(|scenario| format!("\n  {}: {}", scenario.name, scenario.description))
+                        .collect();
+                    EisodosError::Spec(format!(
+                        "unknown scenario `{name}`, expected one of:{available}"
+                    ))
+                })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let prefix = format!("{program}: ");
+    Ok(benchmark_data
+        .into_iter()
+        .filter(|case| {
+            let id = case.id.strip_prefix(&prefix).unwrap_or(&case.id);
+            scenarios.iter().any(|scenario| (scenario.includes)(id))
+        })
+        .collect())
+}
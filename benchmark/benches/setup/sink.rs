@@ -0,0 +1,223 @@
+//! Outputs of the compute units measured by the runner.
+//!
+//! `EISODOS_SINKS` selects a comma-separated list of sinks, `markdown,json-run`
+//! by default:
+//!
+//! - `markdown`: prepends a table to `compute_units.md`, with the delta to the
+//!   previous results of each benchmark.
+//! - `json`: appends a JSON object per benchmark to `compute_units.jsonl`.
+//! - `json-run`: writes the results of the run to `runs/<program>.json`, as an
+//!   array of objects with the id of each benchmark and the commit of the run.
+//! - `csv`: appends a row per benchmark to `compute_units.csv`.
+//! - `sqlite`: inserts a row per benchmark into the `compute_units` table of
+//!   `compute_units.db`, with the commit of the run (`EISODOS_COMMIT`, or the
+//!   `HEAD` of the repository).
+//! - `stdout`: prints a table.
+//!
+//! Files are written to `../target/benches`. Every program is benchmarked by a
+//! separate run, so sinks add to the results of previous runs instead of
+//! replacing them, except `json-run`, which replaces the previous run of the
+//! program only.
+
+use eisodos::error::EisodosError;
+use eisodos::history::{self, HistoryRow};
+use eisodos::results::{self, BenchResult};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const OUT_DIR: &str = "../target/benches";
+
+/// Output of the results of a run.
+pub trait ResultSink {
+    /// Writes the `results` of a run started at `timestamp`.
+    fn write(&mut self, timestamp: &str, results: &[BenchResult]) -> Result<(), EisodosError>;
+}
+
+/// Returns the sinks selected by `EISODOS_SINKS`.
+pub fn from_env() -> Result<Vec<Box<dyn ResultSink>>, EisodosError> {
+    let sinks = std::env::var("EISODOS_SINKS").unwrap_or_else(|_| "markdown,json-run".to_string());
+    sinks
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| -> Result<Box<dyn ResultSink>, EisodosError> {
+            match name {
+                "markdown" => Ok(Box::new(Markdown)),
+                "json" => Ok(Box::new(Json)),
+                "json-run" => Ok(Box::new(JsonRun)),
+                "csv" => Ok(Box::new(Csv)),
+                "sqlite" => Ok(Box::new(Sqlite)),
+                "stdout" => Ok(Box::new(Stdout)),
+                _ => Err(EisodosError::Spec(format!(
+                    "invalid EISODOS_SINKS `{sinks}`, expected a list of `markdown`, `json`, \
+                     `json-run`, `csv`, `sqlite` or `stdout`"
+                ))),
+            }
+        })
+        .collect()
+}
+
+fn out_path(file: &str) -> Result<PathBuf, EisodosError> {
+    fs::create_dir_all(OUT_DIR).map_err(|error| EisodosError::write(OUT_DIR, error))?;
+    Ok(Path::new(OUT_DIR).join(file))
+}
+
+/// Appends `contents` to `file`, after `header` if the file is new.
+fn append(file: &str, header: &str, contents: &str) -> Result<(), EisodosError> {
+    let path = out_path(file)?;
+    let write = |error| EisodosError::write(&path, error);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(write)?;
+    if file.metadata().map_err(write)?.len() == 0 {
+        file.write_all(header.as_bytes()).map_err(write)?;
+    }
+    file.write_all(contents.as_bytes()).map_err(write)
+}
+
+/// Table in the format of `mollusk-svm-bencher`, read by `coyote`.
+pub struct Markdown;
+
+impl ResultSink for Markdown {
+    fn write(&mut self, timestamp: &str, results: &[BenchResult]) -> Result<(), EisodosError> {
+        let path = out_path("compute_units.md")?;
+        let previous_contents = fs::read_to_string(&path).unwrap_or_default();
+        let previous = results::parse_markdown(&previous_contents);
+
+        let mut md = format!(
+            "#### Compute Units: {timestamp}\n\n| Name | CUs | Delta |\n|------|------|-------|\n"
+        );
+        for result in results {
+            let delta = match previous.iter().find(|old| old.id() == result.id()) {
+                Some(old) if old.cus == result.cus => "--".to_string(),
+                Some(old) => format!("{:+}", result.cus as i64 - old.cus as i64),
+                None => "- new -".to_string(),
+            };
+            md.push_str(&format!(
+                "| {} | {} | {} |\n",
+                result.id(),
+                result.cus,
+                delta
+            ));
+        }
+
+        fs::write(&path, format!("{md}\n{previous_contents}"))
+            .map_err(|error| EisodosError::write(&path, error))
+    }
+}
+
+/// One JSON object per line.
+pub struct Json;
+
+impl ResultSink for Json {
+    fn write(&mut self, timestamp: &str, results: &[BenchResult]) -> Result<(), EisodosError> {
+        let mut lines = String::new();
+        for result in results {
+            let line = serde_json::json!({
+                "timestamp": timestamp,
+                "program": result.program,
+                "scenario": result.scenario,
+                "cus": result.cus,
+            });
+            lines.push_str(&format!("{line}\n"));
+        }
+        append("compute_units.jsonl", "", &lines)
+    }
+}
+
+/// JSON array of the results of the run, one file per program, so CI can read
+/// the latest results of every program without parsing the markdown.
+pub struct JsonRun;
+
+impl ResultSink for JsonRun {
+    fn write(&mut self, timestamp: &str, results: &[BenchResult]) -> Result<(), EisodosError> {
+        let Some(first) = results.first() else {
+            return Ok(());
+        };
+        let runs = Path::new(OUT_DIR).join("runs");
+        fs::create_dir_all(&runs).map_err(|error| EisodosError::write(&runs, error))?;
+        let path = out_path(&format!("runs/{}.json", first.program))?;
+        let write = |error| EisodosError::write(&path, error);
+
+        let commit = history::current_commit();
+        let results: Vec<serde_json::Value> = results
+            .iter()
+            .map(|result| {
+                serde_json::json!({
+                    "id": result.id(),
+                    "program": result.program,
+                    "scenario": result.scenario,
+                    "cus": result.cus,
+                    "timestamp": timestamp,
+                    "commit": commit,
+                })
+            })
+            .collect();
+        let json = serde_json::to_string_pretty(&results)
+            .map_err(|error| write(io::Error::other(error)))?;
+        fs::write(&path, format!("{json}\n")).map_err(write)
+    }
+}
+
+/// Comma-separated values, with a header if the file is new.
+pub struct Csv;
+
+impl ResultSink for Csv {
+    fn write(&mut self, timestamp: &str, results: &[BenchResult]) -> Result<(), EisodosError> {
+        // Scenarios contain commas, e.g. `Position (Idx 0, Strictly1)`.
+        let quote = |value: &str| format!("\"{}\"", value.replace('"', "\"\""));
+        let mut rows = String::new();
+        for result in results {
+            rows.push_str(&format!(
+                "{},{},{},{}\n",
+                quote(timestamp),
+                quote(&result.program),
+                quote(&result.scenario),
+                result.cus
+            ));
+        }
+        append(
+            "compute_units.csv",
+            "timestamp,program,scenario,cus\n",
+            &rows,
+        )
+    }
+}
+
+/// SQLite database, so results of many runs can be queried. Rows are recorded
+/// with the commit of the run, so the history can be pruned by `coyote`.
+pub struct Sqlite;
+
+impl ResultSink for Sqlite {
+    fn write(&mut self, timestamp: &str, results: &[BenchResult]) -> Result<(), EisodosError> {
+        let mut connection = history::open(out_path("compute_units.db")?)?;
+        let commit = history::current_commit();
+
+        let transaction = connection.transaction()?;
+        for result in results {
+            let row = HistoryRow {
+                timestamp: timestamp.to_string(),
+                program: result.program.clone(),
+                scenario: result.scenario.clone(),
+                cus: result.cus,
+                commit: commit.clone(),
+            };
+            history::insert(&transaction, &row)?;
+        }
+        Ok(transaction.commit()?)
+    }
+}
+
+/// Table printed to the standard output.
+pub struct Stdout;
+
+impl ResultSink for Stdout {
+    fn write(&mut self, timestamp: &str, results: &[BenchResult]) -> Result<(), EisodosError> {
+        let table = results::to_markdown(results);
+        write!(io::stdout().lock(), "Compute Units: {timestamp}\n\n{table}")
+            .map_err(|error| EisodosError::write("the standard output", error))
+    }
+}
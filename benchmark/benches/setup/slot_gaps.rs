@@ -0,0 +1,178 @@
+//! Distributions of the slot gaps between consecutive SlotHashes entries, i.e.
+//! of the number of slots skipped between two blocks, plus one.
+//!
+//! The SlotHashes benchmarks are generated for every distribution of
+//! `EISODOS_SLOT_GAPS`, a comma-separated list of:
+//!
+//! - `fixed:<gap>`: every gap is `gap`.
+//! - `geometric:<p>`: every slot produces a block with probability `p`, so gaps
+//!   are geometrically distributed with mean `1 / p`.
+//! - `empirical:<path>`: gaps drawn from a histogram file with a `<gap>
+//!   <weight>` line per gap (`#` starts a comment), e.g. as observed on a
+//!   cluster.
+//!
+//! By default, the benchmarks use `Strictly1` (`fixed:1`), `Avg1.05` (a gap of
+//! 2 once every 20 slots) and `Avg2` (gaps of 1 or 3 with equal weights).
+
+use eisodos::error::EisodosError;
+use std::{fs, path::Path};
+
+/// Modulus of the pseudo-random generator.
+const PRNG_MODULUS: u64 = 2_147_483_647; // 2^31 - 1
+
+// Simple deterministic PRNG for varied decrements
+// Using a basic Lehmer / MINSTD generator approach
+fn simple_prng(seed: u64) -> u64 {
+    const A: u64 = 16807; // Multiplier
+    let initial_state = if seed == 0 { 1 } else { seed };
+    (A.wrapping_mul(initial_state)) % PRNG_MODULUS
+}
+
+/// Uniform value in `(0, 1]` for `seed` (SplitMix64), as sequential seeds of
+/// `simple_prng` are too correlated to be used as uniform values.
+fn uniform(seed: u64) -> f64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    ((z >> 11) + 1) as f64 / (1u64 << 53) as f64
+}
+
+/// Distribution of the slot gaps.
+#[derive(Clone, Debug)]
+pub enum GapDistribution {
+    Fixed(u64),
+    /// Probability of a slot producing a block.
+    Geometric(f64),
+    /// `(gap, weight)` pairs.
+    Empirical(Vec<(u64, u64)>),
+}
+
+impl GapDistribution {
+    /// Returns the gap following the `index`th entry, deterministically.
+    pub fn gap(&self, index: usize) -> u64 {
+        match self {
+            GapDistribution::Fixed(gap) => *gap,
+            GapDistribution::Geometric(p) => {
+                // Inverse transform sampling of the number of skipped slots.
+                1 + (uniform(index as u64).ln() / (1.0 - p).ln()).floor() as u64
+            }
+            GapDistribution::Empirical(histogram) => {
+                let total: u64 = histogram.iter().map(|(_, weight)| weight).sum();
+                let mut draw = simple_prng(index as u64) % total;
+                for &(gap, weight) in histogram {
+                    if draw < weight {
+                        return gap;
+                    }
+                    draw -= weight;
+                }
+                unreachable!("draw below the total weight")
+            }
+        }
+    }
+}
+
+/// A named distribution, the name being shown in the benchmark ids.
+#[derive(Clone, Debug)]
+pub struct SlotGaps {
+    pub name: &'static str,
+    pub distribution: GapDistribution,
+}
+
+/// Returns the distributions selected by `EISODOS_SLOT_GAPS`, or the default
+/// ones, failing if a distribution is malformed or its histogram cannot be
+/// read.
+pub fn from_env() -> Result<Vec<SlotGaps>, EisodosError> {
+    let Ok(specs) = std::env::var("EISODOS_SLOT_GAPS") else {
+        return Ok(vec![
+            SlotGaps {
+                name: "Strictly1",
+                distribution: GapDistribution::Fixed(1),
+            },
+            // The histogram order matches the draws of the former hardcoded
+            // strategies, so their benchmarks are unchanged.
+            SlotGaps {
+                name: "Avg1.05",
+                distribution: GapDistribution::Empirical(vec![(2, 1), (1, 19)]),
+            },
+            SlotGaps {
+                name: "Avg2",
+                distribution: GapDistribution::Empirical(vec![(1, 1), (3, 1)]),
+            },
+        ]);
+    };
+
+    specs.split(',').map(|spec| parse(spec.trim())).collect()
+}
+
+fn invalid(spec: &str, reason: &str) -> EisodosError {
+    EisodosError::Spec(format!(
+        "invalid EISODOS_SLOT_GAPS distribution `{spec}`: {reason}"
+    ))
+}
+
+fn parse(spec: &str) -> Result<SlotGaps, EisodosError> {
+    let (kind, value) = spec
+        .split_once(':')
+        .ok_or_else(|| invalid(spec, "expected `<kind>:<value>`"))?;
+
+    let (name, distribution) = match kind {
+        "fixed" => match value.parse() {
+            Ok(gap) if gap > 0 => (spec.to_string(), GapDistribution::Fixed(gap)),
+            _ => return Err(invalid(spec, "the gap must be a positive integer")),
+        },
+        "geometric" => match value.parse() {
+            Ok(p) if p > 0.0 && p <= 1.0 => (spec.to_string(), GapDistribution::Geometric(p)),
+            _ => return Err(invalid(spec, "the probability must be in (0, 1]")),
+        },
+        "empirical" => {
+            let path = Path::new(value);
+            let stem = path.file_stem().map(|stem| stem.to_string_lossy());
+            (
+                format!("empirical:{}", stem.unwrap_or_default()),
+                GapDistribution::Empirical(load_histogram(path)?),
+            )
+        }
+        _ => {
+            return Err(invalid(
+                spec,
+                "expected `fixed`, `geometric` or `empirical`",
+            ))
+        }
+    };
+
+    Ok(SlotGaps {
+        // Distributions are created once per run and their names are shared by
+        // every SlotHashes benchmark, like the default names.
+        name: Box::leak(name.into_boxed_str()),
+        distribution,
+    })
+}
+
+fn load_histogram(path: &Path) -> Result<Vec<(u64, u64)>, EisodosError> {
+    let contents = fs::read_to_string(path).map_err(|error| EisodosError::read(path, error))?;
+
+    let histogram: Vec<(u64, u64)> = contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut fields = line.split_whitespace().map(str::parse::<u64>);
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some(Ok(gap)), Some(Ok(weight)), None) if gap > 0 => Ok((gap, weight)),
+                _ => Err(EisodosError::Spec(format!(
+                    "invalid histogram line `{line}` in {}, expected `<gap> <weight>`",
+                    path.display()
+                ))),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    if !histogram.iter().any(|&(_, weight)| weight > 0) {
+        return Err(EisodosError::Spec(format!(
+            "histogram {} has no weight",
+            path.display()
+        )));
+    }
+    Ok(histogram)
+}
@@ -0,0 +1,171 @@
+//! Differential fuzzing of the instruction tags.
+//!
+//! Random instruction data is sent, without accounts, to every program, each of
+//! which either rejects it while unpacking (`InvalidInstructionData`) or
+//! accepts it, usually to fail on the missing accounts. The programs must
+//! agree, so a tag added to one program but not the others, or unpacked
+//! differently, is caught. Tags implemented by only some of the programs on
+//! purpose are skipped.
+//!
+//! `EISODOS_FUZZ_CASES` sets the number of inputs (`10000` by default) and
+//! `EISODOS_FUZZ_SEED` the seed of their generator, printed on failure so that
+//! a divergence can be reproduced.
+
+use super::{programs::BenchProgram, runner::exit_on_error, setup, VERSION_2};
+use solana_instruction::{error::InstructionError, Instruction};
+
+/// Tags not implemented by every program on purpose.
+#[rustfmt::skip]
+const FRAMEWORK_SPECIFIC_TAGS: &[u8] = &[
+    // Unchecked SlotHashes lookups, Dispatch and the Clock and Rent sysvar
+    // instructions (pinocchio)
+    8, 9, 10, 11, 20, 21, 22, 23, 24,
+    // WriteStateBorsh and WriteStateBincode (solana-program and
+    // solana-nostd-entrypoint)
+    30, 31,
+    // Zero-copy SlotHashes lookups (solana-nostd-entrypoint)
+    33, 34, 35,
+    // StakeHistoryPosition and SlotHashesPositionSysvar (solana-program)
+    45, 48,
+    // Allocate (pinocchio)
+    49,
+];
+
+/// Tags above the largest one, so unknown tags are sent as well.
+const MAX_FUZZ_TAG: u8 = 63;
+
+/// Longest instruction data sent, past the largest instruction (`AppendLeaf`,
+/// versioned).
+const MAX_FUZZ_LEN: usize = 40;
+
+/// Lengths of the instruction data of the implemented instructions, sent more
+/// often than other lengths: the tag alone or followed by 1 to 5, 8 or 32
+/// bytes.
+const INSTRUCTION_LENS: [usize; 8] = [1, 2, 3, 4, 5, 6, 9, 33];
+
+/// SplitMix64 generator of the fuzzed inputs.
+struct Generator(u64);
+
+impl Generator {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    /// Random instruction data, mostly starting with a known tag, raw or
+    /// versioned.
+    fn data(&mut self) -> Vec<u8> {
+        let len = match self.below(2) {
+            0 => self.below(MAX_FUZZ_LEN + 1),
+            _ => INSTRUCTION_LENS[self.below(INSTRUCTION_LENS.len())],
+        };
+        let mut data: Vec<u8> = (0..len).map(|_| self.next() as u8).collect();
+
+        if let Some(first) = data.first_mut() {
+            match self.below(8) {
+                0..=4 => *first = self.below(MAX_FUZZ_TAG as usize + 1) as u8,
+                5 => {
+                    *first = VERSION_2;
+                    let tag = self.below(MAX_FUZZ_TAG as usize + 1) as u8;
+                    data.insert(1, tag);
+                }
+                _ => {}
+            }
+        }
+        data
+    }
+}
+
+/// Returns the tag of the instruction data, versioned or not.
+fn tag(data: &[u8]) -> Option<u8> {
+    match data {
+        [VERSION_2, tag, ..] | [tag, ..] => Some(*tag),
+        [] => None,
+    }
+}
+
+fn env_or(name: &str, default: u64) -> u64 {
+    match std::env::var(name) {
+        Ok(value) => value
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid {name} `{value}`, expected an integer")),
+        Err(_) => default,
+    }
+}
+
+/// Sends the same random instruction data to every program of `programs` and
+/// checks that they agree on rejecting it while unpacking.
+pub fn run(programs: &[&dyn BenchProgram]) {
+    let [first, ..] = programs else {
+        return;
+    };
+    let mut mollusk = exit_on_error(setup(*first));
+    for program in &programs[1..] {
+        mollusk.add_program(
+            &program.id(),
+            program.name(),
+            &mollusk_svm::program::loader_keys::LOADER_V3,
+        );
+    }
+
+    let _span = tracing::info_span!("tag_fuzz").entered();
+
+    let cases = env_or("EISODOS_FUZZ_CASES", 10_000);
+    let seed = env_or("EISODOS_FUZZ_SEED", 0);
+    let mut generator = Generator(seed);
+    let mut divergences = Vec::new();
+    let mut checked = 0;
+
+    for _ in 0..cases {
+        let data = generator.data();
+        if tag(&data).is_some_and(|tag| FRAMEWORK_SPECIFIC_TAGS.contains(&tag)) {
+            continue;
+        }
+        checked += 1;
+
+        let rejected: Vec<bool> = programs
+            .iter()
+            .map(|program| {
+                let instruction = Instruction {
+                    program_id: program.id(),
+                    accounts: vec![],
+                    data: data.clone(),
+                };
+                let result = mollusk.process_instruction(&instruction, &[]);
+                result.raw_result == Err(InstructionError::InvalidInstructionData)
+            })
+            .collect();
+
+        if rejected.contains(&true) && rejected.contains(&false) {
+            let verdicts: Vec<String> = programs
+                .iter()
+                .zip(&rejected)
+                .map(|(program, rejected)| {
+                    let verdict = if *rejected { "rejects" } else { "accepts" };
+                    format!("{} {verdict}", program.name())
+                })
+                .collect();
+            divergences.push(format!("{data:?}: {}", verdicts.join(", ")));
+        }
+    }
+
+    tracing::info!(
+        checked,
+        divergences = divergences.len(),
+        seed,
+        "tag fuzzing done"
+    );
+    assert!(
+        divergences.is_empty(),
+        "programs disagree on {} of {checked} inputs (EISODOS_FUZZ_SEED={seed}):\n{}",
+        divergences.len(),
+        divergences.join("\n")
+    );
+}
@@ -1,7 +1,6 @@
 #![feature(test)]
 
 extern crate mollusk_svm;
-extern crate mollusk_svm_bencher;
 extern crate solana_account;
 extern crate solana_instruction;
 extern crate solana_pubkey;
@@ -19,6 +18,6 @@ mod solana_program {
 
     #[bench]
     fn run(_bencher: &mut Bencher) {
-        runner::run(&eisodos_solana_program::ID, "eisodos_solana_program");
+        runner::run(&programs::SolanaProgram);
     }
 }
@@ -0,0 +1,26 @@
+#![feature(test)]
+
+extern crate mollusk_svm;
+extern crate solana_account;
+extern crate solana_instruction;
+extern crate solana_pubkey;
+extern crate test;
+
+mod setup;
+use setup::*;
+
+#[cfg(test)]
+mod tag_fuzz {
+
+    use super::*;
+    use test::Bencher;
+
+    #[bench]
+    fn run(_bencher: &mut Bencher) {
+        setup::tag_fuzz::run(&[
+            &programs::Pinocchio,
+            &programs::SolanaProgram,
+            &programs::SolanaNostdEntrypoint,
+        ]);
+    }
+}
@@ -0,0 +1,251 @@
+//! Bisection of the commit introducing a compute unit regression.
+//!
+//! The commits between a good and a bad commit are checked out in a separate
+//! worktree (`target/bisect`), kept between bisections so that its build cache
+//! is reused, and only the program of the benchmark is rebuilt and
+//! benchmarked. `EISODOS_BENCHMARK` selects the benchmark; commits predating
+//! it run every benchmark of the program, the result being the same.
+//!
+//! A commit is bad when the benchmark consumes more CUs than at the good
+//! commit, so the search finds the first bad commit as long as the CUs do not
+//! go back down in between.
+
+use crate::error::EisodosError;
+use crate::results;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Location of the worktree the commits are checked out in.
+pub const WORKTREE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/bisect");
+
+/// Benchmark to bisect, by id.
+#[derive(Debug, PartialEq)]
+pub struct Target {
+    /// Id of the benchmark, e.g. `eisodos_pinocchio: Transfer`.
+    pub id: String,
+    /// Bench target running the benchmarks of the program, e.g. `pinocchio`.
+    pub bench: String,
+    /// Folder of the program in `programs`, e.g. `solana-program`.
+    pub folder: String,
+}
+
+impl Target {
+    /// Parses the benchmark `id`, as written by the runner.
+    pub fn parse(id: &str) -> Result<Self, EisodosError> {
+        let (program, _) = id.split_once(": ").ok_or_else(|| {
+            EisodosError::Spec(format!(
+                "invalid benchmark id `{id}`, expected `<program>: <name>`"
+            ))
+        })?;
+        let bench = program
+            .strip_prefix("eisodos_")
+            .ok_or_else(|| EisodosError::Spec(format!("unknown program `{program}`")))?;
+
+        Ok(Self {
+            id: id.to_string(),
+            bench: bench.to_string(),
+            folder: bench.replace('_', "-"),
+        })
+    }
+}
+
+/// Commit introducing a regression.
+#[derive(Debug, PartialEq)]
+pub struct Bisection {
+    pub commit: String,
+    /// CUs at the good commit.
+    pub good_cus: u64,
+    /// CUs at the first bad commit.
+    pub cus: u64,
+}
+
+/// Returns the index of the first of `commits` (oldest first, the last one
+/// being bad) consuming more than `good_cus`, measuring as few commits as
+/// possible.
+pub fn first_bad(
+    commits: &[String],
+    good_cus: u64,
+    mut measure: impl FnMut(&str) -> Result<u64, EisodosError>,
+) -> Result<usize, EisodosError> {
+    let mut low = 0;
+    let mut high = commits.len().saturating_sub(1);
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if measure(&commits[mid])? > good_cus {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    Ok(low)
+}
+
+/// Finds the commit between `good` and `bad` introducing the regression of
+/// `target`, reporting each measurement to `progress`.
+pub fn bisect(
+    target: &Target,
+    good: &str,
+    bad: &str,
+    mut progress: impl FnMut(&str, u64),
+) -> Result<Bisection, EisodosError> {
+    let commits = git_output(&[
+        "rev-list",
+        "--reverse",
+        "--ancestry-path",
+        &format!("{good}..{bad}"),
+    ])?;
+    let commits: Vec<String> = commits.lines().map(str::to_string).collect();
+    let Some(last) = commits.last() else {
+        return Err(EisodosError::Spec(format!(
+            "{bad} is not a descendant of {good}"
+        )));
+    };
+
+    let worktree = worktree()?;
+    let mut measure_commit = |commit: &str| -> Result<u64, EisodosError> {
+        let cus = measure(&worktree, target, commit)?;
+        progress(commit, cus);
+        Ok(cus)
+    };
+
+    let good_cus = measure_commit(good)?;
+    let bad_cus = measure_commit(last)?;
+    if bad_cus <= good_cus {
+        return Err(EisodosError::Check(format!(
+            "no regression of `{}`: {good_cus} CUs at {good}, {bad_cus} at {bad}",
+            target.id
+        )));
+    }
+
+    let index = first_bad(&commits, good_cus, &mut measure_commit)?;
+    let cus = if index + 1 == commits.len() {
+        bad_cus
+    } else {
+        measure_commit(&commits[index])?
+    };
+
+    Ok(Bisection {
+        commit: commits[index].clone(),
+        good_cus,
+        cus,
+    })
+}
+
+/// Returns the worktree, adding it if needed.
+fn worktree() -> Result<PathBuf, EisodosError> {
+    let path = PathBuf::from(WORKTREE_PATH);
+    if !path.join(".git").exists() {
+        git_output(&["worktree", "add", "--detach", WORKTREE_PATH])?;
+    }
+    Ok(path)
+}
+
+/// Checks out `commit` in the worktree, then builds the program of `target`
+/// and runs its benchmark.
+fn measure(worktree: &Path, target: &Target, commit: &str) -> Result<u64, EisodosError> {
+    run(Command::new("git")
+        .current_dir(worktree)
+        .args(["checkout", "--quiet", "--detach", commit]))?;
+
+    let manifest = worktree
+        .join("programs")
+        .join(&target.folder)
+        .join("Cargo.toml");
+    if !manifest.exists() {
+        return Err(EisodosError::Build(format!(
+            "no program `{}` at {commit}, only the default builds can be bisected",
+            target.folder
+        )));
+    }
+    run(Command::new("cargo")
+        .current_dir(worktree)
+        .arg("build-sbf")
+        .arg("--manifest-path")
+        .arg(&manifest))?;
+
+    let results_path = worktree.join("target/benches/compute_units.md");
+    let _ = std::fs::remove_file(&results_path);
+    run(Command::new("cargo")
+        .current_dir(worktree.join("benchmark"))
+        .args(["bench", "--bench", &target.bench])
+        .env("EISODOS_SINKS", "markdown")
+        .env("EISODOS_BENCHMARK", &target.id)
+        .env_remove("EISODOS_SCENARIOS")
+        .env_remove("EISODOS_TAGS")
+        .env_remove("EISODOS_SHARD"))?;
+
+    let results =
+        results::load(&results_path).map_err(|error| EisodosError::read(&results_path, error))?;
+    results
+        .iter()
+        .find(|result| result.id() == target.id)
+        .map(|result| result.cus)
+        .ok_or_else(|| EisodosError::Check(format!("no benchmark `{}` at {commit}", target.id)))
+}
+
+fn run(command: &mut Command) -> Result<(), EisodosError> {
+    let status = command
+        .status()
+        .map_err(|error| EisodosError::Build(format!("failed to run {command:?}: {error}")))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(EisodosError::Build(format!("{command:?} failed: {status}")))
+    }
+}
+
+fn git_output(args: &[&str]) -> Result<String, EisodosError> {
+    let output = Command::new("git")
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(args)
+        .output()
+        .map_err(|error| EisodosError::Build(format!("failed to run git: {error}")))?;
+    if !output.status.success() {
+        return Err(EisodosError::Build(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_parse() {
+        assert_eq!(
+            Target::parse("eisodos_solana_program: Position (Idx 0, Strictly1)").unwrap(),
+            Target {
+                id: "eisodos_solana_program: Position (Idx 0, Strictly1)".to_string(),
+                bench: "solana_program".to_string(),
+                folder: "solana-program".to_string(),
+            }
+        );
+        assert!(Target::parse("Transfer").is_err());
+        assert!(Target::parse("other: Transfer").is_err());
+    }
+
+    #[test]
+    fn test_first_bad() {
+        let commits: Vec<String> = (0..10).map(|commit| commit.to_string()).collect();
+        let cus = [100, 100, 100, 90, 100, 120, 120, 130, 120, 120];
+
+        let mut measured = Vec::new();
+        let index = first_bad(&commits, 100, |commit| {
+            measured.push(commit.to_string());
+            Ok(cus[commit.parse::<usize>().unwrap()])
+        });
+
+        assert_eq!(index.unwrap(), 5);
+        assert!(measured.len() <= 4);
+        assert_eq!(
+            first_bad(&commits[9..], 100, |_| unreachable!()).unwrap(),
+            0
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Errors of the library and of the benchmark setup.
+//!
+//! Every kind of failure has its own variant, so callers can tell a failed
+//! check (a regression, a size change) from invalid input or an environment
+//! problem, and its own exit code of `coyote` and the benchmarks.
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum EisodosError {
+    /// Invalid command line arguments or input, e.g. a malformed benchmark id
+    /// or option value.
+    Spec(String),
+    /// A file could not be read or written.
+    Io { context: String, source: io::Error },
+    /// A program binary could not be parsed.
+    Elf(String),
+    /// A command building or benchmarking the programs (`cargo`, `git`)
+    /// failed.
+    Build(String),
+    /// A check failed: results, sizes or accounts differ from what was
+    /// expected.
+    Check(String),
+    /// The history of the `sqlite` sink could not be read or written.
+    History(rusqlite::Error),
+}
+
+impl EisodosError {
+    /// Failure to read the file at `path`.
+    pub fn read(path: impl AsRef<Path>, source: io::Error) -> Self {
+        Self::Io {
+            context: format!("failed to read {}", path.as_ref().display()),
+            source,
+        }
+    }
+
+    /// Failure to write the file at `path`.
+    pub fn write(path: impl AsRef<Path>, source: io::Error) -> Self {
+        Self::Io {
+            context: format!("failed to write {}", path.as_ref().display()),
+            source,
+        }
+    }
+
+    /// Returns the exit code of `coyote` and the benchmarks failing with the
+    /// error:
+    ///
+    /// - `1` for a failed check ([`Self::Check`]), like a failed test.
+    /// - `2` for invalid input ([`Self::Spec`]), like a usage error.
+    /// - `3` for a file that could not be read or written ([`Self::Io`]).
+    /// - `4` for an invalid program binary ([`Self::Elf`]).
+    /// - `5` for a failed `cargo` or `git` command ([`Self::Build`]).
+    /// - `6` for a history error ([`Self::History`]).
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Check(_) => 1,
+            Self::Spec(_) => 2,
+            Self::Io { .. } => 3,
+            Self::Elf(_) => 4,
+            Self::Build(_) => 5,
+            Self::History(_) => 6,
+        }
+    }
+}
+
+impl fmt::Display for EisodosError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Spec(message) | Self::Build(message) | Self::Check(message) => {
+                write!(f, "{message}")
+            }
+            Self::Io { context, source } => write!(f, "{context}: {source}"),
+            Self::Elf(message) => write!(f, "invalid program binary: {message}"),
+            Self::History(error) => write!(f, "history error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for EisodosError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::History(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for EisodosError {
+    fn from(error: rusqlite::Error) -> Self {
+        Self::History(error)
+    }
+}
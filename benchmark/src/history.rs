@@ -0,0 +1,466 @@
+//! History of the results written by the `sqlite` sink.
+//!
+//! Every run inserts a row per benchmark into the `compute_units` table, with
+//! the commit the programs were built from, so the history grows without
+//! bound. Old runs can be pruned by timestamp or commit, runs identical to the
+//! previous run of the same program compacted away, and the retained history
+//! exported as JSON lines, in the format of the `json` sink. The results of a
+//! commit can also serve as the baseline new results are checked against, so
+//! that regressions are caught as soon as they are benchmarked.
+
+use crate::results::BenchResult;
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+/// Default location of the database written by the `sqlite` sink.
+pub const HISTORY_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../target/benches/compute_units.db"
+);
+
+/// Minimum length of a commit matched by prefix, so that an empty or short
+/// commit does not match the runs of every commit.
+pub const MIN_COMMIT_LEN: usize = 7;
+
+/// Condition of the rows of the commit `?1`, full or abbreviated to at least
+/// [`MIN_COMMIT_LEN`] (`?2`) characters. Rows written before full commits were
+/// recorded hold abbreviated ones, so either may be a prefix of the other.
+const COMMIT_MATCHES: &str = "length(?1) >= ?2 AND length(git_commit) >= ?2
+    AND (substr(git_commit, 1, length(?1)) = ?1 OR substr(?1, 1, length(git_commit)) = git_commit)";
+
+/// A run of a program, keyed by program and timestamp, with the CUs of each
+/// of its benchmarks.
+type Run = ((String, String), Vec<(String, u64)>);
+
+/// A row of the history.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryRow {
+    pub timestamp: String,
+    pub program: String,
+    pub scenario: String,
+    pub cus: u64,
+    /// Commit of the run, unknown for rows written before it was recorded.
+    pub commit: Option<String>,
+}
+
+/// Opens the database at `path`, creating the table or adding the commit
+/// column to a table written by an older sink if needed.
+pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Connection> {
+    let connection = Connection::open(path)?;
+    migrate(&connection)?;
+    Ok(connection)
+}
+
+fn migrate(connection: &Connection) -> rusqlite::Result<()> {
+    connection.execute_batch(
+        "CREATE TABLE IF NOT EXISTS compute_units (
+            timestamp TEXT NOT NULL,
+            program TEXT NOT NULL,
+            scenario TEXT NOT NULL,
+            cus INTEGER NOT NULL,
+            git_commit TEXT
+        )",
+    )?;
+
+    let has_commit: bool = connection.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('compute_units') WHERE name = 'git_commit'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_commit {
+        connection.execute("ALTER TABLE compute_units ADD COLUMN git_commit TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Returns the commit the benchmarks are run from: `EISODOS_COMMIT` if set,
+/// otherwise the `HEAD` of the repository, if any.
+pub fn current_commit() -> Option<String> {
+    if let Ok(commit) = std::env::var("EISODOS_COMMIT") {
+        return Some(commit);
+    }
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !commit.is_empty()).then_some(commit)
+}
+
+/// Inserts the row of a benchmark.
+pub fn insert(connection: &Connection, row: &HistoryRow) -> rusqlite::Result<()> {
+    connection.execute(
+        "INSERT INTO compute_units (timestamp, program, scenario, cus, git_commit)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            row.timestamp,
+            row.program,
+            row.scenario,
+            row.cus as i64,
+            row.commit
+        ],
+    )?;
+    Ok(())
+}
+
+/// Returns every row, ordered by timestamp, program and scenario.
+pub fn rows(connection: &Connection) -> rusqlite::Result<Vec<HistoryRow>> {
+    let mut statement = connection.prepare(
+        "SELECT timestamp, program, scenario, cus, git_commit FROM compute_units
+         ORDER BY timestamp, program, scenario",
+    )?;
+    let rows = statement.query_map([], |row| {
+        Ok(HistoryRow {
+            timestamp: row.get(0)?,
+            program: row.get(1)?,
+            scenario: row.get(2)?,
+            cus: row.get::<_, i64>(3)? as u64,
+            commit: row.get(4)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Returns the results of the latest run of every benchmark at `commit`, full
+/// or abbreviated to at least [`MIN_COMMIT_LEN`] characters, ordered by
+/// program and scenario.
+pub fn results_at(connection: &Connection, commit: &str) -> rusqlite::Result<Vec<BenchResult>> {
+    let mut statement = connection.prepare(&format!(
+        "SELECT program, scenario, cus FROM compute_units
+         WHERE {COMMIT_MATCHES}
+         ORDER BY program, scenario, timestamp DESC"
+    ))?;
+    let rows = statement.query_map(params![commit, MIN_COMMIT_LEN as i64], |row| {
+        Ok(BenchResult {
+            program: row.get(0)?,
+            scenario: row.get(1)?,
+            cus: row.get::<_, i64>(2)? as u64,
+        })
+    })?;
+
+    let mut results: Vec<BenchResult> = Vec::new();
+    for result in rows {
+        let result = result?;
+        // Rows of a benchmark are next to each other, the latest first.
+        if results.last().is_some_and(|last| last.id() == result.id()) {
+            continue;
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// Compute units of a benchmark exceeding its baseline.
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub id: String,
+    pub baseline: u64,
+    pub cus: u64,
+}
+
+impl Regression {
+    /// Returns the increase from the baseline, in percent.
+    pub fn percent(&self) -> f64 {
+        if self.baseline == 0 {
+            return f64::INFINITY;
+        }
+        (self.cus as f64 - self.baseline as f64) / self.baseline as f64 * 100.0
+    }
+}
+
+/// Returns the benchmarks of `results` consuming more than `threshold`
+/// percent more CUs than in `baseline`. Benchmarks missing from the baseline
+/// are not checked.
+pub fn regressions(
+    baseline: &[BenchResult],
+    results: &[BenchResult],
+    threshold: f64,
+) -> Vec<Regression> {
+    results
+        .iter()
+        .filter_map(|result| {
+            let base = baseline.iter().find(|base| base.id() == result.id())?;
+            let regression = Regression {
+                id: result.id(),
+                baseline: base.cus,
+                cus: result.cus,
+            };
+            (result.cus > base.cus && regression.percent() > threshold).then_some(regression)
+        })
+        .collect()
+}
+
+/// Deletes the rows of runs started before `timestamp`, returning the number
+/// of rows deleted.
+///
+/// Timestamps are compared as strings, so a prefix of the runner's format
+/// (e.g. `2025-01-31`) prunes every run started before that day.
+pub fn prune_before(connection: &Connection, timestamp: &str) -> rusqlite::Result<usize> {
+    connection.execute(
+        "DELETE FROM compute_units WHERE timestamp < ?1",
+        params![timestamp],
+    )
+}
+
+/// Deletes the rows of runs of `commit`, full or abbreviated to at least
+/// [`MIN_COMMIT_LEN`] characters, returning the number of rows deleted.
+pub fn prune_commit(connection: &Connection, commit: &str) -> rusqlite::Result<usize> {
+    connection.execute(
+        &format!("DELETE FROM compute_units WHERE {COMMIT_MATCHES}"),
+        params![commit, MIN_COMMIT_LEN as i64],
+    )
+}
+
+/// Deletes every run (the rows of a program sharing a timestamp) measuring the
+/// same CUs for the same benchmarks as the previous run of the program, so
+/// only the runs where something changed are kept. Returns the number of runs
+/// deleted.
+pub fn compact(connection: &mut Connection) -> rusqlite::Result<usize> {
+    // Runs keyed by program and timestamp, so the runs of a program are next
+    // to each other once sorted.
+    let mut runs: Vec<Run> = Vec::new();
+    for row in rows(connection)? {
+        let run = (row.program, row.timestamp);
+        match runs.last_mut() {
+            Some((last, results)) if *last == run => results.push((row.scenario, row.cus)),
+            _ => runs.push((run, vec![(row.scenario, row.cus)])),
+        }
+    }
+    runs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let duplicates: Vec<&(String, String)> = runs
+        .windows(2)
+        .filter(|pair| {
+            let ((previous_program, _), previous_results) = &pair[0];
+            let ((program, _), results) = &pair[1];
+            previous_program == program && previous_results == results
+        })
+        .map(|pair| &pair[1].0)
+        .collect();
+
+    let transaction = connection.transaction()?;
+    for (program, timestamp) in &duplicates {
+        transaction.execute(
+            "DELETE FROM compute_units WHERE timestamp = ?1 AND program = ?2",
+            params![timestamp, program],
+        )?;
+    }
+    transaction.commit()?;
+
+    Ok(duplicates.len())
+}
+
+/// Renders the rows as JSON lines, one object per benchmark.
+pub fn to_json_lines(rows: &[HistoryRow]) -> String {
+    rows.iter()
+        .map(|row| {
+            let line = serde_json::json!({
+                "timestamp": row.timestamp,
+                "program": row.program,
+                "scenario": row.scenario,
+                "cus": row.cus,
+                "commit": row.commit,
+            });
+            format!("{line}\n")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(timestamp: &str, program: &str, scenario: &str, cus: u64, commit: &str) -> HistoryRow {
+        HistoryRow {
+            timestamp: timestamp.to_string(),
+            program: program.to_string(),
+            scenario: scenario.to_string(),
+            cus,
+            commit: Some(commit.to_string()),
+        }
+    }
+
+    fn history(rows: &[HistoryRow]) -> Connection {
+        let connection = Connection::open_in_memory().unwrap();
+        migrate(&connection).unwrap();
+        for row in rows {
+            insert(&connection, row).unwrap();
+        }
+        connection
+    }
+
+    #[test]
+    fn test_migrate_adds_commit() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE compute_units (
+                    timestamp TEXT NOT NULL,
+                    program TEXT NOT NULL,
+                    scenario TEXT NOT NULL,
+                    cus INTEGER NOT NULL
+                );
+                INSERT INTO compute_units VALUES ('2025-01-01 00:00:00 UTC', 'a', 'Ping', 10);",
+            )
+            .unwrap();
+
+        migrate(&connection).unwrap();
+        migrate(&connection).unwrap();
+
+        assert_eq!(
+            rows(&connection).unwrap(),
+            vec![HistoryRow {
+                commit: None,
+                ..row("2025-01-01 00:00:00 UTC", "a", "Ping", 10, "")
+            }]
+        );
+    }
+
+    #[test]
+    fn test_results_at() {
+        let connection = history(&[
+            row("2025-01-01 00:00:00 UTC", "a", "Ping", 10, "1111111"),
+            row("2025-01-01 00:00:00 UTC", "a", "Transfer", 20, "1111111"),
+            row("2025-01-02 00:00:00 UTC", "a", "Ping", 11, "1111111"),
+            row("2025-01-03 00:00:00 UTC", "a", "Ping", 12, "2222222"),
+            row("2025-01-04 00:00:00 UTC", "a", "Ping", 13, &"3".repeat(40)),
+        ]);
+
+        let result = |scenario: &str, cus| BenchResult {
+            program: "a".to_string(),
+            scenario: scenario.to_string(),
+            cus,
+        };
+        assert_eq!(
+            results_at(&connection, &"1".repeat(40)).unwrap(),
+            vec![result("Ping", 11), result("Transfer", 20)]
+        );
+        assert_eq!(
+            results_at(&connection, "3333333").unwrap(),
+            vec![result("Ping", 13)]
+        );
+        assert!(results_at(&connection, "111").unwrap().is_empty());
+        assert!(results_at(&connection, "4444444").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_regressions() {
+        let result = |scenario: &str, cus| BenchResult {
+            program: "a".to_string(),
+            scenario: scenario.to_string(),
+            cus,
+        };
+        let baseline = [
+            result("Ping", 100),
+            result("Log", 100),
+            result("Transfer", 1000),
+        ];
+        let results = [
+            result("Ping", 102),
+            result("Log", 101),
+            result("Transfer", 900),
+            result("Account (3)", 500),
+        ];
+
+        assert_eq!(
+            regressions(&baseline, &results, 1.0),
+            vec![Regression {
+                id: "a: Ping".to_string(),
+                baseline: 100,
+                cus: 102,
+            }]
+        );
+        assert_eq!(regressions(&baseline, &results, 0.0).len(), 2);
+    }
+
+    #[test]
+    fn test_prune() {
+        let connection = history(&[
+            row("2025-01-01 10:00:00 UTC", "a", "Ping", 10, "1111111"),
+            row("2025-01-02 10:00:00 UTC", "a", "Ping", 11, "2222222"),
+            row("2025-01-03 10:00:00 UTC", "a", "Ping", 12, "3333333"),
+            row("2025-01-04 10:00:00 UTC", "a", "Ping", 13, &"4".repeat(40)),
+        ]);
+
+        assert_eq!(prune_before(&connection, "2025-01-02").unwrap(), 1);
+        // Too short to tell commits apart.
+        assert_eq!(prune_commit(&connection, "").unwrap(), 0);
+        assert_eq!(prune_commit(&connection, "33").unwrap(), 0);
+        // A full commit matches its abbreviation, and the other way around.
+        assert_eq!(prune_commit(&connection, &"3".repeat(40)).unwrap(), 1);
+        assert_eq!(prune_commit(&connection, "4444444").unwrap(), 1);
+        assert_eq!(
+            rows(&connection).unwrap(),
+            vec![row("2025-01-02 10:00:00 UTC", "a", "Ping", 11, "2222222")]
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut connection = history(&[
+            row("2025-01-01 00:00:00 UTC", "a", "Ping", 10, "1"),
+            row("2025-01-01 00:00:00 UTC", "a", "Transfer", 20, "1"),
+            row("2025-01-01 00:00:00 UTC", "b", "Ping", 30, "1"),
+            // Same as the previous run of `a`, although a run of `b` is between.
+            row("2025-01-01 00:00:01 UTC", "b", "Ping", 31, "2"),
+            row("2025-01-02 00:00:00 UTC", "a", "Ping", 10, "2"),
+            row("2025-01-02 00:00:00 UTC", "a", "Transfer", 20, "2"),
+            // Changed, then back to the first results.
+            row("2025-01-03 00:00:00 UTC", "a", "Ping", 9, "3"),
+            row("2025-01-03 00:00:00 UTC", "a", "Transfer", 20, "3"),
+            row("2025-01-04 00:00:00 UTC", "a", "Ping", 10, "4"),
+            row("2025-01-04 00:00:00 UTC", "a", "Transfer", 20, "4"),
+        ]);
+
+        assert_eq!(compact(&mut connection).unwrap(), 1);
+        let commits: Vec<(String, String)> = rows(&connection)
+            .unwrap()
+            .into_iter()
+            .map(|row| (row.program, row.commit.unwrap()))
+            .collect();
+        assert!(!commits.contains(&("a".to_string(), "2".to_string())));
+        assert_eq!(commits.len(), 8);
+        assert_eq!(compact(&mut connection).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_to_json_lines() {
+        let rows = [
+            row("2025-01-01 00:00:00 UTC", "a", "Ping", 10, "abc1234"),
+            HistoryRow {
+                commit: None,
+                ..row(
+                    "2025-01-01 00:00:00 UTC",
+                    "a",
+                    "Position (Idx 0, Strictly1)",
+                    20,
+                    "",
+                )
+            },
+        ];
+
+        let lines: Vec<serde_json::Value> = to_json_lines(&rows)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                serde_json::json!({
+                    "timestamp": "2025-01-01 00:00:00 UTC",
+                    "program": "a",
+                    "scenario": "Ping",
+                    "cus": 10,
+                    "commit": "abc1234",
+                }),
+                serde_json::json!({
+                    "timestamp": "2025-01-01 00:00:00 UTC",
+                    "program": "a",
+                    "scenario": "Position (Idx 0, Strictly1)",
+                    "cus": 20,
+                    "commit": null,
+                }),
+            ]
+        );
+    }
+}
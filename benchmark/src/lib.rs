@@ -0,0 +1,11 @@
+//! Post-processing of the compute unit results written by the benchmarks,
+//! maintenance of their history and analysis of the benchmarked program
+//! binaries.
+
+pub mod bisect;
+pub mod error;
+pub mod history;
+pub mod report;
+pub mod results;
+pub mod size;
+pub mod stack;
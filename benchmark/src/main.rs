@@ -1,3 +1,369 @@
-fn main() {
-    println!("beep beep");
+use eisodos::error::EisodosError;
+use eisodos::{bisect, history, report, results, size, stack};
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: coyote <COMMAND> [OPTIONS] [RESULTS | DEPLOY | HISTORY]
+
+Commands:
+  scaling      Fit the Account (N) sweep to fixed + per-account CUs
+  ratio        Show CUs as a ratio of a baseline program
+  compare      Pivot the CUs of RESULTS... into a column per program, with the
+               delta to the cheapest
+  heap         Show the heap high-water mark and allocations of each benchmark
+               next to its CUs
+  slot-hashes  Compare naive and interpolated SlotHashes searches by target index
+  ranges       Show the best to worst case CUs of search and parsing benchmarks
+  fees         Translate CUs into priority fees at several CU prices
+  tags         Group benchmarks by tag
+  summary      Summarize the changes from base results, e.g. for a PR comment
+  merge        Merge result files, e.g. of sharded runs, into one
+  stack        Estimate the static stack usage of the programs in DEPLOY
+  sizes        Show the binary and core::fmt sizes of the programs in DEPLOY
+  size-check   Check the sizes of the programs in DEPLOY against the snapshot
+  size-update  Write the sizes of the programs in DEPLOY to the snapshot
+  prune        Delete the runs of HISTORY before a timestamp or of a commit
+  compact      Delete the runs of HISTORY identical to the previous run
+  export       Print the runs of HISTORY as JSON lines
+  bisect       Find the commit introducing a CU regression of a benchmark
+
+Options:
+  --baseline <PROGRAM>    Baseline program of `ratio` [default: eisodos_pinocchio]
+  --program <PROGRAM>     Program of `slot-hashes` [default: eisodos_pinocchio]
+  --heap <PATH>           Heap usage of `heap` [default: target/benches/heap_usage.md]
+  --allocations <PATH>    Allocation counts of `heap`
+                          [default: target/benches/allocations.md]
+  --params <PATH>         Benchmark parameters of `tags`
+                          [default: target/benches/params.md]
+  --base <PATH>           Base results of `summary`
+  --top <N>               Regressions and improvements listed by `summary`
+                          [default: 5]
+  --prices <PRICES>       Comma-separated CU prices of `fees`, in microlamports
+                          [default: 1000,10000,100000]
+  --snapshot <PATH>       Snapshot of `size-check` and `size-update`
+                          [default: benchmark/sizes.md]
+  --tolerance <PERCENT>   Size change tolerated by `size-check` [default: 2]
+  --before <TIMESTAMP>    Prune the runs started before, e.g. 2025-01-31
+  --commit <COMMIT>       Prune the runs of the commit, full or abbreviated to
+                          at least 7 characters
+  --id <ID>               Benchmark of `bisect`, e.g. \"eisodos_pinocchio: Transfer\"
+  --good <COMMIT>         Commit of `bisect` without the regression
+  --bad <COMMIT>          Commit of `bisect` with the regression [default: HEAD]
+
+RESULTS defaults to target/benches/compute_units.md, DEPLOY to target/deploy
+and HISTORY to target/benches/compute_units.db
+
+Exit codes: 1 for a failed check, 2 for invalid input, 3 for a file error,
+4 for an invalid program binary, 5 for a failed cargo or git command and 6
+for a history error";
+
+/// Command line arguments: the command, `--name value` options and the
+/// remaining positional arguments.
+struct Args {
+    command: Option<String>,
+    options: Vec<(String, String)>,
+    positional: Vec<String>,
+}
+
+impl Args {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, EisodosError> {
+        let mut command = None;
+        let mut options = Vec::new();
+        let mut positional = Vec::new();
+        let mut args = args.peekable();
+
+        while let Some(arg) = args.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                let value = args
+                    .next()
+                    .ok_or_else(|| EisodosError::Spec(format!("missing value for --{name}")))?;
+                options.push((name.to_string(), value));
+            } else if command.is_none() {
+                command = Some(arg);
+            } else {
+                positional.push(arg);
+            }
+        }
+
+        Ok(Self {
+            command,
+            options,
+            positional,
+        })
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(option, _)| option == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn results_path(&self) -> &str {
+        self.positional
+            .first()
+            .map(String::as_str)
+            .unwrap_or(results::RESULTS_PATH)
+    }
+}
+
+fn run(args: &Args) -> Result<(), EisodosError> {
+    let open_history = || {
+        let path = args
+            .positional
+            .first()
+            .map(String::as_str)
+            .unwrap_or(history::HISTORY_PATH);
+        if !Path::new(path).exists() {
+            return Err(EisodosError::Spec(format!(
+                "{path} not found, written by the `sqlite` sink"
+            )));
+        }
+        Ok(history::open(path)?)
+    };
+    let deploy_dir = || {
+        args.positional
+            .first()
+            .map(String::as_str)
+            .unwrap_or(stack::DEPLOY_PATH)
+    };
+    let load = || {
+        let path = args.results_path();
+        results::load(path).map_err(|error| EisodosError::read(path, error))
+    };
+
+    match args.command.as_deref() {
+        Some("scaling") => {
+            let results = load()?;
+            print!(
+                "{}",
+                report::account_scaling_markdown(&report::account_scaling(&results))
+            );
+        }
+        Some("ratio") => {
+            let results = load()?;
+            let baseline = args.option("baseline").unwrap_or("eisodos_pinocchio");
+            print!("{}", report::ratio_markdown(&results, baseline)?);
+        }
+        Some("compare") => {
+            // Results of several files, e.g. of separate runs, are merged.
+            let results = if args.positional.len() > 1 {
+                let sets = args
+                    .positional
+                    .iter()
+                    .map(|path| {
+                        results::load(path).map_err(|error| EisodosError::read(path, error))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                results::merge(sets)
+            } else {
+                load()?
+            };
+            print!("{}", report::compare_markdown(&results));
+        }
+        Some("heap") => {
+            let results = load()?;
+            let path = args.option("heap").unwrap_or(results::HEAP_PATH);
+            let heap = results::load(path).map_err(|error| EisodosError::read(path, error))?;
+            // Written since allocations are counted, so missing for older runs.
+            let path = args
+                .option("allocations")
+                .unwrap_or(results::ALLOCATIONS_PATH);
+            let allocations = results::load(path).unwrap_or_default();
+            print!("{}", report::heap_markdown(&results, &heap, &allocations));
+        }
+        Some("slot-hashes") => {
+            let results = load()?;
+            let program = args.option("program").unwrap_or("eisodos_pinocchio");
+            print!("{}", report::slot_hashes_markdown(&results, program));
+        }
+        Some("ranges") => {
+            let results = load()?;
+            print!("{}", report::ranges_markdown(&results));
+        }
+        Some("fees") => {
+            let results = load()?;
+            let prices = match args.option("prices") {
+                Some(prices) => prices
+                    .split(',')
+                    .map(|price| {
+                        price
+                            .trim()
+                            .parse()
+                            .map_err(|_| EisodosError::Spec(format!("invalid CU price: {price}")))
+                    })
+                    .collect::<Result<Vec<u64>, _>>()?,
+                None => report::DEFAULT_CU_PRICES.to_vec(),
+            };
+            print!("{}", report::fees_markdown(&results, &prices));
+        }
+        Some("tags") => {
+            let results = load()?;
+            let path = args.option("params").unwrap_or(results::PARAMS_PATH);
+            let params =
+                fs::read_to_string(path).map_err(|error| EisodosError::read(path, error))?;
+            print!(
+                "{}",
+                report::tags_markdown(&results, &report::parse_tags(&params))
+            );
+        }
+        Some("summary") => {
+            let results = load()?;
+            let path = args
+                .option("base")
+                .ok_or_else(|| EisodosError::Spec("missing --base <PATH> for summary".into()))?;
+            let base = results::load(path).map_err(|error| EisodosError::read(path, error))?;
+            let top = match args.option("top") {
+                Some(top) => top
+                    .parse()
+                    .map_err(|_| EisodosError::Spec(format!("invalid --top: {top}")))?,
+                None => report::DEFAULT_SUMMARY_TOP,
+            };
+            print!("{}", report::summary_markdown(&base, &results, top));
+        }
+        Some("merge") => {
+            let sets = args
+                .positional
+                .iter()
+                .map(|path| results::load(path).map_err(|error| EisodosError::read(path, error)))
+                .collect::<Result<Vec<_>, _>>()?;
+            print!(
+                "#### Compute Units: merged\n\n{}",
+                results::to_markdown(&results::merge(sets))
+            );
+        }
+        Some("stack") => {
+            print!(
+                "{}",
+                stack::stack_usage_markdown(&stack::load_dir(Path::new(deploy_dir()))?)
+            );
+        }
+        Some("sizes") => {
+            print!(
+                "{}",
+                size::sizes_markdown(&size::load_dir(Path::new(deploy_dir()))?)
+            );
+        }
+        Some("size-check") => {
+            let sizes = size::load_dir(Path::new(deploy_dir()))?;
+            let path = args.option("snapshot").unwrap_or(size::SNAPSHOT_PATH);
+            let snapshot =
+                fs::read_to_string(path).map_err(|error| EisodosError::read(path, error))?;
+            let snapshot = size::parse_snapshot(&snapshot);
+            let tolerance = match args.option("tolerance") {
+                Some(tolerance) => tolerance
+                    .parse()
+                    .map_err(|_| EisodosError::Spec(format!("invalid --tolerance: {tolerance}")))?,
+                None => size::DEFAULT_TOLERANCE,
+            };
+
+            let mut checked = 0;
+            for size in &sizes {
+                if snapshot.iter().any(|(program, _)| *program == size.program) {
+                    checked += 1;
+                } else {
+                    eprintln!("{}: not in the snapshot, not checked", size.program);
+                }
+            }
+            let changes = size::check(&sizes, &snapshot, tolerance);
+            if !changes.is_empty() {
+                let changes: Vec<String> = changes
+                    .iter()
+                    .map(|change| {
+                        format!(
+                            "{}: {} bytes, expected {} ({:+.1}%)",
+                            change.program,
+                            change.bytes,
+                            change.expected,
+                            change.percent()
+                        )
+                    })
+                    .collect();
+                return Err(EisodosError::Check(format!(
+                    "{} programs changed size by more than {tolerance}% (`size-update` updates \
+                     the snapshot):\n{}",
+                    changes.len(),
+                    changes.join("\n")
+                )));
+            }
+            println!("{checked} programs within {tolerance}% of the snapshot");
+        }
+        Some("size-update") => {
+            let sizes = size::load_dir(Path::new(deploy_dir()))?;
+            let path = args.option("snapshot").unwrap_or(size::SNAPSHOT_PATH);
+            fs::write(path, size::snapshot_markdown(&sizes))
+                .map_err(|error| EisodosError::write(path, error))?;
+            println!("Wrote the sizes of {} programs to {path}", sizes.len());
+        }
+        Some("prune") => {
+            let connection = open_history()?;
+            let before = args.option("before");
+            let commit = args.option("commit");
+            if before.is_none() && commit.is_none() {
+                return Err(EisodosError::Spec(
+                    "missing --before <TIMESTAMP> or --commit <COMMIT> for prune".into(),
+                ));
+            }
+            if let Some(commit) = commit.filter(|commit| commit.len() < history::MIN_COMMIT_LEN) {
+                return Err(EisodosError::Spec(format!(
+                    "--commit `{commit}` is too short, expected at least {} characters",
+                    history::MIN_COMMIT_LEN
+                )));
+            }
+            let mut deleted = 0;
+            if let Some(before) = before {
+                deleted += history::prune_before(&connection, before)?;
+            }
+            if let Some(commit) = commit {
+                deleted += history::prune_commit(&connection, commit)?;
+            }
+            println!("Deleted {deleted} rows");
+        }
+        Some("compact") => {
+            let mut connection = open_history()?;
+            let deleted = history::compact(&mut connection)?;
+            println!("Deleted {deleted} duplicate runs");
+        }
+        Some("export") => {
+            let connection = open_history()?;
+            let rows = history::rows(&connection)?;
+            print!("{}", history::to_json_lines(&rows));
+        }
+        Some("bisect") => {
+            let id = args
+                .option("id")
+                .ok_or_else(|| EisodosError::Spec("missing --id <ID> for bisect".into()))?;
+            let good = args
+                .option("good")
+                .ok_or_else(|| EisodosError::Spec("missing --good <COMMIT> for bisect".into()))?;
+            let bad = args.option("bad").unwrap_or("HEAD");
+            let target = bisect::Target::parse(id)?;
+            let bisection = bisect::bisect(&target, good, bad, |commit, cus| {
+                eprintln!("{commit}: {cus} CUs");
+            })?;
+            println!(
+                "First bad commit: {} ({} CUs, {:+} from {good})",
+                bisection.commit,
+                bisection.cus,
+                bisection.cus as i64 - bisection.good_cus as i64
+            );
+        }
+        _ => return Err(EisodosError::Spec(USAGE.to_string())),
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = Args::parse(std::env::args().skip(1)).and_then(|args| run(&args));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::from(error.exit_code())
+        }
+    }
 }
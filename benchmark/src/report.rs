@@ -0,0 +1,825 @@
+use crate::error::EisodosError;
+use crate::results::BenchResult;
+use std::collections::HashMap;
+
+/// Linear model of the entrypoint cost fitted from the `Account (N)` sweep:
+/// `cus = fixed + per_account * N`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountScaling {
+    pub program: String,
+    /// Fixed cost of the entrypoint, in compute units.
+    pub fixed: f64,
+    /// Marginal cost of each account parsed, in compute units.
+    pub per_account: f64,
+    /// Coefficient of determination of the fit.
+    pub r_squared: f64,
+    /// Number of sweep points used for the fit.
+    pub samples: usize,
+}
+
+/// Returns `N` for an `Account (N)` scenario.
+fn account_count(scenario: &str) -> Option<f64> {
+    scenario
+        .strip_prefix("Account (")?
+        .strip_suffix(')')?
+        .parse()
+        .ok()
+}
+
+/// Fits the `Account (N)` results of each program to a linear model with
+/// ordinary least squares. Programs with fewer than two sweep points are
+/// skipped.
+pub fn account_scaling(results: &[BenchResult]) -> Vec<AccountScaling> {
+    let mut programs: Vec<&str> = Vec::new();
+    for result in results {
+        if !programs.contains(&result.program.as_str()) {
+            programs.push(&result.program);
+        }
+    }
+
+    programs
+        .into_iter()
+        .filter_map(|program| {
+            let points: Vec<(f64, f64)> = results
+                .iter()
+                .filter(|result| result.program == program)
+                .filter_map(|result| Some((account_count(&result.scenario)?, result.cus as f64)))
+                .collect();
+            fit(&points).map(|(fixed, per_account, r_squared)| AccountScaling {
+                program: program.to_string(),
+                fixed,
+                per_account,
+                r_squared,
+                samples: points.len(),
+            })
+        })
+        .collect()
+}
+
+/// Ordinary least squares fit returning `(intercept, slope, r_squared)`.
+fn fit(points: &[(f64, f64)]) -> Option<(f64, f64, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let ss_xx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if ss_xx == 0.0 {
+        return None;
+    }
+    let ss_xy: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+
+    let slope = ss_xy / ss_xx;
+    let intercept = mean_y - slope * mean_x;
+
+    let ss_tot: f64 = points.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+    let ss_res: f64 = points
+        .iter()
+        .map(|(x, y)| (y - (intercept + slope * x)).powi(2))
+        .sum();
+    let r_squared = if ss_tot == 0.0 {
+        1.0
+    } else {
+        1.0 - ss_res / ss_tot
+    };
+
+    Some((intercept, slope, r_squared))
+}
+
+/// Renders the account scaling fits as a markdown table.
+pub fn account_scaling_markdown(scaling: &[AccountScaling]) -> String {
+    let mut md = String::new();
+    md.push_str("| Program | Fixed CUs | CUs per account | R² | Samples |\n");
+    md.push_str("|---------|-----------|-----------------|----|---------|\n");
+    for entry in scaling {
+        md.push_str(&format!(
+            "| {} | {:.1} | {:.2} | {:.4} | {} |\n",
+            entry.program, entry.fixed, entry.per_account, entry.r_squared, entry.samples
+        ));
+    }
+    md
+}
+
+/// Results pivoted into scenarios (rows) and programs (columns), both in
+/// order of first appearance.
+pub struct Pivot {
+    pub scenarios: Vec<String>,
+    pub programs: Vec<String>,
+    cus: HashMap<(String, String), u64>,
+}
+
+impl Pivot {
+    pub fn new(results: &[BenchResult]) -> Self {
+        let mut scenarios = Vec::new();
+        let mut programs = Vec::new();
+        let mut cus = HashMap::new();
+
+        for result in results {
+            if !scenarios.contains(&result.scenario) {
+                scenarios.push(result.scenario.clone());
+            }
+            if !programs.contains(&result.program) {
+                programs.push(result.program.clone());
+            }
+            cus.insert(
+                (result.program.clone(), result.scenario.clone()),
+                result.cus,
+            );
+        }
+
+        Self {
+            scenarios,
+            programs,
+            cus,
+        }
+    }
+
+    /// Returns the compute units of `program` for `scenario`, if present.
+    pub fn get(&self, program: &str, scenario: &str) -> Option<u64> {
+        self.cus
+            .get(&(program.to_string(), scenario.to_string()))
+            .copied()
+    }
+
+    /// Returns the program with the lowest compute units for `scenario`.
+    pub fn best(&self, scenario: &str) -> Option<&str> {
+        self.programs
+            .iter()
+            .filter_map(|program| Some((program, self.get(program, scenario)?)))
+            .min_by_key(|(_, cus)| *cus)
+            .map(|(program, _)| program.as_str())
+    }
+}
+
+/// Renders the compute units of every program as a ratio of `baseline`'s,
+/// e.g. `3.40×`, with the cheapest program of each scenario in bold and a
+/// final row counting how many scenarios each program is the cheapest in.
+pub fn ratio_markdown(results: &[BenchResult], baseline: &str) -> Result<String, EisodosError> {
+    let pivot = Pivot::new(results);
+    if !pivot.programs.iter().any(|program| program == baseline) {
+        return Err(EisodosError::Spec(format!(
+            "baseline program `{baseline}` has no results"
+        )));
+    }
+
+    let mut md = format!("| Benchmark (× `{baseline}`) |");
+    for program in &pivot.programs {
+        md.push_str(&format!(" `{program}` |"));
+    }
+    md.push_str("\n|---|");
+    md.push_str(&"---|".repeat(pivot.programs.len()));
+    md.push('\n');
+
+    let mut wins = vec![0usize; pivot.programs.len()];
+
+    for scenario in &pivot.scenarios {
+        let base = pivot.get(baseline, scenario);
+        let best = pivot.best(scenario);
+        md.push_str(&format!("| {scenario} |"));
+
+        for (i, program) in pivot.programs.iter().enumerate() {
+            let cell = match (pivot.get(program, scenario), base) {
+                (Some(cus), Some(base)) if base > 0 => {
+                    format!("{:.2}×", cus as f64 / base as f64)
+                }
+                (Some(cus), _) => format!("{cus} CUs"),
+                (None, _) => "-".to_string(),
+            };
+            if best == Some(program.as_str()) {
+                wins[i] += 1;
+                md.push_str(&format!(" **{cell}** |"));
+            } else {
+                md.push_str(&format!(" {cell} |"));
+            }
+        }
+        md.push('\n');
+    }
+
+    md.push_str("| _Best framework (scenarios)_ |");
+    for count in wins {
+        md.push_str(&format!(" {count} |"));
+    }
+    md.push('\n');
+
+    Ok(md)
+}
+
+/// Renders the compute units of every program side by side, a column per
+/// program, with the delta to the cheapest program of each scenario, e.g.
+/// `3400 (+240.0%)`, the cheapest program being in bold.
+pub fn compare_markdown(results: &[BenchResult]) -> String {
+    let pivot = Pivot::new(results);
+
+    let mut md = String::from("| Benchmark |");
+    for program in &pivot.programs {
+        md.push_str(&format!(" `{program}` |"));
+    }
+    md.push_str("\n|---|");
+    md.push_str(&"---|".repeat(pivot.programs.len()));
+    md.push('\n');
+
+    for scenario in &pivot.scenarios {
+        let best = pivot.best(scenario);
+        let best_cus = best.and_then(|best| pivot.get(best, scenario));
+        md.push_str(&format!("| {scenario} |"));
+
+        for program in &pivot.programs {
+            let cell = match (pivot.get(program, scenario), best_cus) {
+                (Some(cus), _) if best == Some(program.as_str()) => format!("**{cus}**"),
+                (Some(cus), Some(best_cus)) if best_cus > 0 => format!(
+                    "{cus} ({:+.1}%)",
+                    (cus as f64 - best_cus as f64) / best_cus as f64 * 100.0
+                ),
+                (Some(cus), _) => cus.to_string(),
+                (None, _) => "-".to_string(),
+            };
+            md.push_str(&format!(" {cell} |"));
+        }
+        md.push('\n');
+    }
+
+    md
+}
+
+/// Renders the best to worst case range of the compute units of every
+/// benchmark with a pair of `(Best)` and `(Worst)` fixtures, with a column
+/// per program. The standard fixture, if present, is shown in the middle.
+pub fn ranges_markdown(results: &[BenchResult]) -> String {
+    let pivot = Pivot::new(results);
+
+    let mut md = String::from("| Benchmark |");
+    for program in &pivot.programs {
+        md.push_str(&format!(" `{program}` |"));
+    }
+    md.push_str("\n|---|");
+    md.push_str(&"---|".repeat(pivot.programs.len()));
+    md.push('\n');
+
+    for scenario in &pivot.scenarios {
+        let Some(base) = scenario.strip_suffix(" (Best)") else {
+            continue;
+        };
+        let worst = format!("{base} (Worst)");
+        md.push_str(&format!("| {base} |"));
+
+        for program in &pivot.programs {
+            let cell = match (pivot.get(program, scenario), pivot.get(program, &worst)) {
+                (Some(best), Some(worst)) => match pivot.get(program, base) {
+                    Some(standard) => format!("{best} – {standard} – {worst}"),
+                    None => format!("{best} – {worst}"),
+                },
+                _ => "-".to_string(),
+            };
+            md.push_str(&format!(" {cell} |"));
+        }
+        md.push('\n');
+    }
+
+    md
+}
+
+/// Renders the heap high-water mark and the allocation count of each
+/// benchmark next to its compute units. `heap` and `allocations` hold byte
+/// and allocation counts in place of compute units.
+pub fn heap_markdown(
+    results: &[BenchResult],
+    heap: &[BenchResult],
+    allocations: &[BenchResult],
+) -> String {
+    let mut md =
+        String::from("| Benchmark | CUs | Heap (bytes) | Allocations |\n|---|---|---|---|\n");
+    let find = |set: &[BenchResult], usage: &BenchResult| {
+        set.iter()
+            .find(|result| result.program == usage.program && result.scenario == usage.scenario)
+            .map(|result| result.cus.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    for usage in heap {
+        md.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            usage.id(),
+            find(results, usage),
+            usage.cus,
+            find(allocations, usage)
+        ));
+    }
+
+    md
+}
+
+/// Default number of regressions and improvements listed by the summary.
+pub const DEFAULT_SUMMARY_TOP: usize = 5;
+
+/// Change of a benchmark present in both result sets of a summary.
+struct Change {
+    id: String,
+    base: u64,
+    head: u64,
+}
+
+impl Change {
+    fn delta(&self) -> i64 {
+        self.head as i64 - self.base as i64
+    }
+
+    fn row(&self) -> String {
+        let percent = if self.base > 0 {
+            format!(" ({:+.1}%)", self.delta() as f64 * 100.0 / self.base as f64)
+        } else {
+            String::new()
+        };
+        format!(
+            "| {} | {} | {} | {:+}{percent} |\n",
+            self.id,
+            self.base,
+            self.head,
+            self.delta()
+        )
+    }
+}
+
+/// Renders a compact GitHub-flavored summary of the changes from `base` to
+/// `head` results, e.g. for a pull request comment: totals, and the `top`
+/// largest regressions and improvements.
+pub fn summary_markdown(base: &[BenchResult], head: &[BenchResult], top: usize) -> String {
+    let mut changes: Vec<Change> = head
+        .iter()
+        .filter_map(|result| {
+            let id = result.id();
+            let base = base.iter().find(|base| base.id() == id)?;
+            Some(Change {
+                id,
+                base: base.cus,
+                head: result.cus,
+            })
+        })
+        .collect();
+    let added = head.len() - changes.len();
+    let removed = base
+        .iter()
+        .filter(|base| !head.iter().any(|result| result.id() == base.id()))
+        .count();
+
+    let base_total: u64 = changes.iter().map(|change| change.base).sum();
+    let head_total: u64 = changes.iter().map(|change| change.head).sum();
+    let regressions = changes.iter().filter(|change| change.delta() > 0).count();
+    let improvements = changes.iter().filter(|change| change.delta() < 0).count();
+
+    let mut md = String::from("### Compute units\n\n");
+    md.push_str("| | Base | Head | Delta |\n|---|---:|---:|---:|\n");
+    md.push_str(
+        &Change {
+            id: format!("**Total** ({} benchmarks)", changes.len()),
+            base: base_total,
+            head: head_total,
+        }
+        .row(),
+    );
+    md.push_str(&format!(
+        "\n:chart_with_upwards_trend: {regressions} regressed, :chart_with_downwards_trend: \
+         {improvements} improved, {} unchanged, {added} added, {removed} removed.\n",
+        changes.len() - regressions - improvements
+    ));
+
+    // Largest absolute deltas first, then by id for a stable order.
+    changes.sort_by(|a, b| {
+        b.delta()
+            .abs()
+            .cmp(&a.delta().abs())
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    let sections = [
+        ("Top regressions", regressions, true),
+        ("Top improvements", improvements, false),
+    ];
+    for (title, count, regressed) in sections {
+        if count == 0 {
+            continue;
+        }
+        md.push_str(&format!(
+            "\n<details{}><summary>{title}</summary>\n\n",
+            if regressed { " open" } else { "" }
+        ));
+        md.push_str("| Benchmark | Base | Head | Delta |\n|---|---:|---:|---:|\n");
+        for change in changes
+            .iter()
+            .filter(|change| (change.delta() > 0) == regressed && change.delta() != 0)
+            .take(top)
+        {
+            md.push_str(&change.row());
+        }
+        md.push_str("\n</details>\n");
+    }
+
+    md
+}
+
+/// Parses the tags of each benchmark from the last column of the parameters
+/// written by the runner (`params.md`), keeping the latest occurrence of each
+/// benchmark like [`crate::results::parse_markdown`].
+pub fn parse_tags(contents: &str) -> HashMap<String, Vec<String>> {
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+
+    for line in contents.lines() {
+        let cells: Vec<&str> = line
+            .trim()
+            .trim_matches('|')
+            .split('|')
+            .map(str::trim)
+            .collect();
+        let (Some(name), Some(last)) = (cells.first(), cells.last()) else {
+            continue;
+        };
+        // Skip the header and separator rows, and anything that is not a
+        // benchmark row.
+        if cells.len() < 2 || !name.contains(": ") || tags.contains_key(*name) {
+            continue;
+        }
+        let names = match *last {
+            "-" => Vec::new(),
+            last => last.split(',').map(|tag| tag.trim().to_string()).collect(),
+        };
+        tags.insert(name.to_string(), names);
+    }
+
+    tags
+}
+
+/// Renders the compute units of the benchmarks grouped by tag, in order of
+/// first appearance. A benchmark appears under each of its tags, and those
+/// without any under `untagged`.
+pub fn tags_markdown(results: &[BenchResult], tags: &HashMap<String, Vec<String>>) -> String {
+    let untagged = vec!["untagged".to_string()];
+    let mut groups: Vec<(&str, Vec<&BenchResult>)> = Vec::new();
+
+    for result in results {
+        let names = match tags.get(&result.id()) {
+            Some(names) if !names.is_empty() => names,
+            _ => &untagged,
+        };
+        for name in names {
+            match groups.iter_mut().find(|(group, _)| group == name) {
+                Some((_, group)) => group.push(result),
+                None => groups.push((name, vec![result])),
+            }
+        }
+    }
+
+    let mut md = String::new();
+    for (name, group) in groups {
+        md.push_str(&format!(
+            "#### `{name}`\n\n| Benchmark | CUs |\n|---|---|\n"
+        ));
+        for result in group {
+            md.push_str(&format!("| {} | {} |\n", result.id(), result.cus));
+        }
+        md.push('\n');
+    }
+
+    md
+}
+
+/// Default compute unit prices of the fee report, in microlamports per CU.
+pub const DEFAULT_CU_PRICES: [u64; 3] = [1_000, 10_000, 100_000];
+
+/// Priority fee, in lamports, of requesting `cus` compute units at `price`
+/// microlamports per CU. Like the runtime, fractional lamports are rounded up.
+pub fn priority_fee(cus: u64, price: u64) -> u64 {
+    (u128::from(cus) * u128::from(price)).div_ceil(1_000_000) as u64
+}
+
+/// Renders the priority fee of each benchmark at every compute unit price, so
+/// CU differences can be read as fee impact. Assumes the transaction requests
+/// exactly the compute units consumed.
+pub fn fees_markdown(results: &[BenchResult], prices: &[u64]) -> String {
+    let mut md = String::from("| Benchmark | CUs |");
+    for price in prices {
+        md.push_str(&format!(" {price} µL/CU |"));
+    }
+    md.push_str("\n|---|---|");
+    md.push_str(&"---|".repeat(prices.len()));
+    md.push('\n');
+
+    for result in results {
+        md.push_str(&format!("| {} | {} |", result.id(), result.cus));
+        for price in prices {
+            md.push_str(&format!(" {} |", priority_fee(result.cus, *price)));
+        }
+        md.push('\n');
+    }
+
+    md
+}
+
+/// Parses a SlotHashes index sweep scenario, e.g.
+/// `PositionNaive (Idx 255) (Avg1.05)`, into its search, target index and
+/// decrement strategy.
+fn slot_hashes_sweep(scenario: &str) -> Option<(&str, usize, &str)> {
+    let (search, rest) = scenario.split_once(" (Idx ")?;
+    let (index, rest) = rest.split_once(") (")?;
+    let strategy = rest.strip_suffix(')')?;
+    Some((search, index.parse().ok()?, strategy))
+}
+
+/// CUs of both SlotHashes position searches for a target index.
+struct SweepPoint {
+    index: usize,
+    interpolated: Option<u64>,
+    naive: Option<u64>,
+}
+
+/// Compares the naive and interpolated SlotHashes position searches of
+/// `program` along the target index sweep, with one table per decrement
+/// strategy.
+pub fn slot_hashes_markdown(results: &[BenchResult], program: &str) -> String {
+    let mut sweeps: Vec<(&str, Vec<SweepPoint>)> = Vec::new();
+
+    for result in results.iter().filter(|result| result.program == program) {
+        let Some((search, index, strategy)) = slot_hashes_sweep(&result.scenario) else {
+            continue;
+        };
+        let position = match sweeps.iter().position(|(name, _)| *name == strategy) {
+            Some(position) => position,
+            None => {
+                sweeps.push((strategy, Vec::new()));
+                sweeps.len() - 1
+            }
+        };
+        let points = &mut sweeps[position].1;
+        let position = match points.iter().position(|point| point.index == index) {
+            Some(position) => position,
+            None => {
+                points.push(SweepPoint {
+                    index,
+                    interpolated: None,
+                    naive: None,
+                });
+                points.len() - 1
+            }
+        };
+        match search {
+            "PositionInterpolated" => points[position].interpolated = Some(result.cus),
+            "PositionNaive" => points[position].naive = Some(result.cus),
+            _ => {}
+        }
+    }
+
+    let cell = |cus: Option<u64>| cus.map_or_else(|| "-".to_string(), |cus| cus.to_string());
+    let mut md = String::new();
+
+    for (strategy, mut points) in sweeps {
+        points.sort_by_key(|point| point.index);
+        md.push_str(&format!("#### `{program}`: {strategy}\n\n"));
+        md.push_str("| Idx | Interpolated | Naive | Naive - Interpolated |\n|---|---|---|---|\n");
+        for point in points {
+            let delta = match (point.interpolated, point.naive) {
+                (Some(interpolated), Some(naive)) => {
+                    (naive as i64 - interpolated as i64).to_string()
+                }
+                _ => "-".to_string(),
+            };
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                point.index,
+                cell(point.interpolated),
+                cell(point.naive),
+                delta
+            ));
+        }
+        md.push('\n');
+    }
+
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(program: &str, scenario: &str, cus: u64) -> BenchResult {
+        BenchResult {
+            program: program.to_string(),
+            scenario: scenario.to_string(),
+            cus,
+        }
+    }
+
+    #[test]
+    fn test_account_scaling_fit() {
+        let results = [
+            result("eisodos_pinocchio", "Ping", 14),
+            result("eisodos_pinocchio", "Account (1)", 38),
+            result("eisodos_pinocchio", "Account (3)", 66),
+            result("eisodos_pinocchio", "Account (5)", 94),
+            result("eisodos_jiminy", "Account (1)", 36),
+        ];
+        let scaling = account_scaling(&results);
+
+        // A single sweep point is not enough for a fit.
+        assert_eq!(scaling.len(), 1);
+        assert_eq!(scaling[0].program, "eisodos_pinocchio");
+        assert_eq!(scaling[0].samples, 3);
+        assert!((scaling[0].fixed - 24.0).abs() < 1e-9);
+        assert!((scaling[0].per_account - 14.0).abs() < 1e-9);
+        assert!((scaling[0].r_squared - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_slot_hashes_markdown() {
+        let results = [
+            result("eisodos_pinocchio", "PositionNaive (Idx 511) (Avg2)", 900),
+            result(
+                "eisodos_pinocchio",
+                "PositionInterpolated (Idx 511) (Avg2)",
+                400,
+            ),
+            result(
+                "eisodos_pinocchio",
+                "PositionInterpolated (Idx 0) (Avg2)",
+                350,
+            ),
+            result("eisodos_pinocchio", "GetEntry (Avg2)", 100),
+        ];
+        let md = slot_hashes_markdown(&results, "eisodos_pinocchio");
+        let lines: Vec<&str> = md.lines().collect();
+
+        assert_eq!(lines[0], "#### `eisodos_pinocchio`: Avg2");
+        assert_eq!(lines[4], "| 0 | 350 | - | - |");
+        assert_eq!(lines[5], "| 511 | 400 | 900 | 500 |");
+    }
+
+    #[test]
+    fn test_fees_markdown() {
+        assert_eq!(priority_fee(1_400, 1_000), 2);
+        assert_eq!(priority_fee(1_000, 1_000), 1);
+
+        let results = [result("eisodos_pinocchio", "Transfer", 1_250)];
+        let md = fees_markdown(&results, &DEFAULT_CU_PRICES);
+        let lines: Vec<&str> = md.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "| Benchmark | CUs | 1000 µL/CU | 10000 µL/CU | 100000 µL/CU |"
+        );
+        assert_eq!(lines[1], "|---|---|---|---|---|");
+        assert!(lines[2].ends_with("| 1250 | 2 | 13 | 125 |"));
+    }
+
+    #[test]
+    fn test_heap_markdown() {
+        let results = [result("eisodos_solana_program", "Ping", 120)];
+        let heap = [
+            result("eisodos_solana_program", "Ping", 0),
+            result("eisodos_solana_program", "DedupAccounts (Sorted, 8)", 256),
+        ];
+        let allocations = [result("eisodos_solana_program", "Ping", 0)];
+
+        let md = heap_markdown(&results, &heap, &allocations);
+        let rows: Vec<&str> = md.lines().skip(2).collect();
+        assert_eq!(
+            rows,
+            [
+                "| eisodos_solana_program: Ping | 120 | 0 | 0 |",
+                "| eisodos_solana_program: DedupAccounts (Sorted, 8) | - | 256 | - |",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tags_markdown() {
+        let params = "\
+#### Benchmark Parameters
+
+| Name | Accounts | Data | Entries | Strategy | Index | Variants | Tags |
+|------|----------|------|---------|----------|-------|----------|------|
+| eisodos_pinocchio: Transfer | 3 | 0 | - | - | - | - | cpi |
+| eisodos_pinocchio: PseudoRandom (1) | 2 | 20488 | - | - | - | - | hash, sysvar |
+| eisodos_pinocchio: Transfer (mainnet) | 3 | 0 | - | - | - | - | - |
+";
+        let tags = parse_tags(params);
+        assert_eq!(tags.len(), 3);
+        assert_eq!(
+            tags["eisodos_pinocchio: PseudoRandom (1)"],
+            ["hash", "sysvar"]
+        );
+
+        let results = [
+            result("eisodos_pinocchio", "PseudoRandom (1)", 2_000),
+            result("eisodos_pinocchio", "Transfer", 1_250),
+            result("eisodos_pinocchio", "Transfer (mainnet)", 1_300),
+        ];
+        let md = tags_markdown(&results, &tags);
+        let sections: Vec<&str> = md.lines().filter(|line| line.starts_with("####")).collect();
+
+        assert_eq!(
+            sections,
+            [
+                "#### `hash`",
+                "#### `sysvar`",
+                "#### `cpi`",
+                "#### `untagged`"
+            ]
+        );
+        let lines: Vec<&str> = md.lines().collect();
+        let cpi = lines.iter().position(|line| *line == "#### `cpi`").unwrap();
+        assert_eq!(lines[cpi + 4], "| eisodos_pinocchio: Transfer | 1250 |");
+    }
+
+    #[test]
+    fn test_summary_markdown() {
+        let base = [
+            result("eisodos_pinocchio", "Ping", 14),
+            result("eisodos_pinocchio", "Transfer", 1_000),
+            result("eisodos_pinocchio", "Log", 100),
+            result("eisodos_pinocchio", "Removed", 10),
+        ];
+        let head = [
+            result("eisodos_pinocchio", "Ping", 14),
+            result("eisodos_pinocchio", "Transfer", 1_200),
+            result("eisodos_pinocchio", "Log", 90),
+            result("eisodos_pinocchio", "Added", 10),
+        ];
+        let md = summary_markdown(&base, &head, 1);
+        let lines: Vec<&str> = md.lines().collect();
+
+        assert_eq!(
+            lines[4],
+            "| **Total** (3 benchmarks) | 1114 | 1304 | +190 (+17.1%) |"
+        );
+        assert!(lines[6].contains("1 regressed"));
+        assert!(lines[6].ends_with("1 improved, 1 unchanged, 1 added, 1 removed."));
+        assert!(md.contains("| eisodos_pinocchio: Transfer | 1000 | 1200 | +200 (+20.0%) |"));
+        assert!(md.contains("| eisodos_pinocchio: Log | 100 | 90 | -10 (-10.0%) |"));
+    }
+
+    #[test]
+    fn test_ranges_markdown() {
+        let results = [
+            result("eisodos_pinocchio", "ParseMetadata", 900),
+            result("eisodos_pinocchio", "ParseMetadata (Best)", 700),
+            result("eisodos_pinocchio", "ParseMetadata (Worst)", 1100),
+            result("eisodos_pinocchio", "MatchProgramId Eq (Best)", 40),
+            result("eisodos_solana_program", "MatchProgramId Eq (Best)", 60),
+            result("eisodos_solana_program", "MatchProgramId Eq (Worst)", 120),
+        ];
+        let md = ranges_markdown(&results);
+        let lines: Vec<&str> = md.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "| Benchmark | `eisodos_pinocchio` | `eisodos_solana_program` |"
+        );
+        assert_eq!(lines[2], "| ParseMetadata | 700 – 900 – 1100 | - |");
+        // A fixture without its pair has no range.
+        assert_eq!(lines[3], "| MatchProgramId Eq | - | 60 – 120 |");
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_ratio_markdown() {
+        let results = [
+            result("eisodos_pinocchio", "Transfer", 1000),
+            result("eisodos_solana_program", "Transfer", 3400),
+            result("eisodos_solana_program", "Log", 146),
+        ];
+        let md = ratio_markdown(&results, "eisodos_pinocchio").unwrap();
+        let lines: Vec<&str> = md.lines().collect();
+
+        assert_eq!(lines[2], "| Transfer | **1.00×** | 3.40× |");
+        // No baseline result: absolute values are shown instead.
+        assert_eq!(lines[3], "| Log | - | **146 CUs** |");
+        assert_eq!(lines[4], "| _Best framework (scenarios)_ | 1 | 1 |");
+
+        assert!(ratio_markdown(&results, "eisodos_jiminy").is_err());
+    }
+
+    #[test]
+    fn test_compare_markdown() {
+        let results = [
+            result("eisodos_pinocchio", "Transfer", 1000),
+            result("eisodos_solana_program", "Transfer", 3400),
+            result("eisodos_jiminy", "Transfer", 1000),
+            result("eisodos_solana_program", "Log", 146),
+        ];
+        let md = compare_markdown(&results);
+        let lines: Vec<&str> = md.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "| Benchmark | `eisodos_pinocchio` | `eisodos_solana_program` | `eisodos_jiminy` |"
+        );
+        // Ties go to the first program.
+        assert_eq!(
+            lines[2],
+            "| Transfer | **1000** | 3400 (+240.0%) | 1000 (+0.0%) |"
+        );
+        assert_eq!(lines[3], "| Log | - | **146** | - |");
+        assert_eq!(lines.len(), 4);
+    }
+}
@@ -0,0 +1,164 @@
+use std::{fs, io, path::Path};
+
+/// Default location of the markdown results written by the benchmarks.
+pub const RESULTS_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../target/benches/compute_units.md"
+);
+
+/// Default location of the heap usage written by the benchmarks of programs
+/// built with the `heap-stats` feature, in the same format.
+pub const HEAP_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../target/benches/heap_usage.md"
+);
+
+/// Default location of the allocation counts written by the benchmarks of
+/// programs built with the `heap-stats` feature, in the same format.
+pub const ALLOCATIONS_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../target/benches/allocations.md"
+);
+
+/// Default location of the benchmark parameters written by the benchmarks.
+pub const PARAMS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/benches/params.md");
+
+/// Compute units consumed by a single benchmark.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchResult {
+    /// Name of the program, e.g. `eisodos_pinocchio`.
+    pub program: String,
+    /// Name of the scenario, e.g. `Account (3)`.
+    pub scenario: String,
+    /// Compute units consumed.
+    pub cus: u64,
+}
+
+impl BenchResult {
+    /// Returns the benchmark id as written by the runner.
+    pub fn id(&self) -> String {
+        format!("{}: {}", self.program, self.scenario)
+    }
+}
+
+/// Parses the markdown written by `mollusk-svm-bencher`.
+///
+/// Each run adds a new table at the top of the file, so only the first
+/// (latest) occurrence of each benchmark is returned, in file order.
+pub fn parse_markdown(contents: &str) -> Vec<BenchResult> {
+    let mut results: Vec<BenchResult> = Vec::new();
+
+    for line in contents.lines() {
+        let cells: Vec<&str> = line
+            .trim()
+            .trim_matches('|')
+            .split('|')
+            .map(str::trim)
+            .collect();
+        let [name, cus, ..] = cells.as_slice() else {
+            continue;
+        };
+        // Skip the header and separator rows, and anything that is not a
+        // result row.
+        let Ok(cus) = cus.replace(',', "").parse::<u64>() else {
+            continue;
+        };
+        let Some((program, scenario)) = name.split_once(": ") else {
+            continue;
+        };
+        if results
+            .iter()
+            .any(|result| result.program == program && result.scenario == scenario)
+        {
+            continue;
+        }
+        results.push(BenchResult {
+            program: program.to_string(),
+            scenario: scenario.to_string(),
+            cus,
+        });
+    }
+
+    results
+}
+
+/// Renders `results` as a markdown table in the format of
+/// `mollusk-svm-bencher`, without deltas.
+pub fn to_markdown(results: &[BenchResult]) -> String {
+    let mut md = String::from("| Name | CUs | Delta |\n|------|------|-------|\n");
+    for result in results {
+        md.push_str(&format!("| {} | {} | -- |\n", result.id(), result.cus));
+    }
+    md
+}
+
+/// Merges result sets, e.g. from the shards of a run. A benchmark present in
+/// several sets keeps the result of the first one.
+pub fn merge(sets: impl IntoIterator<Item = Vec<BenchResult>>) -> Vec<BenchResult> {
+    let mut merged: Vec<BenchResult> = Vec::new();
+    for result in sets.into_iter().flatten() {
+        if !merged.iter().any(|existing| existing.id() == result.id()) {
+            merged.push(result);
+        }
+    }
+    merged
+}
+
+/// Loads the latest results from the markdown file at `path`.
+pub fn load(path: impl AsRef<Path>) -> io::Result<Vec<BenchResult>> {
+    Ok(parse_markdown(&fs::read_to_string(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_markdown_keeps_latest() {
+        let contents = "\
+#### Compute Units: 2025-05-01 10:00:00 UTC
+
+| Name | CUs | Delta |
+|------|------|-------|
+| eisodos_pinocchio: Ping | 14 | -1 |
+| eisodos_pinocchio: Account (3) | 66 | -- |
+
+#### Compute Units: 2025-04-30 10:00:00 UTC
+
+| Name | CUs | Delta |
+|------|------|-------|
+| eisodos_pinocchio: Ping | 15 | -- |
+| eisodos_solana_program: Ping | 41 | -- |
+";
+        let results = parse_markdown(contents);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].id(), "eisodos_pinocchio: Ping");
+        assert_eq!(results[0].cus, 14);
+        assert_eq!(results[1].scenario, "Account (3)");
+        assert_eq!(results[2].program, "eisodos_solana_program");
+        assert_eq!(results[2].cus, 41);
+    }
+
+    #[test]
+    fn test_merge_round_trip() {
+        let shard = |id: &str, cus| {
+            let (program, scenario) = id.split_once(": ").unwrap();
+            BenchResult {
+                program: program.to_string(),
+                scenario: scenario.to_string(),
+                cus,
+            }
+        };
+        let merged = merge([
+            vec![shard("eisodos_pinocchio: Ping", 14)],
+            vec![
+                shard("eisodos_pinocchio: Log", 118),
+                shard("eisodos_pinocchio: Ping", 15),
+            ],
+        ]);
+
+        assert_eq!(parse_markdown(&to_markdown(&merged)), merged);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].cus, 14);
+    }
+}
@@ -0,0 +1,277 @@
+//! Binary sizes of the built programs.
+//!
+//! Feature builds of a program (the `feature-builds` of its manifest) are named
+//! `<program>_<build>`, so the size added by their features is shown as the
+//! delta to the program they are a build of. The size of the `core::fmt`
+//! functions is taken from the symbols of each program, when present.
+//!
+//! The expected size of each program is committed in a snapshot, so that a
+//! dependency bump growing (or shrinking) a binary past a tolerance fails the
+//! size check until the snapshot is updated on purpose.
+
+use crate::error::EisodosError;
+use object::{Object, ObjectSymbol, SymbolKind};
+use std::path::Path;
+
+/// Location of the committed snapshot of the expected binary sizes.
+pub const SNAPSHOT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/sizes.md");
+
+/// Size change tolerated by the size check, in percent of the expected size.
+pub const DEFAULT_TOLERANCE: f64 = 2.0;
+
+/// Binary size of a program.
+#[derive(Debug, PartialEq)]
+pub struct BinarySize {
+    pub program: String,
+    pub bytes: u64,
+    /// Size of the `core::fmt` functions.
+    pub fmt_bytes: u64,
+}
+
+/// Reads the size of every `.so` program in `dir`, sorted by name.
+pub fn load_dir(dir: &Path) -> Result<Vec<BinarySize>, EisodosError> {
+    let entries = std::fs::read_dir(dir).map_err(|error| EisodosError::read(dir, error))?;
+
+    let mut sizes = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|extension| extension != "so") {
+            continue;
+        }
+        let program = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let bytes = std::fs::read(&path).map_err(|error| EisodosError::read(&path, error))?;
+        sizes.push(BinarySize {
+            program,
+            bytes: bytes.len() as u64,
+            fmt_bytes: fmt_bytes(&bytes)?,
+        });
+    }
+    sizes.sort_by(|a, b| a.program.cmp(&b.program));
+
+    Ok(sizes)
+}
+
+/// Whether the demangled `function` belongs to the `core::fmt` machinery,
+/// including the formatting trait implementations of other types.
+fn is_fmt(function: &str) -> bool {
+    function.starts_with("core::fmt::") || function.contains(" as core::fmt::")
+}
+
+/// Returns the size of the `core::fmt` functions of the program ELF `bytes`.
+pub fn fmt_bytes(bytes: &[u8]) -> Result<u64, EisodosError> {
+    let elf = object::File::parse(bytes).map_err(|error| EisodosError::Elf(error.to_string()))?;
+
+    Ok(elf
+        .symbols()
+        .filter(|symbol| symbol.kind() == SymbolKind::Text && symbol.size() > 0)
+        .filter_map(|symbol| {
+            let name = rustc_demangle::demangle(symbol.name().ok()?);
+            is_fmt(&format!("{name:#}")).then_some(symbol.size())
+        })
+        .sum())
+}
+
+/// Returns the program `program` is a feature or version build of, i.e. the
+/// program with the longest name followed by `_` or `@` prefixing it.
+fn base_program<'a>(program: &str, sizes: &'a [BinarySize]) -> Option<&'a BinarySize> {
+    sizes
+        .iter()
+        .filter(|size| {
+            program
+                .strip_prefix(size.program.as_str())
+                .is_some_and(|build| build.starts_with(['_', '@']))
+        })
+        .max_by_key(|size| size.program.len())
+}
+
+/// Renders the size of each program and of its `core::fmt` functions as a
+/// markdown table, with the delta of feature and version builds to their
+/// program.
+pub fn sizes_markdown(sizes: &[BinarySize]) -> String {
+    let mut md = String::from("| Program | Size (bytes) | core::fmt (bytes) | Delta |\n");
+    md.push_str("|---|---|---|---|\n");
+
+    for size in sizes {
+        let delta = base_program(&size.program, sizes)
+            .map(|base| {
+                format!(
+                    "{:+} (`{}`)",
+                    size.bytes as i64 - base.bytes as i64,
+                    base.program
+                )
+            })
+            .unwrap_or_else(|| "-".to_string());
+        md.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            size.program, size.bytes, size.fmt_bytes, delta
+        ));
+    }
+
+    md
+}
+
+/// Program whose size is outside the tolerance of its snapshot.
+#[derive(Debug, PartialEq)]
+pub struct SizeChange {
+    pub program: String,
+    /// Size in the snapshot.
+    pub expected: u64,
+    pub bytes: u64,
+}
+
+impl SizeChange {
+    /// Change from the expected size, in percent.
+    pub fn percent(&self) -> f64 {
+        (self.bytes as f64 - self.expected as f64) * 100.0 / self.expected as f64
+    }
+}
+
+/// Parses the expected size of each program from a snapshot, in the format of
+/// [`snapshot_markdown`].
+pub fn parse_snapshot(contents: &str) -> Vec<(String, u64)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line
+                .trim()
+                .trim_matches('|')
+                .split('|')
+                .map(str::trim)
+                .collect();
+            let [program, bytes, ..] = cells.as_slice() else {
+                return None;
+            };
+            // Skip the header and separator rows.
+            let bytes = bytes.parse::<u64>().ok()?;
+            Some((program.trim_matches('`').to_string(), bytes))
+        })
+        .collect()
+}
+
+/// Renders the size of each program as a snapshot.
+pub fn snapshot_markdown(sizes: &[BinarySize]) -> String {
+    let mut md = String::from("| Program | Size (bytes) |\n");
+    md.push_str("|---|---|\n");
+    for size in sizes {
+        md.push_str(&format!("| `{}` | {} |\n", size.program, size.bytes));
+    }
+    md
+}
+
+/// Returns the programs of `sizes` whose size differs from the `snapshot` by
+/// more than `tolerance` percent, in either direction. Programs missing from
+/// the snapshot are not checked.
+pub fn check(sizes: &[BinarySize], snapshot: &[(String, u64)], tolerance: f64) -> Vec<SizeChange> {
+    sizes
+        .iter()
+        .filter_map(|size| {
+            let (_, expected) = snapshot
+                .iter()
+                .find(|(program, _)| *program == size.program)?;
+            let change = SizeChange {
+                program: size.program.clone(),
+                expected: *expected,
+                bytes: size.bytes,
+            };
+            (change.percent().abs() > tolerance).then_some(change)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(program: &str, bytes: u64, fmt_bytes: u64) -> BinarySize {
+        BinarySize {
+            program: program.to_string(),
+            bytes,
+            fmt_bytes,
+        }
+    }
+
+    #[test]
+    fn test_is_fmt() {
+        assert!(is_fmt("core::fmt::write"));
+        assert!(is_fmt(
+            "core::fmt::num::imp::<impl core::fmt::Display for u64>::fmt"
+        ));
+        assert!(is_fmt("<&T as core::fmt::Display>::fmt"));
+        assert!(!is_fmt("eisodos_pinocchio::processor::process_log"));
+    }
+
+    #[test]
+    fn test_sizes_markdown() {
+        let sizes = [
+            size("eisodos_pinocchio", 10736, 0),
+            size("eisodos_pinocchio@0.8", 10600, 0),
+            size("eisodos_pinocchio_fmt", 13648, 2504),
+            size("eisodos_solana_nostd_entrypoint", 17720, 0),
+        ];
+
+        let md = sizes_markdown(&sizes);
+        let rows: Vec<&str> = md.lines().skip(2).collect();
+        assert_eq!(
+            rows,
+            [
+                "| `eisodos_pinocchio` | 10736 | 0 | - |",
+                "| `eisodos_pinocchio@0.8` | 10600 | 0 | -136 (`eisodos_pinocchio`) |",
+                "| `eisodos_pinocchio_fmt` | 13648 | 2504 | +2912 (`eisodos_pinocchio`) |",
+                "| `eisodos_solana_nostd_entrypoint` | 17720 | 0 | - |",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let sizes = [
+            size("eisodos_pinocchio", 10736, 0),
+            size("eisodos_pinocchio@0.8", 10600, 0),
+        ];
+
+        assert_eq!(
+            parse_snapshot(&snapshot_markdown(&sizes)),
+            [
+                ("eisodos_pinocchio".to_string(), 10736),
+                ("eisodos_pinocchio@0.8".to_string(), 10600),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let sizes = [
+            size("eisodos_anchor", 104_000, 0),
+            size("eisodos_pinocchio", 10_900, 0),
+            size("eisodos_solana_program", 60_000, 0),
+            size("eisodos_steel", 40_000, 0),
+        ];
+        let snapshot = [
+            ("eisodos_anchor".to_string(), 100_000),
+            ("eisodos_pinocchio".to_string(), 10_736),
+            ("eisodos_solana_program".to_string(), 70_000),
+        ];
+
+        let changes = check(&sizes, &snapshot, DEFAULT_TOLERANCE);
+        assert_eq!(
+            changes,
+            [
+                SizeChange {
+                    program: "eisodos_anchor".to_string(),
+                    expected: 100_000,
+                    bytes: 104_000,
+                },
+                SizeChange {
+                    program: "eisodos_solana_program".to_string(),
+                    expected: 70_000,
+                    bytes: 60_000,
+                },
+            ]
+        );
+        assert_eq!(changes[0].percent(), 4.0);
+        assert!(check(&sizes, &snapshot, 15.0).is_empty());
+    }
+}
@@ -0,0 +1,313 @@
+//! Static stack usage estimation of SBF programs.
+//!
+//! Each SBF call frame has a fixed size of 4KB, and going past it fails the
+//! program at runtime. Frame sizes are taken from the `.stack_sizes` section
+//! when the program was built with `-Z emit-stack-sizes`; otherwise they are
+//! estimated from the deepest `r10` (frame pointer) relative access of each
+//! function.
+
+use crate::error::EisodosError;
+use object::{Object, ObjectSection, ObjectSymbol, SymbolKind};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Default directory of the built programs.
+pub const DEPLOY_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy");
+
+/// Size of an SBF call frame.
+pub const FRAME_LIMIT: u64 = 4096;
+
+/// Name of the program entrypoint symbol.
+const ENTRYPOINT: &str = "entrypoint";
+
+/// Frame pointer register.
+const FP: u8 = 10;
+
+/// Opcode of `lddw`, the only 16-byte instruction.
+const LDDW: u8 = 0x18;
+
+/// Opcode of `mov64 dst, src`.
+const MOV64_REG: u8 = 0xbf;
+
+/// Opcode of `add64 dst, imm`.
+const ADD64_IMM: u8 = 0x07;
+
+/// Stack frame size of a function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub function: String,
+    pub bytes: u64,
+}
+
+/// Static stack usage of a program.
+#[derive(Debug)]
+pub struct StackUsage {
+    pub program: String,
+    pub entrypoint: Option<u64>,
+    pub largest: Option<Frame>,
+    /// Number of functions whose frame exceeds [`FRAME_LIMIT`].
+    pub over_limit: usize,
+}
+
+/// Computes the stack usage of the program ELF `bytes`.
+pub fn stack_usage(program: &str, bytes: &[u8]) -> Result<StackUsage, EisodosError> {
+    let frames = frame_sizes(bytes)?;
+
+    Ok(StackUsage {
+        program: program.to_string(),
+        entrypoint: frames
+            .iter()
+            .find(|frame| frame.function == ENTRYPOINT)
+            .map(|frame| frame.bytes),
+        largest: frames.iter().max_by_key(|frame| frame.bytes).cloned(),
+        over_limit: frames
+            .iter()
+            .filter(|frame| frame.bytes > FRAME_LIMIT)
+            .count(),
+    })
+}
+
+/// Computes the stack usage of every `.so` program in `dir`, sorted by name.
+pub fn load_dir(dir: &Path) -> Result<Vec<StackUsage>, EisodosError> {
+    let entries = std::fs::read_dir(dir).map_err(|error| EisodosError::read(dir, error))?;
+
+    let mut usages = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|extension| extension != "so") {
+            continue;
+        }
+        let program = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let bytes = std::fs::read(&path).map_err(|error| EisodosError::read(&path, error))?;
+        usages.push(stack_usage(&program, &bytes)?);
+    }
+    usages.sort_by(|a, b| a.program.cmp(&b.program));
+
+    Ok(usages)
+}
+
+/// Returns the frame size of every function of the ELF `bytes`.
+fn frame_sizes(bytes: &[u8]) -> Result<Vec<Frame>, EisodosError> {
+    let elf = object::File::parse(bytes).map_err(|error| EisodosError::Elf(error.to_string()))?;
+
+    let functions: Vec<_> = elf
+        .symbols()
+        .filter(|symbol| symbol.kind() == SymbolKind::Text && symbol.size() > 0)
+        .filter_map(|symbol| {
+            let name = rustc_demangle::demangle(symbol.name().ok()?);
+            Some((format!("{name:#}"), symbol.address(), symbol.size()))
+        })
+        .collect();
+
+    if let Some(section) = elf.section_by_name(".stack_sizes") {
+        let data = section
+            .data()
+            .map_err(|error| EisodosError::Elf(error.to_string()))?;
+        let sizes = parse_stack_sizes(data);
+
+        return Ok(functions
+            .into_iter()
+            .filter_map(|(function, address, _)| {
+                Some(Frame {
+                    function,
+                    bytes: *sizes.get(&address)?,
+                })
+            })
+            .collect());
+    }
+
+    let text = elf
+        .section_by_name(".text")
+        .ok_or_else(|| EisodosError::Elf("missing .text section".to_string()))?;
+    let code = text
+        .data()
+        .map_err(|error| EisodosError::Elf(error.to_string()))?;
+
+    Ok(functions
+        .into_iter()
+        .filter_map(|(function, address, size)| {
+            let start = usize::try_from(address.checked_sub(text.address())?).ok()?;
+            let end = start.checked_add(usize::try_from(size).ok()?)?;
+            Some(Frame {
+                function,
+                bytes: estimate_frame(code.get(start..end)?),
+            })
+        })
+        .collect())
+}
+
+/// Parses `.stack_sizes` entries: a little-endian 8-byte function address
+/// followed by the ULEB128 frame size. Entries whose size does not fit in 64
+/// bits are skipped.
+fn parse_stack_sizes(mut data: &[u8]) -> HashMap<u64, u64> {
+    let mut sizes = HashMap::new();
+
+    while let Some((address, rest)) = data.split_first_chunk::<8>() {
+        let mut size = Some(0u64);
+        let mut shift = 0u32;
+        let mut read = 0;
+        for byte in rest {
+            size = size
+                .filter(|_| shift < u64::BITS)
+                .map(|size| size | u64::from(byte & 0x7f) << shift);
+            shift = shift.saturating_add(7);
+            read += 1;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        if let Some(size) = size {
+            sizes.insert(u64::from_le_bytes(*address), size);
+        }
+        data = &rest[read..];
+    }
+
+    sizes
+}
+
+/// Estimates the frame size of the SBF `code` of a function from its deepest
+/// frame pointer relative access, following registers derived from it by
+/// `mov64 rX, r10` and `add64 rX, imm`.
+fn estimate_frame(code: &[u8]) -> u64 {
+    // Offset from the frame pointer held by each register, if derived from it.
+    let mut derived: [Option<i64>; 11] = [None; 11];
+    derived[FP as usize] = Some(0);
+    let mut depth = 0i64;
+
+    let mut instructions = code.chunks_exact(8);
+    while let Some(instruction) = instructions.next() {
+        let opcode = instruction[0];
+        let dst = (instruction[1] & 0x0f) as usize;
+        let src = (instruction[1] >> 4) as usize;
+        let offset = i16::from_le_bytes([instruction[2], instruction[3]]) as i64;
+        let imm = i32::from_le_bytes([
+            instruction[4],
+            instruction[5],
+            instruction[6],
+            instruction[7],
+        ]) as i64;
+
+        let base = match opcode & 0x07 {
+            // ldx
+            0x01 => Some(src),
+            // st, stx
+            0x02 | 0x03 => Some(dst),
+            _ => None,
+        };
+        if let Some(Some(base)) = base.map(|base| derived.get(base).copied().flatten()) {
+            depth = depth.min(base + offset);
+        }
+
+        if dst < FP as usize {
+            match opcode {
+                MOV64_REG => derived[dst] = derived.get(src).copied().flatten(),
+                ADD64_IMM => {
+                    derived[dst] = derived[dst].map(|offset| offset + imm);
+                    if let Some(offset) = derived[dst] {
+                        depth = depth.min(offset);
+                    }
+                }
+                // Stores and jumps do not write to `dst`.
+                _ if matches!(opcode & 0x07, 0x02 | 0x03 | 0x05) => {}
+                _ => derived[dst] = None,
+            }
+        }
+
+        if opcode == LDDW {
+            instructions.next();
+        }
+    }
+
+    depth.unsigned_abs()
+}
+
+/// Renders the stack usage of each program as a markdown table.
+pub fn stack_usage_markdown(usages: &[StackUsage]) -> String {
+    let mut md = String::from("| Program | Entrypoint frame | Largest frame | Function |");
+    md.push_str(" Over 4KB |\n|---|---|---|---|---|\n");
+
+    for usage in usages {
+        let entrypoint = usage
+            .entrypoint
+            .map(|bytes| bytes.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let (largest, function) = usage
+            .largest
+            .as_ref()
+            .map(|frame| (frame.bytes.to_string(), format!("`{}`", frame.function)))
+            .unwrap_or_else(|| ("-".to_string(), "-".to_string()));
+        md.push_str(&format!(
+            "| `{}` | {} | {} | {} | {} |\n",
+            usage.program, entrypoint, largest, function, usage.over_limit
+        ));
+    }
+
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instruction(opcode: u8, dst: u8, src: u8, offset: i16, imm: i32) -> [u8; 8] {
+        let mut bytes = [0; 8];
+        bytes[0] = opcode;
+        bytes[1] = (src << 4) | dst;
+        bytes[2..4].copy_from_slice(&offset.to_le_bytes());
+        bytes[4..8].copy_from_slice(&imm.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_estimate_frame() {
+        let code = [
+            // stxdw [r10-16], r1
+            instruction(0x7b, FP, 1, -16, 0),
+            // mov64 r2, r10
+            instruction(MOV64_REG, 2, FP, 0, 0),
+            // add64 r2, -64
+            instruction(ADD64_IMM, 2, 0, 0, -64),
+            // stdw [r2-8], 0
+            instruction(0x7a, 2, 0, -8, 0),
+            // mov64 r2, 0 (no longer derived from r10)
+            instruction(0xb7, 2, 0, 0, 0),
+            // ldxdw r3, [r2-4096]
+            instruction(0x79, 3, 2, -4096, 0),
+            // exit
+            instruction(0x95, 0, 0, 0, 0),
+        ]
+        .concat();
+
+        assert_eq!(estimate_frame(&code), 72);
+    }
+
+    #[test]
+    fn test_parse_stack_sizes() {
+        let mut data = 0x120u64.to_le_bytes().to_vec();
+        // 4200 as ULEB128
+        data.extend([0xe8, 0x20]);
+        data.extend(0x200u64.to_le_bytes());
+        data.push(16);
+
+        let sizes = parse_stack_sizes(&data);
+        assert_eq!(sizes[&0x120], 4200);
+        assert_eq!(sizes[&0x200], 16);
+    }
+
+    #[test]
+    fn test_parse_stack_sizes_overlong() {
+        let mut data = 0x120u64.to_le_bytes().to_vec();
+        // Continuation bytes past 64 bits, then a terminating byte
+        data.extend([0x80; 10]);
+        data.push(1);
+        data.extend(0x200u64.to_le_bytes());
+        data.push(16);
+
+        let sizes = parse_stack_sizes(&data);
+        assert!(!sizes.contains_key(&0x120));
+        assert_eq!(sizes[&0x200], 16);
+    }
+}
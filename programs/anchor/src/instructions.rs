@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// Accounts of the instructions without fixed accounts, which are read from
+/// the remaining accounts.
+#[derive(Accounts)]
+pub struct Empty {}
+
+#[derive(Accounts)]
+pub struct CreateAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub new_account: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Transfer<'info> {
+    #[account(mut)]
+    pub from: Signer<'info>,
+    #[account(mut)]
+    pub to: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
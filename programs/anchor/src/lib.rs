@@ -0,0 +1,98 @@
+use anchor_lang::{prelude::*, system_program};
+
+pub mod instructions;
+
+pub use instructions::*;
+
+declare_id!("Anchor1111111111111111111111111111111111111");
+
+/// Version byte of versioned (V2) instruction data, followed by the tag and the
+/// borsh-encoded fields.
+pub const VERSION_2: u8 = 0x80 | 2;
+
+/// Instructions use the one-byte tags of the other programs as discriminators,
+/// instead of Anchor's 8-byte hashes, so that every program receives the same
+/// instruction data. Versioned instructions have their own two-byte
+/// discriminators.
+#[program]
+pub mod eisodos_anchor {
+    use super::*;
+
+    #[instruction(discriminator = [0])]
+    pub fn ping(_ctx: Context<Empty>) -> Result<()> {
+        Ok(())
+    }
+
+    #[instruction(discriminator = [1])]
+    pub fn log(_ctx: Context<Empty>) -> Result<()> {
+        #[cfg(not(feature = "fmt-log"))]
+        msg!("Instruction: Log");
+        #[cfg(feature = "fmt-log")]
+        msg!("Instruction: Log ({})", core::hint::black_box(1u64));
+        Ok(())
+    }
+
+    #[instruction(discriminator = [2])]
+    pub fn account(ctx: Context<Empty>, expected: u64) -> Result<()> {
+        if ctx.remaining_accounts.len() == expected as usize {
+            Ok(())
+        } else {
+            Err(ProgramError::InvalidArgument.into())
+        }
+    }
+
+    #[instruction(discriminator = [3])]
+    pub fn create_account(ctx: Context<CreateAccount>) -> Result<()> {
+        system_program::create_account(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.new_account.to_account_info(),
+                },
+            ),
+            500_000_000,
+            10,
+            &crate::ID,
+        )
+    }
+
+    #[instruction(discriminator = [4])]
+    pub fn transfer(ctx: Context<Transfer>) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.from.to_account_info(),
+                    to: ctx.accounts.to.to_account_info(),
+                },
+            ),
+            1_000_000_000,
+        )
+    }
+
+    #[instruction(discriminator = [VERSION_2, 0])]
+    pub fn ping_v2(ctx: Context<Empty>) -> Result<()> {
+        ping(ctx)
+    }
+
+    #[instruction(discriminator = [VERSION_2, 2])]
+    pub fn account_v2(ctx: Context<Empty>, expected: u64) -> Result<()> {
+        account(ctx, expected)
+    }
+
+    /// Handles the instruction data matching no discriminator: empty data is
+    /// the `EmptyCall` instruction, anything else is rejected like by the
+    /// other programs.
+    pub fn fallback<'info>(
+        _program_id: &Pubkey,
+        _accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        if data.is_empty() {
+            Ok(())
+        } else {
+            Err(ProgramError::InvalidInstructionData.into())
+        }
+    }
+}
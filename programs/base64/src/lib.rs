@@ -0,0 +1,58 @@
+//! Standard (padded) base64 encoding, as run by the `EmitEventBase64`
+//! benchmark to encode its event inside the program before emitting it.
+//!
+//! The encoding does not depend on the framework, so every program runs the
+//! same code and the benchmarks only differ by their entrypoint.
+
+#![no_std]
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard (padded) base64 encoding of `input` into `output`, returning the
+/// number of bytes written.
+///
+/// `output` must hold at least `input.len().div_ceil(3) * 4` bytes.
+#[inline(always)]
+pub fn encode(input: &[u8], output: &mut [u8]) -> usize {
+    let mut written = 0;
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        output[written] = ALPHABET[(b[0] >> 2) as usize];
+        output[written + 1] = ALPHABET[(((b[0] & 0x03) << 4) | (b[1] >> 4)) as usize];
+        output[written + 2] = if chunk.len() > 1 {
+            ALPHABET[(((b[1] & 0x0f) << 2) | (b[2] >> 6)) as usize]
+        } else {
+            b'='
+        };
+        output[written + 3] = if chunk.len() > 2 {
+            ALPHABET[(b[2] & 0x3f) as usize]
+        } else {
+            b'='
+        };
+        written += 4;
+    }
+    written
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_padding() {
+        let mut output = [0u8; 8];
+        for (input, expected) in [
+            (&b"Man"[..], &b"TWFu"[..]),
+            (b"Ma", b"TWE="),
+            (b"M", b"TQ=="),
+            (b"Many", b"TWFueQ=="),
+        ] {
+            let len = encode(input, &mut output);
+            assert_eq!(&output[..len], expected);
+        }
+    }
+}
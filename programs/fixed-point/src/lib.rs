@@ -0,0 +1,58 @@
+//! Fixed-point interest accrual, as computed by the `AccrueInterestQ64` and
+//! `AccrueInterestFixed` benchmarks.
+//!
+//! Both compound `PRINCIPAL` by `GROWTH` once per period, the former with
+//! hand-rolled Q64.64 arithmetic on a `u128` (integer part in the high 64
+//! bits, fraction in the low 64 bits) and the latter with the `fixed` crate's
+//! `U64F64`, which has the same layout.
+//!
+//! The arithmetic does not depend on the framework, so every program runs the
+//! same code and the benchmarks only differ by their entrypoint.
+
+#![no_std]
+
+use core::hint::black_box;
+use fixed::types::U64F64;
+
+/// One, in Q64.64.
+pub const ONE: u128 = 1 << 64;
+
+/// Growth factor of a period (`1.0001`), in Q64.64.
+pub const GROWTH: u128 = ONE + ONE / 10_000;
+
+/// Initial balance of the accrual.
+pub const PRINCIPAL: u64 = 1_000_000_000;
+
+/// Multiplies two Q64.64 numbers, rounding down. The 256-bit product is never
+/// formed: the 64-bit halves are multiplied separately and only the bits
+/// above the lowest 64 are kept.
+#[inline(always)]
+pub fn mul_q64(a: u128, b: u128) -> u128 {
+    let (a_hi, a_lo) = (a >> 64, a as u64 as u128);
+    let (b_hi, b_lo) = (b >> 64, b as u64 as u128);
+    ((a_hi * b_hi) << 64) + a_hi * b_lo + a_lo * b_hi + ((a_lo * b_lo) >> 64)
+}
+
+/// Accrues `periods` periods of interest with hand-rolled Q64.64 arithmetic,
+/// returning the balance rounded down.
+#[inline(always)]
+pub fn accrue_q64(periods: u8) -> u64 {
+    let growth = black_box(GROWTH);
+    let mut balance = (PRINCIPAL as u128) << 64;
+    for _ in 0..periods {
+        balance = mul_q64(balance, growth);
+    }
+    (balance >> 64) as u64
+}
+
+/// Accrues `periods` periods of interest with the `fixed` crate, returning
+/// the balance rounded down.
+#[inline(always)]
+pub fn accrue_fixed(periods: u8) -> u64 {
+    let growth = black_box(U64F64::from_bits(GROWTH));
+    let mut balance = U64F64::from_num(PRINCIPAL);
+    for _ in 0..periods {
+        balance *= growth;
+    }
+    balance.to_num()
+}
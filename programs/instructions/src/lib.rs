@@ -0,0 +1,576 @@
+//! Instructions of the benchmarked programs.
+//!
+//! Every program built against this crate unpacks the same instruction data,
+//! and the benchmarks pack it with the same code, so tags cannot drift apart.
+//! Instructions implemented by only some of the programs on purpose (e.g. the
+//! unchecked SlotHashes lookups of `pinocchio`) are still unpacked by all of
+//! them, then rejected by the programs not implementing them with
+//! `InvalidInstructionData`, as unknown tags are.
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use shank::ShankInstruction;
+
+/// Version byte of versioned (V2) instruction data, followed by the tag and the
+/// borsh-encoded fields. Versions are `0x80 | version`, above the raw tags.
+///
+/// Instruction fields are fixed-width little-endian integers, for which the
+/// raw and borsh encodings are identical.
+pub const VERSION_2: u8 = 0x80 | 2;
+
+/// Program instructions, annotated for IDL generation with shank. Variants are
+/// in tag order, so the IDL discriminators match the raw tags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ShankInstruction)]
+#[rustfmt::skip]
+pub enum Instruction {
+    Ping,                                                     // Tag 0
+    Log,                                                      // Tag 1
+    /// Expects exactly `expected` accounts.
+    Account {
+        expected: u64,
+    },                                                        // Tag 2
+    #[account(0, writable, signer, name = "from", desc = "Funding account")]
+    #[account(1, writable, signer, name = "account", desc = "Account to create")]
+    #[account(2, name = "system_program", desc = "System program")]
+    CreateAccount,                                            // Tag 3
+    #[account(0, writable, signer, name = "from", desc = "Source account")]
+    #[account(1, writable, name = "to", desc = "Destination account")]
+    #[account(2, name = "system_program", desc = "System program")]
+    Transfer,                                                 // Tag 4
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    SlotHashesGetEntry,                                       // Tag 5
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    SlotHashesGetHashInterpolated,                            // Tag 6
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    SlotHashesPositionInterpolated,                           // Tag 7
+    /// `pinocchio` only.
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    SlotHashesGetEntryUnchecked,                              // Tag 8
+    /// `pinocchio` only.
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    SlotHashesGetHashInterpolatedUnchecked,                   // Tag 9
+    /// `pinocchio` only.
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    SlotHashesPositionInterpolatedUnchecked { target_slot: u64 }, // Tag 10
+    /// `pinocchio` only.
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    SlotHashesPositionNaiveUnchecked { target_slot: u64 },    // Tag 11
+    /// Reads the fields of every account.
+    AccountFields,                                            // Tag 12
+    /// Reads the data of every account.
+    ReadDataChecked,                                          // Tag 13
+    /// Reads the data of every account.
+    ReadDataUnchecked,                                        // Tag 14
+    /// Validates the first `count` accounts.
+    ValidateAccounts { count: u8 },                           // Tag 15
+    /// Checks that every remaining account is rent exempt.
+    #[account(0, name = "rent", desc = "Rent sysvar")]
+    CheckRentExempt,                                          // Tag 16
+    #[account(0, writable, name = "state", desc = "Program state account")]
+    ReadModifyWrite,                                          // Tag 17
+    EmitEvent,                                                // Tag 18
+    EmitEventBase64,                                          // Tag 19
+    /// `pinocchio` only.
+    Dispatch { strategy: u8, variants: u8, tag: u8 },         // Tag 20
+    /// `pinocchio` only.
+    ClockGet,                                                 // Tag 21
+    /// `pinocchio` only.
+    #[account(0, name = "clock", desc = "Clock sysvar")]
+    ClockFromAccount,                                         // Tag 22
+    /// `pinocchio` only.
+    RentGet,                                                  // Tag 23
+    /// `pinocchio` only.
+    #[account(0, name = "rent", desc = "Rent sysvar")]
+    RentFromAccount,                                          // Tag 24
+    #[account(0, name = "token_account", desc = "Token-2022 token account")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    ParseToken2022Extensions,                                 // Tag 25
+    #[account(0, name = "metadata", desc = "Token Metadata account")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    ParseMetadata,                                            // Tag 26
+    /// Checks `m` of the first `n` stored signers.
+    #[account(0, name = "multisig", desc = "Multisig account")]
+    CheckMultisig { m: u8, n: u8 },                           // Tag 27
+    /// Verifies a merkle proof of `depth` nodes.
+    #[account(0, name = "tree", desc = "Account storing the merkle root")]
+    #[account(1, name = "proof", desc = "Account holding the leaf and proof")]
+    VerifyMerkleProof { depth: u8 },                          // Tag 28
+    /// Appends a leaf to a concurrent merkle tree.
+    #[account(0, writable, name = "tree", desc = "Concurrent merkle tree account")]
+    AppendLeaf { leaf: [u8; 32] },                            // Tag 29
+    /// `solana-program` and `solana-nostd-entrypoint` only.
+    #[account(0, writable, name = "state", desc = "Program state account")]
+    WriteStateBorsh,                                          // Tag 30
+    /// `solana-program` and `solana-nostd-entrypoint` only.
+    #[account(0, writable, name = "state", desc = "Program state account")]
+    WriteStateBincode,                                        // Tag 31
+    #[account(0, writable, name = "state", desc = "Program state account")]
+    WriteStateManual,                                         // Tag 32
+    /// `solana-nostd-entrypoint` only.
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    SlotHashesGetEntryZerocopy,                               // Tag 33
+    /// `solana-nostd-entrypoint` only.
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    SlotHashesGetHashZerocopy,                                // Tag 34
+    /// `solana-nostd-entrypoint` only.
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    SlotHashesPositionZerocopy,                               // Tag 35
+    /// Performs `iterations` rounds of division and square root.
+    DivSqrt { iterations: u16 },                              // Tag 36
+    /// Accrues `periods` periods of interest with Q64.64 arithmetic.
+    AccrueInterestQ64 { periods: u8 },                        // Tag 37
+    /// Accrues `periods` periods of interest with the `fixed` crate.
+    AccrueInterestFixed { periods: u8 },                      // Tag 38
+    /// Draws `count` pseudo-random values from the most recent slot hash.
+    #[account(0, name = "slot_hashes", desc = "SlotHashes sysvar")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    PseudoRandom { count: u8 },                               // Tag 39
+    /// Checks that the first `count` accounts have distinct keys.
+    DedupAccounts { count: u8, strategy: u8 },                // Tag 40
+    /// Checks the owner of every account after the first `fixed`.
+    ProcessRemaining { fixed: u8 },                           // Tag 41
+    /// Matches the key of the first account against known program ids.
+    #[account(0, name = "account", desc = "Account whose key is matched")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    MatchProgramId { strategy: u8 },                          // Tag 42
+    #[account(0, name = "program_data", desc = "Upgradeable program data account")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    ParseProgramData,                                         // Tag 43
+    #[account(0, name = "feature", desc = "Runtime feature account")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    CheckFeature,                                             // Tag 44
+    /// Looks up `epoch` in the StakeHistory sysvar. `solana-program` only.
+    #[account(0, name = "stake_history", desc = "StakeHistory sysvar")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    StakeHistoryPosition { epoch: u64 },                      // Tag 45
+    /// Reads a `u128` and a `u64` field at `offset` in the data of the first
+    /// account, aligned or not.
+    #[account(0, name = "data", desc = "Account holding the fields")]
+    #[account(1, optional, writable, name = "result", desc = "Result account")]
+    ReadAlignment { offset: u8, strategy: u8 },               // Tag 46
+    /// Reads the total epoch stake, or the stake of the vote account if
+    /// `vote_account` is not 0.
+    #[account(0, optional, name = "vote_account", desc = "Vote account")]
+    GetEpochStake { vote_account: u8 },                       // Tag 47
+    /// Looks up `slot` through the SlotHashes sysvar syscall.
+    /// `solana-program` only.
+    #[account(0, optional, writable, name = "result", desc = "Result account")]
+    SlotHashesPositionSysvar { slot: u64, strategy: u8 },     // Tag 48
+    /// Allocates a zeroed buffer of `size` bytes on the heap. `pinocchio` only,
+    /// failing unless built with the `bump-allocator` feature.
+    Allocate { size: u16 },                                   // Tag 49
+    /// Transfers lamports from a program derived address of `num_seeds`
+    /// seeds of `seed_len` bytes, plus the `bump` seed, through CPI.
+    #[account(0, writable, name = "from", desc = "Program derived address")]
+    #[account(1, writable, name = "to", desc = "Recipient")]
+    #[account(2, name = "system_program", desc = "System program")]
+    CpiSignedSeeds { num_seeds: u8, seed_len: u8, bump: u8 }, // Tag 50
+    /// Assigns the system-owned account to the program through CPI, writes
+    /// its data, then zeroes it and assigns it back to the system program.
+    #[account(0, writable, signer, name = "account", desc = "System-owned account")]
+    #[account(1, name = "system_program", desc = "System program")]
+    OwnerRoundTrip,                                           // Tag 51
+    /// Zeroes the first `len` bytes of the data of the account.
+    #[account(0, writable, name = "account", desc = "Program-owned account")]
+    ZeroData { len: u32, strategy: u8 },                      // Tag 52
+    /// Copies the first `len` bytes of the data of the source account to the
+    /// destination account.
+    #[account(0, name = "source", desc = "Account copied from")]
+    #[account(1, writable, name = "destination", desc = "Program-owned account copied to")]
+    CopyAccountData { len: u32, strategy: u8 },               // Tag 53
+    /// Sent with empty instruction data, which no other instruction has.
+    EmptyCall,                                                // No data
+}
+
+impl Instruction {
+    /// Length of the longest raw instruction data (`AppendLeaf`).
+    pub const MAX_LEN: usize = 1 + 32;
+
+    /// Unpacks a byte buffer into a [Instruction](enum.Instruction.html), or
+    /// `None` if it is not the data of any instruction.
+    #[inline(always)]
+    pub fn unpack(input: &[u8]) -> Option<Self> {
+        match input.split_first() {
+            // 0 - Ping
+            Some((&0, [])) => Some(Instruction::Ping),
+            // 1 - Log
+            Some((&1, [])) => Some(Instruction::Log),
+            // 2 - Account
+            Some((&2, remaining)) if remaining.len() == 8 => Some(Instruction::Account {
+                expected: u64::from_le_bytes(remaining[0..8].try_into().unwrap()),
+            }),
+            // 3 - CreateAccount
+            Some((&3, [])) => Some(Instruction::CreateAccount),
+            // 4 - Transfer
+            Some((&4, [])) => Some(Instruction::Transfer),
+            // 5 - SlotHashesGetEntry
+            Some((&5, [])) => Some(Instruction::SlotHashesGetEntry),
+            // 6 - SlotHashesGetHashInterpolated
+            Some((&6, [])) => Some(Instruction::SlotHashesGetHashInterpolated),
+            // 7 - SlotHashesPositionInterpolated
+            Some((&7, [])) => Some(Instruction::SlotHashesPositionInterpolated),
+            // 8 - SlotHashesGetEntryUnchecked
+            Some((&8, [])) => Some(Instruction::SlotHashesGetEntryUnchecked),
+            // 9 - SlotHashesGetHashInterpolatedUnchecked
+            Some((&9, [])) => Some(Instruction::SlotHashesGetHashInterpolatedUnchecked),
+            // 10 - SlotHashesPositionInterpolatedUnchecked { target_slot: Slot }
+            Some((&10, remaining)) if remaining.len() == 8 => {
+                let target_slot = u64::from_le_bytes(remaining[0..8].try_into().unwrap());
+                Some(Instruction::SlotHashesPositionInterpolatedUnchecked { target_slot })
+            }
+            // 11 - SlotHashesPositionNaiveUnchecked { target_slot: Slot }
+            Some((&11, remaining)) if remaining.len() == 8 => {
+                let target_slot = u64::from_le_bytes(remaining[0..8].try_into().unwrap());
+                Some(Instruction::SlotHashesPositionNaiveUnchecked { target_slot })
+            }
+            // 12 - AccountFields
+            Some((&12, [])) => Some(Instruction::AccountFields),
+            // 13 - ReadDataChecked
+            Some((&13, [])) => Some(Instruction::ReadDataChecked),
+            // 14 - ReadDataUnchecked
+            Some((&14, [])) => Some(Instruction::ReadDataUnchecked),
+            // 15 - ValidateAccounts { count: u8 }
+            Some((&15, [count])) => Some(Instruction::ValidateAccounts { count: *count }),
+            // 16 - CheckRentExempt
+            Some((&16, [])) => Some(Instruction::CheckRentExempt),
+            // 17 - ReadModifyWrite
+            Some((&17, [])) => Some(Instruction::ReadModifyWrite),
+            // 18 - EmitEvent
+            Some((&18, [])) => Some(Instruction::EmitEvent),
+            // 19 - EmitEventBase64
+            Some((&19, [])) => Some(Instruction::EmitEventBase64),
+            // 20 - Dispatch { strategy: u8, variants: u8, tag: u8 }
+            Some((&20, [strategy, variants, tag])) => Some(Instruction::Dispatch {
+                strategy: *strategy,
+                variants: *variants,
+                tag: *tag,
+            }),
+            // 21 - ClockGet
+            Some((&21, [])) => Some(Instruction::ClockGet),
+            // 22 - ClockFromAccount
+            Some((&22, [])) => Some(Instruction::ClockFromAccount),
+            // 23 - RentGet
+            Some((&23, [])) => Some(Instruction::RentGet),
+            // 24 - RentFromAccount
+            Some((&24, [])) => Some(Instruction::RentFromAccount),
+            // 25 - ParseToken2022Extensions
+            Some((&25, [])) => Some(Instruction::ParseToken2022Extensions),
+            // 26 - ParseMetadata
+            Some((&26, [])) => Some(Instruction::ParseMetadata),
+            // 27 - CheckMultisig { m: u8, n: u8 }
+            Some((&27, [m, n])) => Some(Instruction::CheckMultisig { m: *m, n: *n }),
+            // 28 - VerifyMerkleProof { depth: u8 }
+            Some((&28, [depth])) => Some(Instruction::VerifyMerkleProof { depth: *depth }),
+            // 29 - AppendLeaf { leaf: [u8; 32] }
+            Some((&29, remaining)) if remaining.len() == 32 => Some(Instruction::AppendLeaf {
+                leaf: remaining.try_into().unwrap(),
+            }),
+            // 30 - WriteStateBorsh
+            Some((&30, [])) => Some(Instruction::WriteStateBorsh),
+            // 31 - WriteStateBincode
+            Some((&31, [])) => Some(Instruction::WriteStateBincode),
+            // 32 - WriteStateManual
+            Some((&32, [])) => Some(Instruction::WriteStateManual),
+            // 33 - SlotHashesGetEntryZerocopy
+            Some((&33, [])) => Some(Instruction::SlotHashesGetEntryZerocopy),
+            // 34 - SlotHashesGetHashZerocopy
+            Some((&34, [])) => Some(Instruction::SlotHashesGetHashZerocopy),
+            // 35 - SlotHashesPositionZerocopy
+            Some((&35, [])) => Some(Instruction::SlotHashesPositionZerocopy),
+            // 36 - DivSqrt { iterations: u16 }
+            Some((&36, remaining)) if remaining.len() == 2 => Some(Instruction::DivSqrt {
+                iterations: u16::from_le_bytes(remaining[0..2].try_into().unwrap()),
+            }),
+            // 37 - AccrueInterestQ64 { periods: u8 }
+            Some((&37, [periods])) => Some(Instruction::AccrueInterestQ64 { periods: *periods }),
+            // 38 - AccrueInterestFixed { periods: u8 }
+            Some((&38, [periods])) => Some(Instruction::AccrueInterestFixed { periods: *periods }),
+            // 39 - PseudoRandom { count: u8 }
+            Some((&39, [count])) => Some(Instruction::PseudoRandom { count: *count }),
+            // 40 - DedupAccounts { count: u8, strategy: u8 }
+            Some((&40, [count, strategy])) => Some(Instruction::DedupAccounts {
+                count: *count,
+                strategy: *strategy,
+            }),
+            // 41 - ProcessRemaining { fixed: u8 }
+            Some((&41, [fixed])) => Some(Instruction::ProcessRemaining { fixed: *fixed }),
+            // 42 - MatchProgramId { strategy: u8 }
+            Some((&42, [strategy])) => Some(Instruction::MatchProgramId {
+                strategy: *strategy,
+            }),
+            // 43 - ParseProgramData
+            Some((&43, [])) => Some(Instruction::ParseProgramData),
+            // 44 - CheckFeature
+            Some((&44, [])) => Some(Instruction::CheckFeature),
+            // 45 - StakeHistoryPosition { epoch: u64 }
+            Some((&45, remaining)) if remaining.len() == 8 => {
+                Some(Instruction::StakeHistoryPosition {
+                    epoch: u64::from_le_bytes(remaining[0..8].try_into().unwrap()),
+                })
+            }
+            // 46 - ReadAlignment { offset: u8, strategy: u8 }
+            Some((&46, [offset, strategy])) => Some(Instruction::ReadAlignment {
+                offset: *offset,
+                strategy: *strategy,
+            }),
+            // 47 - GetEpochStake { vote_account: u8 }
+            Some((&47, [vote_account])) => Some(Instruction::GetEpochStake {
+                vote_account: *vote_account,
+            }),
+            // 48 - SlotHashesPositionSysvar { slot: u64, strategy: u8 }
+            Some((&48, remaining)) if remaining.len() == 9 => {
+                Some(Instruction::SlotHashesPositionSysvar {
+                    slot: u64::from_le_bytes(remaining[0..8].try_into().unwrap()),
+                    strategy: remaining[8],
+                })
+            }
+            // 49 - Allocate { size: u16 }
+            Some((&49, remaining)) if remaining.len() == 2 => Some(Instruction::Allocate {
+                size: u16::from_le_bytes(remaining[0..2].try_into().unwrap()),
+            }),
+            // 50 - CpiSignedSeeds { num_seeds: u8, seed_len: u8, bump: u8 }
+            Some((&50, [num_seeds, seed_len, bump])) => Some(Instruction::CpiSignedSeeds {
+                num_seeds: *num_seeds,
+                seed_len: *seed_len,
+                bump: *bump,
+            }),
+            // 51 - OwnerRoundTrip
+            Some((&51, [])) => Some(Instruction::OwnerRoundTrip),
+            // 52 - ZeroData { len: u32, strategy: u8 }
+            Some((&52, remaining)) if remaining.len() == 5 => Some(Instruction::ZeroData {
+                len: u32::from_le_bytes(remaining[0..4].try_into().unwrap()),
+                strategy: remaining[4],
+            }),
+            // 53 - CopyAccountData { len: u32, strategy: u8 }
+            Some((&53, remaining)) if remaining.len() == 5 => Some(Instruction::CopyAccountData {
+                len: u32::from_le_bytes(remaining[0..4].try_into().unwrap()),
+                strategy: remaining[4],
+            }),
+            // Empty - EmptyCall
+            None => Some(Instruction::EmptyCall),
+            // V2 - Versioned (not nested)
+            Some((&VERSION_2, versioned)) if versioned.first() != Some(&VERSION_2) => {
+                Self::unpack(versioned)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the tag of the instruction, `None` for `EmptyCall`.
+    pub fn tag(&self) -> Option<u8> {
+        let tag = match self {
+            Instruction::Ping => 0,
+            Instruction::Log => 1,
+            Instruction::Account { .. } => 2,
+            Instruction::CreateAccount => 3,
+            Instruction::Transfer => 4,
+            Instruction::SlotHashesGetEntry => 5,
+            Instruction::SlotHashesGetHashInterpolated => 6,
+            Instruction::SlotHashesPositionInterpolated => 7,
+            Instruction::SlotHashesGetEntryUnchecked => 8,
+            Instruction::SlotHashesGetHashInterpolatedUnchecked => 9,
+            Instruction::SlotHashesPositionInterpolatedUnchecked { .. } => 10,
+            Instruction::SlotHashesPositionNaiveUnchecked { .. } => 11,
+            Instruction::AccountFields => 12,
+            Instruction::ReadDataChecked => 13,
+            Instruction::ReadDataUnchecked => 14,
+            Instruction::ValidateAccounts { .. } => 15,
+            Instruction::CheckRentExempt => 16,
+            Instruction::ReadModifyWrite => 17,
+            Instruction::EmitEvent => 18,
+            Instruction::EmitEventBase64 => 19,
+            Instruction::Dispatch { .. } => 20,
+            Instruction::ClockGet => 21,
+            Instruction::ClockFromAccount => 22,
+            Instruction::RentGet => 23,
+            Instruction::RentFromAccount => 24,
+            Instruction::ParseToken2022Extensions => 25,
+            Instruction::ParseMetadata => 26,
+            Instruction::CheckMultisig { .. } => 27,
+            Instruction::VerifyMerkleProof { .. } => 28,
+            Instruction::AppendLeaf { .. } => 29,
+            Instruction::WriteStateBorsh => 30,
+            Instruction::WriteStateBincode => 31,
+            Instruction::WriteStateManual => 32,
+            Instruction::SlotHashesGetEntryZerocopy => 33,
+            Instruction::SlotHashesGetHashZerocopy => 34,
+            Instruction::SlotHashesPositionZerocopy => 35,
+            Instruction::DivSqrt { .. } => 36,
+            Instruction::AccrueInterestQ64 { .. } => 37,
+            Instruction::AccrueInterestFixed { .. } => 38,
+            Instruction::PseudoRandom { .. } => 39,
+            Instruction::DedupAccounts { .. } => 40,
+            Instruction::ProcessRemaining { .. } => 41,
+            Instruction::MatchProgramId { .. } => 42,
+            Instruction::ParseProgramData => 43,
+            Instruction::CheckFeature => 44,
+            Instruction::StakeHistoryPosition { .. } => 45,
+            Instruction::ReadAlignment { .. } => 46,
+            Instruction::GetEpochStake { .. } => 47,
+            Instruction::SlotHashesPositionSysvar { .. } => 48,
+            Instruction::Allocate { .. } => 49,
+            Instruction::CpiSignedSeeds { .. } => 50,
+            Instruction::OwnerRoundTrip => 51,
+            Instruction::ZeroData { .. } => 52,
+            Instruction::CopyAccountData { .. } => 53,
+            Instruction::EmptyCall => return None,
+        };
+        Some(tag)
+    }
+
+    /// Packs the raw instruction data into `buffer`, returning its length.
+    pub fn pack_into(&self, buffer: &mut [u8; Self::MAX_LEN]) -> usize {
+        let Some(tag) = self.tag() else {
+            return 0;
+        };
+        buffer[0] = tag;
+        let fields = &mut buffer[1..];
+
+        let len = match *self {
+            Instruction::Account { expected } => write(fields, &expected.to_le_bytes()),
+            Instruction::SlotHashesPositionInterpolatedUnchecked { target_slot }
+            | Instruction::SlotHashesPositionNaiveUnchecked { target_slot } => {
+                write(fields, &target_slot.to_le_bytes())
+            }
+            Instruction::ValidateAccounts { count }
+            | Instruction::PseudoRandom { count }
+            | Instruction::AccrueInterestQ64 { periods: count }
+            | Instruction::AccrueInterestFixed { periods: count }
+            | Instruction::VerifyMerkleProof { depth: count }
+            | Instruction::ProcessRemaining { fixed: count }
+            | Instruction::MatchProgramId { strategy: count }
+            | Instruction::GetEpochStake {
+                vote_account: count,
+            } => write(fields, &[count]),
+            Instruction::Dispatch {
+                strategy,
+                variants,
+                tag,
+            } => write(fields, &[strategy, variants, tag]),
+            Instruction::CheckMultisig { m, n } => write(fields, &[m, n]),
+            Instruction::AppendLeaf { leaf } => write(fields, &leaf),
+            Instruction::DivSqrt { iterations } => write(fields, &iterations.to_le_bytes()),
+            Instruction::DedupAccounts { count, strategy } => write(fields, &[count, strategy]),
+            Instruction::StakeHistoryPosition { epoch } => write(fields, &epoch.to_le_bytes()),
+            Instruction::ReadAlignment { offset, strategy } => write(fields, &[offset, strategy]),
+            Instruction::SlotHashesPositionSysvar { slot, strategy } => {
+                write(fields, &slot.to_le_bytes()) + write(&mut fields[8..], &[strategy])
+            }
+            Instruction::Allocate { size } => write(fields, &size.to_le_bytes()),
+            Instruction::CpiSignedSeeds {
+                num_seeds,
+                seed_len,
+                bump,
+            } => write(fields, &[num_seeds, seed_len, bump]),
+            Instruction::ZeroData { len, strategy }
+            | Instruction::CopyAccountData { len, strategy } => {
+                write(fields, &len.to_le_bytes()) + write(&mut fields[4..], &[strategy])
+            }
+            _ => 0,
+        };
+
+        1 + len
+    }
+
+    /// Returns the raw instruction data.
+    #[cfg(feature = "alloc")]
+    pub fn pack(&self) -> alloc::vec::Vec<u8> {
+        let mut buffer = [0; Self::MAX_LEN];
+        let len = self.pack_into(&mut buffer);
+        buffer[..len].to_vec()
+    }
+
+    /// Returns the versioned (V2) instruction data: the version byte followed
+    /// by the raw instruction data.
+    #[cfg(feature = "alloc")]
+    pub fn pack_v2(&self) -> alloc::vec::Vec<u8> {
+        [&[VERSION_2], self.pack().as_slice()].concat()
+    }
+}
+
+/// Writes `bytes` at the start of `buffer`, returning their length.
+fn write(buffer: &mut [u8], bytes: &[u8]) -> usize {
+    buffer[..bytes.len()].copy_from_slice(bytes);
+    bytes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_unpack() {
+        let instructions = [
+            Instruction::Ping,
+            Instruction::Account { expected: 3 },
+            Instruction::SlotHashesPositionNaiveUnchecked {
+                target_slot: 1 << 40,
+            },
+            Instruction::Dispatch {
+                strategy: 1,
+                variants: 8,
+                tag: 7,
+            },
+            Instruction::AppendLeaf { leaf: [7; 32] },
+            Instruction::SlotHashesPositionZerocopy,
+            Instruction::DivSqrt { iterations: 300 },
+            Instruction::GetEpochStake { vote_account: 1 },
+            Instruction::SlotHashesPositionSysvar {
+                slot: 42,
+                strategy: 1,
+            },
+            Instruction::CopyAccountData {
+                len: 10_240,
+                strategy: 2,
+            },
+            Instruction::EmptyCall,
+        ];
+
+        for instruction in instructions {
+            let mut buffer = [0; Instruction::MAX_LEN];
+            let len = instruction.pack_into(&mut buffer);
+            assert_eq!(Instruction::unpack(&buffer[..len]), Some(instruction));
+            assert_eq!(
+                buffer.first().filter(|_| len > 0).copied(),
+                instruction.tag()
+            );
+        }
+    }
+
+    #[test]
+    fn test_unpack_every_tag() {
+        // Every tag below 54 is an instruction, with at most 32 bytes of
+        // fields.
+        for tag in 0..54u8 {
+            let mut data = [0; Instruction::MAX_LEN];
+            data[0] = tag;
+            let unpacked =
+                (1..=Instruction::MAX_LEN).find_map(|len| Instruction::unpack(&data[..len]));
+            assert_eq!(
+                unpacked.and_then(|instruction| instruction.tag()),
+                Some(tag)
+            );
+        }
+        assert_eq!(Instruction::unpack(&[54]), None);
+        assert_eq!(Instruction::unpack(&[0, 0]), None);
+    }
+
+    #[test]
+    fn test_unpack_versioned() {
+        assert_eq!(
+            Instruction::unpack(&[VERSION_2, 2, 1, 0, 0, 0, 0, 0, 0, 0]),
+            Some(Instruction::Account { expected: 1 })
+        );
+        assert_eq!(Instruction::unpack(&[VERSION_2, VERSION_2, 0]), None);
+    }
+}
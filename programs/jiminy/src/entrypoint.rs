@@ -1,7 +1,9 @@
 use crate::{
     instruction::Instruction,
     processor::{
-        process_account, process_create_account, process_log, process_ping, process_transfer,
+        process_account, process_create_account, process_empty_call, process_log, process_ping,
+        process_slot_hashes_get_entry, process_slot_hashes_get_hash_interpolated,
+        process_slot_hashes_position_interpolated, process_transfer,
     },
     Accounts, ProgramResult, MAX_ACCOUNTS,
 };
@@ -23,5 +25,13 @@ pub fn process_instruction(
         Instruction::Account { expected } => process_account(accounts, expected),
         Instruction::CreateAccount => process_create_account(accounts),
         Instruction::Transfer => process_transfer(accounts),
+        Instruction::SlotHashesGetEntry => process_slot_hashes_get_entry(accounts),
+        Instruction::SlotHashesGetHashInterpolated => {
+            process_slot_hashes_get_hash_interpolated(accounts)
+        }
+        Instruction::SlotHashesPositionInterpolated => {
+            process_slot_hashes_position_interpolated(accounts)
+        }
+        Instruction::EmptyCall => process_empty_call(),
     }
 }
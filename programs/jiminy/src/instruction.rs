@@ -1,5 +1,12 @@
 use jiminy_entrypoint::program_error::{BuiltInProgramError, ProgramError};
 
+/// Version byte of versioned (V2) instruction data, followed by the tag and the
+/// borsh-encoded fields. Versions are `0x80 | version`, above the raw tags.
+///
+/// Instruction fields are fixed-width little-endian integers, for which the
+/// raw and borsh encodings are identical.
+pub const VERSION_2: u8 = 0x80 | 2;
+
 #[derive(Clone, Debug)]
 #[rustfmt::skip]
 pub enum Instruction {
@@ -10,6 +17,10 @@ pub enum Instruction {
     },
     CreateAccount,
     Transfer,
+    SlotHashesGetEntry,
+    SlotHashesGetHashInterpolated,
+    SlotHashesPositionInterpolated,
+    EmptyCall,
 }
 
 impl Instruction {
@@ -29,6 +40,18 @@ impl Instruction {
             Some((&3, [])) => Ok(Instruction::CreateAccount),
             // 4 - Transfer
             Some((&4, [])) => Ok(Instruction::Transfer),
+            // 5 - SlotHashesGetEntry
+            Some((&5, [])) => Ok(Instruction::SlotHashesGetEntry),
+            // 6 - SlotHashesGetHashInterpolated
+            Some((&6, [])) => Ok(Instruction::SlotHashesGetHashInterpolated),
+            // 7 - SlotHashesPositionInterpolated
+            Some((&7, [])) => Ok(Instruction::SlotHashesPositionInterpolated),
+            // Empty - EmptyCall
+            None => Ok(Instruction::EmptyCall),
+            // V2 - Versioned (not nested)
+            Some((&VERSION_2, versioned)) if versioned.first() != Some(&VERSION_2) => {
+                Self::unpack(versioned)
+            }
             _ => Err(ProgramError::from_builtin(
                 BuiltInProgramError::InvalidInstructionData,
             )),
@@ -2,8 +2,11 @@ use jiminy_entrypoint::program_error::ProgramError;
 
 pub mod entrypoint;
 pub mod instruction;
+pub mod output;
 pub mod processor;
 
+pub use eisodos_sorted_sysvar as sorted_sysvar;
+
 type ProgramResult = Result<(), ProgramError>;
 
 const MAX_ACCOUNTS: usize = 128;
@@ -17,3 +20,7 @@ type Accounts<'a> = jiminy_entrypoint::account::Accounts<'a, MAX_ACCOUNTS>;
 const PROG_ID_STR: &str = "Jim1ny1111111111111111111111111111111111111";
 
 pub const ID: [u8; 32] = const_crypto::bs58::decode_pubkey(PROG_ID_STR);
+
+const SLOT_HASHES_ID_STR: &str = "SysvarS1otHashes111111111111111111111111111";
+
+pub const SLOT_HASHES_ID: [u8; 32] = const_crypto::bs58::decode_pubkey(SLOT_HASHES_ID_STR);
@@ -0,0 +1,23 @@
+//! Writes to the result account, following the convention of
+//! [`eisodos_output`], which encodes the written values.
+
+use crate::{Accounts, ProgramResult};
+pub use eisodos_output::{encode_hash, encode_position};
+use jiminy_entrypoint::program_error::{BuiltInProgramError, ProgramError};
+
+/// Writes `output` to the result account at `index` in `accounts`, if
+/// present.
+#[inline(always)]
+pub fn write_output(accounts: &mut Accounts, index: usize, output: &[u8]) -> ProgramResult {
+    if let Some(&handle) = accounts.as_slice().get(index) {
+        accounts
+            .get_mut(handle)
+            .data_mut()
+            .get_mut(..output.len())
+            .ok_or(ProgramError::from_builtin(
+                BuiltInProgramError::AccountDataTooSmall,
+            ))?
+            .copy_from_slice(output);
+    }
+    Ok(())
+}
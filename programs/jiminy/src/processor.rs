@@ -1,4 +1,8 @@
-use crate::{Accounts, Cpi, ProgramResult};
+use crate::{
+    output::{encode_hash, encode_position, write_output},
+    sorted_sysvar::SlotHashes,
+    Accounts, Cpi, ProgramResult, SLOT_HASHES_ID,
+};
 use jiminy_entrypoint::program_error::{BuiltInProgramError, ProgramError};
 use jiminy_log::sol_log;
 use jiminy_system_prog_interface::{
@@ -11,10 +15,53 @@ pub fn process_ping() -> ProgramResult {
     Ok(())
 }
 
+#[inline(always)]
+pub fn process_empty_call() -> ProgramResult {
+    Ok(())
+}
+
+/// Stack buffer the message of the `Log` instruction is formatted into with
+/// the `fmt-log` feature, as the program does not allocate.
+#[cfg(feature = "fmt-log")]
+struct LogBuffer {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+#[cfg(feature = "fmt-log")]
+impl core::fmt::Write for LogBuffer {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
 #[inline(always)]
 pub fn process_log() -> ProgramResult {
-    const MSG: &str = "Instruction: Log";
-    sol_log(MSG);
+    #[cfg(not(feature = "fmt-log"))]
+    {
+        const MSG: &str = "Instruction: Log";
+        sol_log(MSG);
+    }
+    #[cfg(feature = "fmt-log")]
+    {
+        use core::fmt::Write;
+
+        let mut buffer = LogBuffer {
+            bytes: [0; 64],
+            len: 0,
+        };
+        write!(buffer, "Instruction: Log ({})", core::hint::black_box(1u64))
+            .map_err(|_| ProgramError::from_builtin(BuiltInProgramError::InvalidArgument))?;
+        // SAFETY: only `&str`s are written to the buffer.
+        let message = unsafe { core::str::from_utf8_unchecked(&buffer.bytes[..buffer.len]) };
+        sol_log(message);
+    }
     Ok(())
 }
 
@@ -70,3 +117,51 @@ pub fn process_transfer(accounts: &mut Accounts) -> ProgramResult {
         &[],
     )
 }
+
+/// Returns the data of the SlotHashes sysvar account, the first account.
+#[inline(always)]
+fn slot_hashes_data<'a>(accounts: &'a Accounts) -> Result<&'a [u8], ProgramError> {
+    let [slot_hashes, ..] = accounts.as_slice() else {
+        return Err(ProgramError::from_builtin(
+            BuiltInProgramError::NotEnoughAccountKeys,
+        ));
+    };
+    let slot_hashes = accounts.get(*slot_hashes);
+    if slot_hashes.key() != &SLOT_HASHES_ID {
+        return Err(ProgramError::from_builtin(
+            BuiltInProgramError::IncorrectProgramId,
+        ));
+    }
+    Ok(slot_hashes.data())
+}
+
+#[inline(always)]
+pub fn process_slot_hashes_get_entry(accounts: &Accounts) -> ProgramResult {
+    let num_entries = SlotHashes::new(slot_hashes_data(accounts)?)
+        .num_entries()
+        .ok_or(ProgramError::from_builtin(
+            BuiltInProgramError::AccountDataTooSmall,
+        ))?;
+    let _is_empty = num_entries == 0;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn process_slot_hashes_get_hash_interpolated(accounts: &mut Accounts) -> ProgramResult {
+    let slot_hashes = SlotHashes::new(slot_hashes_data(accounts)?);
+    let output = encode_hash(
+        slot_hashes
+            .binary_search(0)
+            .ok()
+            .and_then(|position| slot_hashes.value(position)?.try_into().ok()),
+    );
+    write_output(accounts, 1, &output)
+}
+
+#[inline(always)]
+pub fn process_slot_hashes_position_interpolated(accounts: &mut Accounts) -> ProgramResult {
+    let position = SlotHashes::new(slot_hashes_data(accounts)?)
+        .binary_search(0)
+        .ok();
+    write_output(accounts, 1, &encode_position(position))
+}
@@ -0,0 +1,204 @@
+//! Token Metadata account layout, as read by the `ParseMetadata` benchmark.
+//!
+//! Metadata accounts are borsh-encoded, with variable-length strings and
+//! creators. Since the programs do not allocate, the account is parsed in
+//! place: strings, creators and the collection are borrowed from the data.
+//!
+//! The parser does not depend on the framework, so every program runs the
+//! same code and the benchmarks only differ by their entrypoint.
+
+#![no_std]
+
+/// Account key of `MetadataV1` accounts.
+pub const KEY_METADATA_V1: u8 = 4;
+
+/// Creator of a metadata account, as encoded.
+#[repr(C)]
+pub struct Creator {
+    pub address: [u8; 32],
+    pub verified: u8,
+    pub share: u8,
+}
+
+/// Collection of a metadata account, as encoded.
+#[repr(C)]
+pub struct Collection {
+    pub verified: u8,
+    pub key: [u8; 32],
+}
+
+/// Uses of a metadata account: the use method and the remaining and total
+/// number of uses.
+pub struct Uses {
+    pub use_method: u8,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+/// `MetadataV1` fields up to `uses`; later fields are not read.
+pub struct Metadata<'a> {
+    pub update_authority: &'a [u8; 32],
+    pub mint: &'a [u8; 32],
+    pub name: &'a str,
+    pub symbol: &'a str,
+    pub uri: &'a str,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<&'a [Creator]>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<&'a Collection>,
+    pub uses: Option<Uses>,
+}
+
+impl<'a> Metadata<'a> {
+    /// Parses the metadata from the start of `data`.
+    ///
+    /// Returns `None` if the data is not a `MetadataV1` account or is not
+    /// validly encoded.
+    #[inline(always)]
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut reader = Reader { data };
+
+        if reader.u8()? != KEY_METADATA_V1 {
+            return None;
+        }
+
+        Some(Self {
+            update_authority: reader.array()?,
+            mint: reader.array()?,
+            name: reader.str()?,
+            symbol: reader.str()?,
+            uri: reader.str()?,
+            seller_fee_basis_points: u16::from_le_bytes(*reader.array()?),
+            creators: match reader.bool()? {
+                true => {
+                    let len = u32::from_le_bytes(*reader.array()?) as usize;
+                    let bytes = reader.take(len * core::mem::size_of::<Creator>())?;
+                    // SAFETY: `bytes` holds `len` creators and `Creator` has
+                    // alignment 1.
+                    Some(unsafe {
+                        core::slice::from_raw_parts(bytes.as_ptr() as *const Creator, len)
+                    })
+                }
+                false => None,
+            },
+            primary_sale_happened: reader.bool()?,
+            is_mutable: reader.bool()?,
+            edition_nonce: match reader.bool()? {
+                true => Some(reader.u8()?),
+                false => None,
+            },
+            token_standard: match reader.bool()? {
+                true => Some(reader.u8()?),
+                false => None,
+            },
+            collection: match reader.bool()? {
+                true => {
+                    let bytes = reader.take(core::mem::size_of::<Collection>())?;
+                    // SAFETY: `bytes` holds a collection and `Collection` has
+                    // alignment 1.
+                    Some(unsafe { &*(bytes.as_ptr() as *const Collection) })
+                }
+                false => None,
+            },
+            uses: match reader.bool()? {
+                true => Some(Uses {
+                    use_method: reader.u8()?,
+                    remaining: u64::from_le_bytes(*reader.array()?),
+                    total: u64::from_le_bytes(*reader.array()?),
+                }),
+                false => None,
+            },
+        })
+    }
+}
+
+/// Reads borsh-encoded values borrowed from the data.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    #[inline(always)]
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.data.len() < len {
+            return None;
+        }
+        let (value, remaining) = self.data.split_at(len);
+        self.data = remaining;
+        Some(value)
+    }
+
+    #[inline(always)]
+    fn array<const N: usize>(&mut self) -> Option<&'a [u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    #[inline(always)]
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    #[inline(always)]
+    fn bool(&mut self) -> Option<bool> {
+        match self.u8()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    fn str(&mut self) -> Option<&'a str> {
+        let len = u32::from_le_bytes(*self.array()?) as usize;
+        core::str::from_utf8(self.take(len)?).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_metadata() {
+        let mut data = [0; 256];
+        let mut len = 0;
+        let mut push = |bytes: &[u8]| {
+            data[len..len + bytes.len()].copy_from_slice(bytes);
+            len += bytes.len();
+        };
+        push(&[KEY_METADATA_V1]);
+        push(&[1; 32]);
+        push(&[2; 32]);
+        for value in ["name", "SYM", "uri"] {
+            push(&(value.len() as u32).to_le_bytes());
+            push(value.as_bytes());
+        }
+        push(&500u16.to_le_bytes());
+        push(&[1]);
+        push(&1u32.to_le_bytes());
+        push(&[3; 32]);
+        push(&[1, 100]);
+        push(&[0, 1, 1, 255, 0]);
+        push(&[1, 1]);
+        push(&[4; 32]);
+        push(&[0]);
+
+        let metadata = Metadata::parse(&data[..len]).unwrap();
+        assert_eq!(metadata.symbol, "SYM");
+        assert_eq!(
+            metadata.creators.map(|creators| creators[0].share),
+            Some(100)
+        );
+        assert_eq!(metadata.edition_nonce, Some(255));
+        assert_eq!(
+            metadata.collection.map(|collection| collection.key),
+            Some([4; 32])
+        );
+        assert!(metadata.uses.is_none());
+
+        assert!(Metadata::parse(&data[..len - 1]).is_none());
+    }
+}
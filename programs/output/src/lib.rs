@@ -0,0 +1,44 @@
+//! Result account convention.
+//!
+//! Instructions computing a value (e.g. a found index or hash) write it to an
+//! optional writable "result" account, passed right after the instruction's
+//! own accounts, so that benchmarks can be verified without logging. Values
+//! are written at the start of the account data as a found flag (`1` or `0`)
+//! followed by the value, if found.
+//!
+//! The encodings are shared by every program, which only writes them to the
+//! result account with its own account type.
+
+#![no_std]
+
+/// Maximum length of a written value, including the found flag.
+pub const OUTPUT_LEN: usize = 1 + 32;
+
+/// Encodes an optional index as the found flag followed by a `u64`.
+#[inline(always)]
+pub fn encode_position(position: Option<usize>) -> [u8; 9] {
+    encode_value(position.map(|position| position as u64))
+}
+
+/// Encodes an optional value (e.g. an amount) as the found flag followed by
+/// the `u64`.
+#[inline(always)]
+pub fn encode_value(value: Option<u64>) -> [u8; 9] {
+    let mut output = [0; 9];
+    if let Some(value) = value {
+        output[0] = 1;
+        output[1..].copy_from_slice(&value.to_le_bytes());
+    }
+    output
+}
+
+/// Encodes an optional hash as the found flag followed by the hash.
+#[inline(always)]
+pub fn encode_hash(hash: Option<&[u8; 32]>) -> [u8; OUTPUT_LEN] {
+    let mut output = [0; OUTPUT_LEN];
+    if let Some(hash) = hash {
+        output[0] = 1;
+        output[1..].copy_from_slice(hash);
+    }
+    output
+}
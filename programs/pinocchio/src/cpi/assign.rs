@@ -0,0 +1,44 @@
+use super::SYSTEM_PROGRAM_ID;
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke_signed_unchecked,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+/// Assign an account to a program.
+///
+/// This function is a wrapper around the system program's `assign`
+/// instruction.
+///
+/// # Safety
+///
+/// This function assumes that the account is not mutably borrowed.
+pub unsafe fn assign_unchecked(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    // instruction accounts
+    let account_metas = [AccountMeta::writable_signer(account.key())];
+
+    // instruction data
+    // - [0..4 ]: instruction discriminator
+    // - [4..36]: owner pubkey
+    let mut instruction_data = [0; 36];
+    instruction_data[0] = 1;
+    instruction_data[4..36].copy_from_slice(owner);
+
+    // SAFETY: The caller must guarantee that the account is not mutably
+    // borrowed.
+    unsafe {
+        invoke_signed_unchecked(
+            &Instruction {
+                program_id: &SYSTEM_PROGRAM_ID,
+                accounts: &account_metas,
+                data: &instruction_data,
+            },
+            &[account.into()],
+            &[],
+        );
+    }
+
+    Ok(())
+}
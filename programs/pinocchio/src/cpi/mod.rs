@@ -1,9 +1,11 @@
 use pinocchio::pubkey::Pubkey;
 
+mod assign;
 mod create_account;
 mod transfer;
 
+pub use assign::assign_unchecked;
 pub use create_account::create_account_unchecked;
-pub use transfer::transfer_unchecked;
+pub use transfer::{transfer_signed_unchecked, transfer_unchecked};
 
-const SYSTEM_PROGRAM_ID: Pubkey = [0; 32];
+pub const SYSTEM_PROGRAM_ID: Pubkey = [0; 32];
@@ -2,7 +2,7 @@ use super::SYSTEM_PROGRAM_ID;
 use pinocchio::{
     account_info::AccountInfo,
     cpi::invoke_signed_unchecked,
-    instruction::{AccountMeta, Instruction},
+    instruction::{AccountMeta, Instruction, Signer},
     ProgramResult,
 };
 
@@ -18,6 +18,21 @@ pub unsafe fn transfer_unchecked(
     from: &AccountInfo,
     to: &AccountInfo,
     lamports: u64,
+) -> ProgramResult {
+    transfer_signed_unchecked(from, to, lamports, &[])
+}
+
+/// Transfer lamports from a program derived address signed for by `signers`.
+///
+/// # Safety
+///
+/// This function assumes that accounts are not mutably borrowed.
+#[inline(always)]
+pub unsafe fn transfer_signed_unchecked(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    lamports: u64,
+    signers: &[Signer],
 ) -> ProgramResult {
     // instruction accounts
     let account_metas = [
@@ -43,7 +58,7 @@ pub unsafe fn transfer_unchecked(
                 data: &instruction_data,
             },
             &[from.into(), to.into()],
-            &[],
+            signers,
         );
     }
 
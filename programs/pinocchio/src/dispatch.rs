@@ -0,0 +1,102 @@
+//! Instruction dispatch strategies.
+//!
+//! Each `variants_*` module defines a synthetic instruction set of the given
+//! size and dispatches to its handlers in two ways:
+//!
+//! - `enum_match`: unpack the tag into an enum, then match on the enum (the
+//!   strategy used by the program entrypoint);
+//! - `jump_table`: index a table of function pointers with the tag.
+//!
+//! Matching directly on the tag byte is not a separate strategy: the unpacked
+//! enum only renames the tag, so both matches compile to the same code.
+//!
+//! Handlers are never inlined so that only the dispatch cost differs.
+
+use core::hint::black_box;
+use pinocchio::{program_error::ProgramError, ProgramResult};
+
+/// Dispatch strategy selectors used in the `Dispatch` instruction data.
+pub const ENUM_MATCH: u8 = 0;
+pub const JUMP_TABLE: u8 = 1;
+
+#[inline(never)]
+fn handler<const N: u8>() -> ProgramResult {
+    black_box(N);
+    Ok(())
+}
+
+macro_rules! dispatch_table {
+    ($module:ident, $($index:literal => $variant:ident),+ $(,)?) => {
+        pub mod $module {
+            use super::*;
+
+            #[derive(Clone, Copy)]
+            enum Variant {
+                $($variant),+
+            }
+
+            #[inline(always)]
+            fn unpack(tag: u8) -> Result<Variant, ProgramError> {
+                match tag {
+                    $($index => Ok(Variant::$variant),)+
+                    _ => Err(ProgramError::InvalidInstructionData),
+                }
+            }
+
+            const HANDLERS: &[fn() -> ProgramResult] = &[$(handler::<$index>),+];
+
+            #[inline(always)]
+            pub fn enum_match(tag: u8) -> ProgramResult {
+                match unpack(tag)? {
+                    $(Variant::$variant => handler::<$index>(),)+
+                }
+            }
+
+            #[inline(always)]
+            pub fn jump_table(tag: u8) -> ProgramResult {
+                let handler = HANDLERS
+                    .get(tag as usize)
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+                handler()
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+dispatch_table!(variants_4,
+    0 => V0, 1 => V1, 2 => V2, 3 => V3
+);
+
+#[rustfmt::skip]
+dispatch_table!(variants_16,
+    0 => V0, 1 => V1, 2 => V2, 3 => V3, 4 => V4, 5 => V5, 6 => V6, 7 => V7, 8 => V8, 9 => V9,
+    10 => V10, 11 => V11, 12 => V12, 13 => V13, 14 => V14, 15 => V15
+);
+
+#[rustfmt::skip]
+dispatch_table!(variants_64,
+    0 => V0, 1 => V1, 2 => V2, 3 => V3, 4 => V4, 5 => V5, 6 => V6, 7 => V7, 8 => V8, 9 => V9,
+    10 => V10, 11 => V11, 12 => V12, 13 => V13, 14 => V14, 15 => V15, 16 => V16, 17 => V17,
+    18 => V18, 19 => V19, 20 => V20, 21 => V21, 22 => V22, 23 => V23, 24 => V24, 25 => V25,
+    26 => V26, 27 => V27, 28 => V28, 29 => V29, 30 => V30, 31 => V31, 32 => V32, 33 => V33,
+    34 => V34, 35 => V35, 36 => V36, 37 => V37, 38 => V38, 39 => V39, 40 => V40, 41 => V41,
+    42 => V42, 43 => V43, 44 => V44, 45 => V45, 46 => V46, 47 => V47, 48 => V48, 49 => V49,
+    50 => V50, 51 => V51, 52 => V52, 53 => V53, 54 => V54, 55 => V55, 56 => V56, 57 => V57,
+    58 => V58, 59 => V59, 60 => V60, 61 => V61, 62 => V62, 63 => V63
+);
+
+/// Dispatches `tag` within the instruction set of `variants` variants using
+/// `strategy`.
+#[inline(always)]
+pub fn dispatch(strategy: u8, variants: u8, tag: u8) -> ProgramResult {
+    match (strategy, variants) {
+        (ENUM_MATCH, 4) => variants_4::enum_match(tag),
+        (ENUM_MATCH, 16) => variants_16::enum_match(tag),
+        (ENUM_MATCH, 64) => variants_64::enum_match(tag),
+        (JUMP_TABLE, 4) => variants_4::jump_table(tag),
+        (JUMP_TABLE, 16) => variants_16::jump_table(tag),
+        (JUMP_TABLE, 64) => variants_64::jump_table(tag),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
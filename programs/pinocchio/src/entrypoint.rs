@@ -1,25 +1,41 @@
+#[cfg(feature = "pinocchio-fork")]
+use crate::slot_hashes::{
+    process_slot_hashes_get_entry, process_slot_hashes_get_entry_unchecked,
+    process_slot_hashes_get_hash_interpolated, process_slot_hashes_get_hash_interpolated_unchecked,
+    process_slot_hashes_position_interpolated, process_slot_hashes_position_interpolated_unchecked,
+    process_slot_hashes_position_naive_unchecked,
+};
 use {
     crate::{
+        dispatch::dispatch,
         instruction::Instruction,
         processor::{
-            process_account, process_create_account, process_log, process_ping,
-            process_slot_hashes_get_entry, process_slot_hashes_get_entry_unchecked,
-            process_slot_hashes_get_hash_interpolated,
-            process_slot_hashes_get_hash_interpolated_unchecked,
-            process_slot_hashes_position_interpolated,
-            process_slot_hashes_position_interpolated_unchecked,
-            process_slot_hashes_position_naive_unchecked,
-            process_transfer,
+            process_account, process_account_fields, process_accrue_interest_fixed,
+            process_accrue_interest_q64, process_allocate, process_append_leaf,
+            process_check_feature, process_check_multisig, process_check_rent_exempt,
+            process_clock_from_account, process_clock_get, process_copy_account_data,
+            process_cpi_signed_seeds, process_create_account, process_dedup_accounts,
+            process_div_sqrt, process_emit_event, process_emit_event_base64, process_empty_call,
+            process_get_epoch_stake, process_log, process_match_program_id,
+            process_owner_round_trip, process_parse_metadata, process_parse_program_data,
+            process_parse_token_2022_extensions, process_ping, process_pseudo_random,
+            process_read_alignment, process_read_data_checked, process_read_data_unchecked,
+            process_read_modify_write, process_remaining, process_rent_from_account,
+            process_rent_get, process_transfer, process_validate_accounts,
+            process_verify_merkle_proof, process_write_state_manual, process_zero_data,
         },
     },
     pinocchio::{
-        account_info::AccountInfo, no_allocator, nostd_panic_handler, program_entrypoint,
-        pubkey::Pubkey, ProgramResult,
+        account_info::AccountInfo, nostd_panic_handler, program_entrypoint,
+        program_error::ProgramError, pubkey::Pubkey, ProgramResult,
     },
 };
 
 program_entrypoint!(process_instruction);
-no_allocator!();
+#[cfg(not(feature = "bump-allocator"))]
+pinocchio::no_allocator!();
+#[cfg(feature = "bump-allocator")]
+pinocchio::default_allocator!();
 nostd_panic_handler!();
 
 #[inline(always)]
@@ -28,7 +44,8 @@ pub fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let instruction = Instruction::unpack(instruction_data)?;
+    let instruction =
+        Instruction::unpack(instruction_data).ok_or(ProgramError::InvalidInstructionData)?;
 
     match instruction {
         Instruction::Ping => process_ping(),
@@ -36,24 +53,102 @@ pub fn process_instruction(
         Instruction::Account { expected } => process_account(accounts, expected),
         Instruction::CreateAccount => process_create_account(accounts),
         Instruction::Transfer => process_transfer(accounts),
+        #[cfg(feature = "pinocchio-fork")]
         Instruction::SlotHashesGetEntry => process_slot_hashes_get_entry(accounts),
+        #[cfg(feature = "pinocchio-fork")]
         Instruction::SlotHashesGetHashInterpolated => {
             process_slot_hashes_get_hash_interpolated(accounts)
         }
+        #[cfg(feature = "pinocchio-fork")]
         Instruction::SlotHashesPositionInterpolated => {
             process_slot_hashes_position_interpolated(accounts)
         }
+        #[cfg(feature = "pinocchio-fork")]
         Instruction::SlotHashesGetEntryUnchecked => unsafe {
             process_slot_hashes_get_entry_unchecked(accounts)
         },
+        #[cfg(feature = "pinocchio-fork")]
         Instruction::SlotHashesGetHashInterpolatedUnchecked => unsafe {
             process_slot_hashes_get_hash_interpolated_unchecked(accounts)
         },
+        #[cfg(feature = "pinocchio-fork")]
         Instruction::SlotHashesPositionInterpolatedUnchecked { target_slot } => unsafe {
             process_slot_hashes_position_interpolated_unchecked(accounts, target_slot)
         },
+        #[cfg(feature = "pinocchio-fork")]
         Instruction::SlotHashesPositionNaiveUnchecked { target_slot } => unsafe {
             process_slot_hashes_position_naive_unchecked(accounts, target_slot)
         },
+        Instruction::AccountFields => process_account_fields(accounts),
+        Instruction::ReadDataChecked => process_read_data_checked(accounts),
+        Instruction::ReadDataUnchecked => unsafe { process_read_data_unchecked(accounts) },
+        Instruction::ValidateAccounts { count } => process_validate_accounts(accounts, count),
+        Instruction::CheckRentExempt => process_check_rent_exempt(accounts),
+        Instruction::ReadModifyWrite => process_read_modify_write(accounts),
+        Instruction::EmitEvent => process_emit_event(),
+        Instruction::EmitEventBase64 => process_emit_event_base64(),
+        Instruction::Dispatch {
+            strategy,
+            variants,
+            tag,
+        } => dispatch(strategy, variants, tag),
+        Instruction::ClockGet => process_clock_get(),
+        Instruction::ClockFromAccount => process_clock_from_account(accounts),
+        Instruction::RentGet => process_rent_get(),
+        Instruction::RentFromAccount => process_rent_from_account(accounts),
+        Instruction::ParseToken2022Extensions => process_parse_token_2022_extensions(accounts),
+        Instruction::ParseMetadata => process_parse_metadata(accounts),
+        Instruction::CheckMultisig { m, n } => process_check_multisig(accounts, m, n),
+        Instruction::VerifyMerkleProof { depth } => process_verify_merkle_proof(accounts, depth),
+        Instruction::AppendLeaf { leaf } => process_append_leaf(accounts, leaf),
+        Instruction::WriteStateManual => process_write_state_manual(accounts),
+        Instruction::DivSqrt { iterations } => process_div_sqrt(iterations),
+        Instruction::AccrueInterestQ64 { periods } => process_accrue_interest_q64(periods),
+        Instruction::AccrueInterestFixed { periods } => process_accrue_interest_fixed(periods),
+        Instruction::PseudoRandom { count } => process_pseudo_random(accounts, count),
+        Instruction::DedupAccounts { count, strategy } => {
+            process_dedup_accounts(accounts, count, strategy)
+        }
+        Instruction::ProcessRemaining { fixed } => process_remaining(accounts, fixed),
+        Instruction::MatchProgramId { strategy } => process_match_program_id(accounts, strategy),
+        Instruction::ParseProgramData => process_parse_program_data(accounts),
+        Instruction::CheckFeature => process_check_feature(accounts),
+        Instruction::ReadAlignment { offset, strategy } => {
+            process_read_alignment(accounts, offset, strategy)
+        }
+        Instruction::GetEpochStake { vote_account } => {
+            process_get_epoch_stake(accounts, vote_account)
+        }
+        Instruction::Allocate { size } => process_allocate(size),
+        Instruction::EmptyCall => process_empty_call(),
+        Instruction::CpiSignedSeeds {
+            num_seeds,
+            seed_len,
+            bump,
+        } => process_cpi_signed_seeds(accounts, num_seeds, seed_len, bump),
+        Instruction::OwnerRoundTrip => process_owner_round_trip(accounts),
+        Instruction::ZeroData { len, strategy } => process_zero_data(accounts, len, strategy),
+        Instruction::CopyAccountData { len, strategy } => {
+            process_copy_account_data(accounts, len, strategy)
+        }
+        // Only built against the SlotHashes sysvar of the pinocchio fork.
+        #[cfg(not(feature = "pinocchio-fork"))]
+        Instruction::SlotHashesGetEntry
+        | Instruction::SlotHashesGetHashInterpolated
+        | Instruction::SlotHashesPositionInterpolated
+        | Instruction::SlotHashesGetEntryUnchecked
+        | Instruction::SlotHashesGetHashInterpolatedUnchecked
+        | Instruction::SlotHashesPositionInterpolatedUnchecked { .. }
+        | Instruction::SlotHashesPositionNaiveUnchecked { .. } => {
+            Err(ProgramError::InvalidInstructionData)
+        }
+        // Implemented by other programs only.
+        Instruction::WriteStateBorsh
+        | Instruction::WriteStateBincode
+        | Instruction::SlotHashesGetEntryZerocopy
+        | Instruction::SlotHashesGetHashZerocopy
+        | Instruction::SlotHashesPositionZerocopy
+        | Instruction::StakeHistoryPosition { .. }
+        | Instruction::SlotHashesPositionSysvar { .. } => Err(ProgramError::InvalidInstructionData),
     }
 }
@@ -1,8 +1,22 @@
 #![no_std]
 
+#[cfg(feature = "bump-allocator")]
+extern crate alloc;
+
 pub mod cpi;
+pub mod dispatch;
 pub mod entrypoint;
-pub mod instruction;
+pub mod metadata;
+pub mod output;
 pub mod processor;
+#[cfg(feature = "pinocchio-fork")]
+pub mod slot_hashes;
+pub mod state;
+pub mod token_2022;
+
+pub use eisodos_base64 as base64;
+pub use eisodos_fixed_point as fixed_point;
+pub use eisodos_instructions as instruction;
+pub use eisodos_sorted_sysvar as sorted_sysvar;
 
 pinocchio_pubkey::declare_id!("Pinocchio1111111111111111111111111111111111");
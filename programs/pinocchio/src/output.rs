@@ -0,0 +1,18 @@
+//! Writes to the result account, following the convention of
+//! [`eisodos_output`], which encodes the written values.
+
+pub use eisodos_output::{encode_hash, encode_position, encode_value};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+/// Writes `output` to the result account at `index` in `accounts`, if
+/// present.
+#[inline(always)]
+pub fn write_output(accounts: &[AccountInfo], index: usize, output: &[u8]) -> ProgramResult {
+    if let Some(account) = accounts.get(index) {
+        let mut data = account.try_borrow_mut_data()?;
+        data.get_mut(..output.len())
+            .ok_or(ProgramError::AccountDataTooSmall)?
+            .copy_from_slice(output);
+    }
+    Ok(())
+}
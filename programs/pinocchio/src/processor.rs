@@ -1,21 +1,70 @@
-use crate::cpi::{create_account_unchecked, transfer_unchecked};
-use pinocchio::pubkey::log as log_pubkey;
-use pinocchio::pubkey::Pubkey;
-use pinocchio::sysvars::slot_hashes::{
-    get_entry_from_slice_unchecked, get_hash_from_slice_unchecked,
-    position_from_slice_binary_search_unchecked, SlotHashes, MAX_ENTRIES as MAX_SLOT_HASH_ENTRIES,
-    NUM_ENTRIES_SIZE, SLOT_SIZE, ENTRY_SIZE
+use crate::cpi::{
+    assign_unchecked, create_account_unchecked, transfer_signed_unchecked, transfer_unchecked,
+    SYSTEM_PROGRAM_ID,
 };
-use pinocchio::sysvars::clock::Slot;
-use pinocchio::{account_info::AccountInfo, msg, program_error::ProgramError, ProgramResult};
+use crate::base64;
+use crate::fixed_point::{accrue_fixed, accrue_q64};
+use crate::metadata::{Metadata, TOKEN_METADATA_PROGRAM_ID};
+use crate::output::{encode_hash, encode_position, encode_value, write_output};
+use crate::sorted_sysvar::{KEY_SIZE, LEN_PREFIX_SIZE};
+use crate::state::{Event, MerkleTree, State, BUFFER_SIZE, MAX_DEPTH, MAX_SIGNERS};
+use crate::token_2022::{Extensions, TOKEN_2022_PROGRAM_ID};
+use core::hint::black_box;
+use pinocchio::log::sol_log_data;
+use pinocchio::pubkey::log as log_pubkey;
+use pinocchio::instruction::{Seed, Signer};
+use pinocchio::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use pinocchio::sysvars::clock::Clock;
+use pinocchio::sysvars::{rent::Rent, Sysvar};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
 #[inline(always)]
 pub fn process_ping() -> ProgramResult {
     Ok(())
 }
 
+#[inline(always)]
+pub fn process_empty_call() -> ProgramResult {
+    Ok(())
+}
+
+/// Stack buffer the message of the `Log` instruction is formatted into with
+/// the `fmt-log` feature, as the program does not allocate.
+#[cfg(feature = "fmt-log")]
+struct LogBuffer {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+#[cfg(feature = "fmt-log")]
+impl core::fmt::Write for LogBuffer {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
 #[inline(always)]
 pub fn process_log() -> ProgramResult {
+    #[cfg(feature = "fmt-log")]
+    {
+        use core::fmt::Write;
+
+        let mut buffer = LogBuffer {
+            bytes: [0; 64],
+            len: 0,
+        };
+        write!(buffer, "Instruction: Log ({})", black_box(1u64))
+            .map_err(|_| ProgramError::InvalidArgument)?;
+        // SAFETY: only `&str`s are written to the buffer.
+        let message = unsafe { core::str::from_utf8_unchecked(&buffer.bytes[..buffer.len]) };
+        pinocchio::log::sol_log(message);
+    }
     Ok(())
 }
 
@@ -28,6 +77,45 @@ pub fn process_account(accounts: &[AccountInfo], expected: u64) -> ProgramResult
     }
 }
 
+/// Reads every header field of each account, isolating the accessor cost.
+#[inline(always)]
+pub fn process_account_fields(accounts: &[AccountInfo]) -> ProgramResult {
+    for account in accounts {
+        black_box(account.key());
+        // SAFETY: The owner is only read; no CPI can reassign it here.
+        black_box(unsafe { account.owner() });
+        black_box(account.lamports());
+        black_box(account.data_len());
+        black_box(account.is_signer());
+        black_box(account.is_writable());
+    }
+    Ok(())
+}
+
+/// Reads the first bytes of each account data through the checked borrow.
+#[inline(always)]
+pub fn process_read_data_checked(accounts: &[AccountInfo]) -> ProgramResult {
+    for account in accounts {
+        let data = account.try_borrow_data()?;
+        black_box(data.first());
+    }
+    Ok(())
+}
+
+/// Reads the first bytes of each account data without borrow tracking.
+///
+/// # Safety
+///
+/// The caller must guarantee that no account data is mutably borrowed.
+#[inline(always)]
+pub unsafe fn process_read_data_unchecked(accounts: &[AccountInfo]) -> ProgramResult {
+    for account in accounts {
+        let data = account.borrow_data_unchecked();
+        black_box(data.first());
+    }
+    Ok(())
+}
+
 #[inline(always)]
 pub fn process_create_account(accounts: &[AccountInfo]) -> ProgramResult {
     let [from, to, _remaining @ ..] = accounts else {
@@ -46,118 +134,905 @@ pub fn process_transfer(accounts: &[AccountInfo]) -> ProgramResult {
     unsafe { transfer_unchecked(from, to, 1_000_000_000) }
 }
 
+/// Performs the canonical owner, signer, writable and rent-exemption checks
+/// on the first `count` accounts.
 #[inline(always)]
-pub fn process_slot_hashes_get_entry(accounts: &[AccountInfo]) -> ProgramResult {
-    let slot_hashes_account = accounts.get(0).ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let slot_hashes = SlotHashes::from_account_info(slot_hashes_account)?;
-    let _ = slot_hashes.get_entry(0);
+pub fn process_validate_accounts(accounts: &[AccountInfo], count: u8) -> ProgramResult {
+    let accounts = accounts
+        .get(..count as usize)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let rent = Rent::get()?;
+
+    for account in accounts {
+        if !account.is_owned_by(&crate::ID) {
+            return Err(ProgramError::IllegalOwner);
+        }
+        if !account.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if !account.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !rent.is_exempt(account.lamports(), account.data_len()) {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+    }
     Ok(())
 }
 
+/// Loads `Rent` from the sysvar account (first account) and checks that every
+/// remaining account is rent exempt.
 #[inline(always)]
-pub fn process_slot_hashes_get_hash_interpolated(accounts: &[AccountInfo]) -> ProgramResult {
-    let slot_hashes_account = accounts.get(0).ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let slot_hashes = SlotHashes::from_account_info(slot_hashes_account)?;
-    let _ = slot_hashes.get_hash(0);
+pub fn process_check_rent_exempt(accounts: &[AccountInfo]) -> ProgramResult {
+    let [rent_account, accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let rent = Rent::from_account_info(rent_account)?;
+
+    for account in accounts {
+        if !rent.is_exempt(account.lamports(), account.data_len()) {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+    }
     Ok(())
 }
 
+/// Loads the program state, increments the counter, stamps the current
+/// unix timestamp and writes it back in place.
 #[inline(always)]
-pub fn process_slot_hashes_position_interpolated(accounts: &[AccountInfo]) -> ProgramResult {
-    let slot_hashes_account = accounts.get(0).ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let slot_hashes = SlotHashes::from_account_info(slot_hashes_account)?;
-    let _ = slot_hashes.position(0);
+pub fn process_read_modify_write(accounts: &[AccountInfo]) -> ProgramResult {
+    let [state_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !state_account.is_owned_by(&crate::ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut data = state_account.try_borrow_mut_data()?;
+    let state = State::load_mut(&mut data)?;
+
+    let counter = u64::from_le_bytes(state.counter)
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    state.counter = counter.to_le_bytes();
+    state.timestamp = Clock::get()?.unix_timestamp.to_le_bytes();
     Ok(())
 }
 
+/// Borsh-serializes an `Event` by hand and emits it with `sol_log_data`.
 #[inline(always)]
-pub unsafe fn process_slot_hashes_get_entry_unchecked(accounts: &[AccountInfo]) -> ProgramResult {
-    let account = &accounts[0];
-    let data = account.borrow_data_unchecked();
-    let _entry = get_entry_from_slice_unchecked(data, 0);
+pub fn process_emit_event() -> ProgramResult {
+    let mut event = [0u8; Event::LEN];
+    Event::sample().serialize(&mut event);
+    sol_log_data(&[&event]);
     Ok(())
 }
 
+/// Same as `process_emit_event`, but base64-encodes the payload in the
+/// program before emitting it.
 #[inline(always)]
-pub unsafe fn process_slot_hashes_get_hash_interpolated_unchecked(
-    accounts: &[AccountInfo],
-) -> ProgramResult {
-    let account = &accounts[0];
-    let data = account.borrow_data_unchecked();
-    let target_slot = 0;
-    let hash_opt = get_hash_from_slice_unchecked(data, target_slot, MAX_SLOT_HASH_ENTRIES);
-    if hash_opt.is_some() {
-        msg!("GH found");
+pub fn process_emit_event_base64() -> ProgramResult {
+    let mut event = [0u8; Event::LEN];
+    Event::sample().serialize(&mut event);
+    let mut encoded = [0u8; Event::BASE64_LEN];
+    let len = base64::encode(&event, &mut encoded);
+    sol_log_data(&[&encoded[..len]]);
+    Ok(())
+}
+
+/// Loads `Clock` through its syscall.
+#[inline(always)]
+pub fn process_clock_get() -> ProgramResult {
+    let clock = Clock::get()?;
+    black_box(&clock);
+    Ok(())
+}
+
+/// Loads `Clock` from the sysvar account (first account).
+#[inline(always)]
+pub fn process_clock_from_account(accounts: &[AccountInfo]) -> ProgramResult {
+    let clock_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    #[cfg(feature = "pinocchio-fork")]
+    let clock = Clock::from_account_info(clock_account)?;
+    // Released versions only load `Rent` from its account, so the clock is
+    // loaded the same way.
+    #[cfg(not(feature = "pinocchio-fork"))]
+    let clock = {
+        if clock_account.key() != &CLOCK_ID {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = clock_account.try_borrow_data()?;
+        if data.len() < core::mem::size_of::<Clock>() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        pinocchio::account_info::Ref::map(data, |data| unsafe { &*(data.as_ptr() as *const Clock) })
+    };
+    black_box(&clock);
+    Ok(())
+}
+
+#[cfg(not(feature = "pinocchio-fork"))]
+const CLOCK_ID: Pubkey = pinocchio_pubkey::pubkey!("SysvarC1ock11111111111111111111111111111111");
+
+/// Loads `Rent` through its syscall.
+#[inline(always)]
+pub fn process_rent_get() -> ProgramResult {
+    let rent = Rent::get()?;
+    black_box(&rent);
+    Ok(())
+}
+
+/// Loads `Rent` from the sysvar account (first account).
+#[inline(always)]
+pub fn process_rent_from_account(accounts: &[AccountInfo]) -> ProgramResult {
+    let rent_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let rent = Rent::from_account_info(rent_account)?;
+    black_box(&rent);
+    Ok(())
+}
+
+/// Walks the Token-2022 extensions of a token account (first account) and
+/// writes its withheld transfer fee to the result account.
+#[inline(always)]
+pub fn process_parse_token_2022_extensions(accounts: &[AccountInfo]) -> ProgramResult {
+    let [token_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !token_account.is_owned_by(&TOKEN_2022_PROGRAM_ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let extensions = Extensions::parse(&token_account.try_borrow_data()?)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    black_box(extensions.count);
+    black_box(extensions.memo_required);
+
+    write_output(accounts, 1, &encode_value(extensions.withheld_amount))
+}
+
+/// Parses the Token Metadata account (first account) in place and writes its
+/// collection to the result account.
+#[inline(always)]
+pub fn process_parse_metadata(accounts: &[AccountInfo]) -> ProgramResult {
+    let [metadata_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !metadata_account.is_owned_by(&TOKEN_METADATA_PROGRAM_ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = metadata_account.try_borrow_data()?;
+    let metadata = Metadata::parse(&data).ok_or(ProgramError::InvalidAccountData)?;
+    black_box(&metadata);
+
+    let collection = metadata.collection.map(|collection| &collection.key);
+    write_output(accounts, 1, &encode_hash(collection))
+}
+
+/// Checks that at least `m` of the remaining accounts are signers from the
+/// first `n` keys of the signer set stored in the multisig account (first
+/// account), matching each stored key at most once.
+#[inline(always)]
+pub fn process_check_multisig(accounts: &[AccountInfo], m: u8, n: u8) -> ProgramResult {
+    let [multisig_account, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if m == 0 || m > n || n as usize > MAX_SIGNERS {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if !multisig_account.is_owned_by(&crate::ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = multisig_account.try_borrow_data()?;
+    let signer_set = data
+        .get(..n as usize * 32)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    let mut matched = [false; MAX_SIGNERS];
+    let mut num_signers = 0u8;
+    for signer in signers {
+        for (position, key) in signer_set.chunks_exact(32).enumerate() {
+            if key == signer.key() && !matched[position] {
+                if !signer.is_signer() {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+                matched[position] = true;
+                num_signers += 1;
+            }
+        }
+    }
+
+    if num_signers < m {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+/// Hashes a pair of merkle nodes with the sha256 syscall.
+#[inline(always)]
+fn hash_nodes(left: &[u8], right: &[u8]) -> [u8; 32] {
+    let vals = [left, right];
+    let mut hash = [0u8; 32];
+
+    #[cfg(target_os = "solana")]
+    unsafe {
+        pinocchio::syscalls::sol_sha256(
+            vals.as_ptr() as *const u8,
+            vals.len() as u64,
+            hash.as_mut_ptr(),
+        );
+    }
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    core::hint::black_box(&vals);
+
+    hash
+}
+
+/// Hashes a sorted pair of merkle nodes.
+#[inline(always)]
+fn hash_pair(a: &[u8], b: &[u8]) -> [u8; 32] {
+    if a <= b {
+        hash_nodes(a, b)
     } else {
-        msg!("GH not found");
+        hash_nodes(b, a)
+    }
+}
+
+/// Verifies that the leaf (first 32 bytes of the proof account, second
+/// account) hashes up with the `depth` proof nodes following it to the root
+/// stored in the tree account (first account). Pairs are sorted before
+/// hashing, so the proof needs no path bits.
+#[inline(always)]
+pub fn process_verify_merkle_proof(accounts: &[AccountInfo], depth: u8) -> ProgramResult {
+    let [tree_account, proof_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !tree_account.is_owned_by(&crate::ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let tree = tree_account.try_borrow_data()?;
+    let root = tree.get(..32).ok_or(ProgramError::AccountDataTooSmall)?;
+    let proof_data = proof_account.try_borrow_data()?;
+    let proof = proof_data
+        .get(..32 * (depth as usize + 1))
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    let (leaf, siblings) = proof.split_at(32);
+    let mut node = [0u8; 32];
+    node.copy_from_slice(leaf);
+    for sibling in siblings.chunks_exact(32) {
+        node = hash_pair(&node, sibling);
+    }
+
+    if node.as_slice() != root {
+        return Err(ProgramError::InvalidArgument);
     }
     Ok(())
 }
 
+/// Appends `leaf` to the concurrent merkle tree stored in the tree account
+/// (first account): hashes it up to the new root, updating the frontier, and
+/// records the root and path in the next changelog buffer entry.
 #[inline(always)]
-pub unsafe fn process_slot_hashes_position_interpolated_unchecked(
-    accounts: &[AccountInfo],
-    target_slot: Slot
-) -> ProgramResult {
-    let account = &accounts[0];
-    let data = account.borrow_data_unchecked();
-    let pos_opt = position_from_slice_binary_search_unchecked(data, target_slot, MAX_SLOT_HASH_ENTRIES);
-    if pos_opt.is_some() {
-        msg!("IP found");
+pub fn process_append_leaf(accounts: &[AccountInfo], leaf: [u8; 32]) -> ProgramResult {
+    let [tree_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !tree_account.is_owned_by(&crate::ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut data = tree_account.try_borrow_mut_data()?;
+    let tree = MerkleTree::load_mut(&mut data)?;
+
+    let index = u64::from_le_bytes(tree.sequence_number);
+    if index >= 1 << MAX_DEPTH {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let active_index = (u64::from_le_bytes(tree.active_index) as usize + 1) % BUFFER_SIZE;
+    let change_log = &mut tree.change_logs[active_index];
+
+    let mut node = leaf;
+    let mut empty = [0u8; 32];
+    for level in 0..MAX_DEPTH {
+        change_log.path[level] = node;
+        if (index >> level) & 1 == 1 {
+            node = hash_nodes(&tree.frontier[level], &node);
+        } else {
+            tree.frontier[level] = node;
+            node = hash_nodes(&node, &empty);
+        }
+        empty = hash_nodes(&empty, &empty);
+    }
+
+    change_log.root = node;
+    change_log.index = (index as u32).to_le_bytes();
+    tree.active_index = (active_index as u64).to_le_bytes();
+    tree.sequence_number = (index + 1).to_le_bytes();
+    Ok(())
+}
+
+/// Writes the sample state into the state account (first account) in place,
+/// with hand-written little-endian writes.
+#[inline(always)]
+pub fn process_write_state_manual(accounts: &[AccountInfo]) -> ProgramResult {
+    let [state_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !state_account.is_owned_by(&crate::ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut data = state_account.try_borrow_mut_data()?;
+    *State::load_mut(&mut data)? = State::sample();
+    Ok(())
+}
+
+/// Integer square root (Newton's method), rounded down.
+#[inline(always)]
+fn isqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = value / 2 + (value & 1);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Performs `iterations` rounds of 64-bit division and modulo, 128-bit
+/// division and integer square root, none of which sBPF has native
+/// instructions for.
+#[inline(always)]
+pub fn process_div_sqrt(iterations: u16) -> ProgramResult {
+    let mut acc = 0u64;
+    for i in 0..iterations as u64 {
+        let dividend = black_box(u64::MAX - i);
+        let divisor = black_box(i + 3);
+
+        acc = acc.wrapping_add(dividend / divisor);
+        acc = acc.wrapping_add(dividend % divisor);
+        let wide = dividend as u128 * dividend as u128;
+        acc = acc.wrapping_add((wide / divisor as u128) as u64);
+        acc = acc.wrapping_add(isqrt(dividend));
+    }
+    black_box(acc);
+    Ok(())
+}
+
+/// Accrues `periods` periods of interest with hand-rolled Q64.64 arithmetic.
+#[inline(always)]
+pub fn process_accrue_interest_q64(periods: u8) -> ProgramResult {
+    black_box(accrue_q64(periods));
+    Ok(())
+}
+
+/// Accrues `periods` periods of interest with the `fixed` crate.
+#[inline(always)]
+pub fn process_accrue_interest_fixed(periods: u8) -> ProgramResult {
+    black_box(accrue_fixed(periods));
+    Ok(())
+}
+
+/// SlotHashes sysvar id.
+const SLOT_HASHES_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("SysvarS1otHashes111111111111111111111111111");
+
+/// Hashes `vals` with the keccak256 syscall.
+#[inline(always)]
+fn keccak(vals: &[&[u8]]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+
+    #[cfg(target_os = "solana")]
+    unsafe {
+        pinocchio::syscalls::sol_keccak256(
+            vals.as_ptr() as *const u8,
+            vals.len() as u64,
+            hash.as_mut_ptr(),
+        );
+    }
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    core::hint::black_box(vals);
+
+    hash
+}
+
+/// Draws `count` pseudo-random `u64` values, each the first 8 bytes of the
+/// keccak256 hash of the most recent slot hash (SlotHashes sysvar, first
+/// account) and the draw index, and writes the last one to the result
+/// account.
+#[inline(always)]
+pub fn process_pseudo_random(accounts: &[AccountInfo], count: u8) -> ProgramResult {
+    let slot_hashes_account = accounts
+        .first()
+        .filter(|account| account.key() == &SLOT_HASHES_ID)
+        .ok_or(ProgramError::InvalidArgument)?;
+    let data = slot_hashes_account.try_borrow_data()?;
+
+    let seed_start = LEN_PREFIX_SIZE + KEY_SIZE;
+    let seed = data
+        .get(seed_start..seed_start + 32)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    let mut value = None;
+    for index in 0..count {
+        let hash = keccak(&[seed, &[index]]);
+        value = Some(black_box(u64::from_le_bytes(hash[..8].try_into().unwrap())));
+    }
+
+    write_output(
+        accounts,
+        1,
+        &encode_position(value.map(|value| value as usize)),
+    )
+}
+
+/// Maximum number of accounts deduplicated by sorting, which sorts their keys
+/// in a stack buffer.
+const MAX_DEDUP_ACCOUNTS: usize = 64;
+
+/// Fails if any two of the first `count` accounts have the same key, comparing
+/// every pair (`strategy` 0) or sorting the keys and comparing neighbors (any
+/// other strategy).
+#[inline(always)]
+pub fn process_dedup_accounts(accounts: &[AccountInfo], count: u8, strategy: u8) -> ProgramResult {
+    let accounts = accounts
+        .get(..count as usize)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let duplicate = if strategy == 0 {
+        accounts.iter().enumerate().any(|(i, account)| {
+            accounts[i + 1..]
+                .iter()
+                .any(|other| other.key() == account.key())
+        })
     } else {
-        msg!("IP not found");
+        let default = Pubkey::default();
+        let mut keys = [&default; MAX_DEDUP_ACCOUNTS];
+        let keys = keys
+            .get_mut(..accounts.len())
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        for (key, account) in keys.iter_mut().zip(accounts) {
+            *key = account.key();
+        }
+        keys.sort_unstable();
+        keys.windows(2).any(|pair| pair[0] == pair[1])
+    };
+
+    if duplicate {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Consumes the first `fixed` accounts, then checks that every remaining
+/// account is owned by the program, as Anchor programs iterate
+/// `remaining_accounts`.
+#[inline(always)]
+pub fn process_remaining(accounts: &[AccountInfo], fixed: u8) -> ProgramResult {
+    if accounts.len() < fixed as usize {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let (fixed_accounts, remaining_accounts) = accounts.split_at(fixed as usize);
+
+    for account in fixed_accounts {
+        black_box(account.key());
+    }
+    for account in remaining_accounts {
+        if !account.is_owned_by(&crate::ID) {
+            return Err(ProgramError::IllegalOwner);
+        }
+    }
+    Ok(())
+}
+
+/// Program ids matched by the `MatchProgramId` benchmark.
+const KNOWN_PROGRAM_IDS: [Pubkey; 8] = [
+    pinocchio_pubkey::pubkey!("11111111111111111111111111111111"),
+    pinocchio_pubkey::pubkey!("ComputeBudget111111111111111111111111111111"),
+    pinocchio_pubkey::pubkey!("AddressLookupTab1e1111111111111111111111111"),
+    pinocchio_pubkey::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+    TOKEN_2022_PROGRAM_ID,
+    pinocchio_pubkey::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"),
+    TOKEN_METADATA_PROGRAM_ID,
+    pinocchio_pubkey::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"),
+];
+
+/// Compares two byte slices of the same length with the `sol_memcmp` syscall.
+#[inline(always)]
+fn memcmp_eq(a: &[u8], b: &[u8]) -> bool {
+    #[cfg(target_os = "solana")]
+    let result = {
+        let mut result = 0i32;
+        unsafe {
+            pinocchio::syscalls::sol_memcmp_(
+                a.as_ptr(),
+                b.as_ptr(),
+                a.len() as u64,
+                &mut result as *mut i32,
+            );
+        }
+        result
+    };
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    let result = (a != b) as i32;
+
+    result == 0
+}
+
+/// Finds the known program id matching the key of the first account and
+/// writes its position to the result account, comparing keys with `==`
+/// (`strategy` 0), as byte slices (`strategy` 1) or with the `sol_memcmp`
+/// syscall (any other strategy).
+#[inline(always)]
+pub fn process_match_program_id(accounts: &[AccountInfo], strategy: u8) -> ProgramResult {
+    let key = accounts
+        .first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?
+        .key();
+
+    let position = match strategy {
+        0 => KNOWN_PROGRAM_IDS.iter().position(|id| key == id),
+        1 => KNOWN_PROGRAM_IDS.iter().position(|id| key[..] == id[..]),
+        _ => KNOWN_PROGRAM_IDS
+            .iter()
+            .position(|id| memcmp_eq(&key[..], &id[..])),
+    };
+    write_output(accounts, 1, &encode_position(position))
+}
+
+/// BPF upgradeable loader id, owner of `ProgramData` accounts.
+const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// Discriminant of `UpgradeableLoaderState::ProgramData`, a bincode `u32`.
+const PROGRAM_DATA_TAG: u32 = 3;
+
+/// Offset of the optional upgrade authority in `ProgramData` accounts, after
+/// the discriminant and the deployment slot.
+const UPGRADE_AUTHORITY_OFFSET: usize = 4 + 8;
+
+/// Reads the upgrade authority of the `ProgramData` account (first account)
+/// and writes it to the result account.
+#[inline(always)]
+pub fn process_parse_program_data(accounts: &[AccountInfo]) -> ProgramResult {
+    let [program_data_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !program_data_account.is_owned_by(&BPF_LOADER_UPGRADEABLE_ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = program_data_account.try_borrow_data()?;
+    // The authority option is followed by the program, so the header is
+    // always 45 bytes long, even without an authority.
+    let header = data
+        .get(..UPGRADE_AUTHORITY_OFFSET + 1 + 32)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    if header[..4] != PROGRAM_DATA_TAG.to_le_bytes() {
+        return Err(ProgramError::InvalidAccountData);
     }
+
+    let authority = match header[UPGRADE_AUTHORITY_OFFSET] {
+        0 => None,
+        1 => Some(header[UPGRADE_AUTHORITY_OFFSET + 1..].try_into().unwrap()),
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    write_output(accounts, 1, &encode_hash(authority))
+}
+
+/// Feature program id, owner of runtime feature accounts.
+const FEATURE_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("Feature111111111111111111111111111111111111");
+
+/// Parses the runtime feature account (first account), a bincode
+/// `Option<u64>` activation slot, and writes the slot to the result account
+/// if the feature is active.
+#[inline(always)]
+pub fn process_check_feature(accounts: &[AccountInfo]) -> ProgramResult {
+    let [feature_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !feature_account.is_owned_by(&FEATURE_PROGRAM_ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = feature_account.try_borrow_data()?;
+    let activated_at = match data.first() {
+        Some(0) => None,
+        Some(1) => {
+            let slot = data.get(1..9).ok_or(ProgramError::InvalidAccountData)?;
+            Some(u64::from_le_bytes(slot.try_into().unwrap()))
+        }
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    write_output(
+        accounts,
+        1,
+        &encode_position(activated_at.map(|slot| slot as usize)),
+    )
+}
+
+/// Size of the fields read by `ReadAlignment`: a `u128` followed by a `u64`.
+const ALIGNMENT_FIELDS_SIZE: usize = 16 + 8;
+
+/// Reads a `u128` field at `offset` within the data of the first account and
+/// the `u64` field following it, with `read_unaligned` (`strategy` 0), by
+/// copying their bytes (`strategy` 1) or by casting the data pointer (any
+/// other strategy), and writes the low 64 bits of their sum to the result
+/// account. Casting fails unless the `u128` field is aligned.
+#[inline(always)]
+pub fn process_read_alignment(accounts: &[AccountInfo], offset: u8, strategy: u8) -> ProgramResult {
+    let [account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let data = account.try_borrow_data()?;
+    let offset = offset as usize;
+    let fields = data
+        .get(offset..offset + ALIGNMENT_FIELDS_SIZE)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let ptr = fields.as_ptr();
+
+    let (large, small) = match strategy {
+        // SAFETY: `fields` holds both values.
+        0 => unsafe {
+            (
+                core::ptr::read_unaligned(ptr as *const u128),
+                core::ptr::read_unaligned(ptr.add(16) as *const u64),
+            )
+        },
+        1 => (
+            u128::from_le_bytes(fields[..16].try_into().unwrap()),
+            u64::from_le_bytes(fields[16..].try_into().unwrap()),
+        ),
+        _ => {
+            if ptr.align_offset(core::mem::align_of::<u128>()) != 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            // SAFETY: `fields` holds both values, and the `u64` field is
+            // aligned as it follows the aligned `u128` field.
+            unsafe { (*(ptr as *const u128), *(ptr.add(16) as *const u64)) }
+        }
+    };
+    write_output(
+        accounts,
+        1,
+        &encode_position(Some((large as u64).wrapping_add(small) as usize)),
+    )
+}
+
+/// Reads the total stake of the current epoch (`vote_account` 0) or the stake
+/// delegated to the vote account (first account) through the
+/// `sol_get_epoch_stake` syscall.
+#[inline(always)]
+pub fn process_get_epoch_stake(accounts: &[AccountInfo], vote_account: u8) -> ProgramResult {
+    // A null vote address requests the total stake.
+    let vote_address = if vote_account == 0 {
+        core::ptr::null()
+    } else {
+        accounts
+            .first()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?
+            .key()
+            .as_ptr()
+    };
+
+    #[cfg(target_os = "solana")]
+    let stake = unsafe { pinocchio::syscalls::sol_get_epoch_stake(vote_address) };
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    let stake = {
+        black_box(vote_address);
+        0
+    };
+
+    black_box(stake);
     Ok(())
 }
 
+/// Allocates a zeroed buffer of `size` bytes with the bump allocator.
+#[cfg(feature = "bump-allocator")]
+#[inline(always)]
+pub fn process_allocate(size: u16) -> ProgramResult {
+    let buffer = alloc::vec![0u8; size as usize];
+    black_box(buffer);
+    Ok(())
+}
+
+/// Fails, as the program is built without an allocator.
+#[cfg(not(feature = "bump-allocator"))]
+#[inline(always)]
+pub fn process_allocate(_size: u16) -> ProgramResult {
+    Err(ProgramError::InvalidInstructionData)
+}
+
+/// Bytes of every seed of `CpiSignedSeeds`, whose seeds only differ by length.
+static SIGNER_SEED: [u8; MAX_SEED_LEN] = [0x5e; MAX_SEED_LEN];
+
+/// Transfers lamports from the program derived address of `num_seeds` seeds
+/// of `seed_len` bytes and the `bump` seed (first account) through CPI,
+/// signing with its seeds.
 #[inline(always)]
-pub unsafe fn process_slot_hashes_position_naive_unchecked(
+pub fn process_cpi_signed_seeds(
     accounts: &[AccountInfo],
-    target_slot: Slot
+    num_seeds: u8,
+    seed_len: u8,
+    bump: u8,
 ) -> ProgramResult {
-    let account = &accounts[0];
-    let data = account.borrow_data_unchecked();
-    let num_entries_val = MAX_SLOT_HASH_ENTRIES;
-
-    // --- Naive Binary Search Logic (directly implemented) ---
-    let result_idx: Option<usize> = {
-        if num_entries_val == 0 {
-            None
+    let [from, to, _remaining @ ..] = accounts else {
+        return Err(ProgramError::InvalidArgument);
+    };
+    let (num_seeds, seed_len) = (num_seeds as usize, seed_len as usize);
+    // The bump seed is one of the `MAX_SEEDS`.
+    if num_seeds >= MAX_SEEDS || seed_len > MAX_SEED_LEN {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let bump = [bump];
+    let seeds: [Seed; MAX_SEEDS] = core::array::from_fn(|index| {
+        if index < num_seeds {
+            Seed::from(&SIGNER_SEED[..seed_len])
         } else {
-            let mut low = 0;
-            let mut high = num_entries_val;
-            let mut found_idx: Option<usize> = None;
-            while low < high {
-                let mid_idx = low + (high - low) / 2;
-                // Bounds check simulation: mid_idx is always < high <= num_entries_val
-                let entry_offset = NUM_ENTRIES_SIZE + mid_idx * ENTRY_SIZE;
-                // Unchecked access relies on benchmark providing correctly sized data (>= offset + ENTRY_SIZE)
-                let entry_bytes = data.get_unchecked(entry_offset..(entry_offset + ENTRY_SIZE));
-                let mid_slot = u64::from_le_bytes(
-                    entry_bytes
-                        .get_unchecked(0..SLOT_SIZE)
-                        .try_into()
-                        .unwrap_unchecked(),
-                );
-                match mid_slot.cmp(&target_slot) {
-                    core::cmp::Ordering::Equal => {
-                        found_idx = Some(mid_idx);
-                        break;
-                    }
-                    // Remember: SlotHashes are stored in descending order
-                    core::cmp::Ordering::Less => high = mid_idx, // mid_slot < target_slot, so target is in lower indices (left half)
-                    core::cmp::Ordering::Greater => low = mid_idx + 1, // mid_slot > target_slot, so target is in higher indices (right half)
-                }
-            }
-            found_idx
+            Seed::from(&bump)
         }
+    });
+    let signer = Signer::from(&seeds[..=num_seeds]);
+
+    unsafe { transfer_signed_unchecked(from, to, 1_000_000_000, &[signer]) }
+}
+
+/// Byte written to the data of the `OwnerRoundTrip` account while the program
+/// owns it.
+const ROUND_TRIP_BYTE: u8 = 0xab;
+
+/// Assigns the system-owned first account to the program through CPI, writes
+/// its data, then zeroes it and assigns the account back to the system
+/// program, which the runtime only allows for zeroed data.
+#[inline(always)]
+pub fn process_owner_round_trip(accounts: &[AccountInfo]) -> ProgramResult {
+    let [account, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
     };
-    if result_idx.is_some() {
-        msg!("NP found");
-    } else {
-        msg!("NP not found");
+    if !account.is_owned_by(&SYSTEM_PROGRAM_ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    unsafe { assign_unchecked(account, &crate::ID)? };
+    // The owner is updated by the runtime when the CPI returns.
+    if !account.is_owned_by(&crate::ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    {
+        let mut data = account.try_borrow_mut_data()?;
+        data.fill(ROUND_TRIP_BYTE);
+        // Keeps the write from being elided by the zeroing.
+        black_box(&mut *data);
+        data.fill(0);
+    }
+    unsafe { account.assign(&SYSTEM_PROGRAM_ID) };
+
+    Ok(())
+}
+
+/// Zeroes a byte slice with the `sol_memset` syscall.
+#[inline(always)]
+fn memset_zero(data: &mut [u8]) {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        pinocchio::syscalls::sol_memset_(data.as_mut_ptr(), 0, data.len() as u64);
+    }
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    {
+        data.fill(0);
+    }
+}
+
+/// Zeroes the first `len` bytes of the data of the first account with a loop
+/// of volatile `u64` writes (`strategy` 0), `fill` (`strategy` 1) or the
+/// `sol_memset` syscall (any other strategy).
+#[inline(always)]
+pub fn process_zero_data(accounts: &[AccountInfo], len: u32, strategy: u8) -> ProgramResult {
+    let [account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let mut data = account.try_borrow_mut_data()?;
+    let data = data
+        .get_mut(..len as usize)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    match strategy {
+        0 => {
+            // SAFETY: every bit pattern is a valid `u64`.
+            let (head, words, tail) = unsafe { data.align_to_mut::<u64>() };
+            // Volatile writes keep the loops from being turned into a
+            // `memset` call.
+            for word in words {
+                unsafe { core::ptr::write_volatile(word, 0) };
+            }
+            for byte in head.iter_mut().chain(tail) {
+                unsafe { core::ptr::write_volatile(byte, 0) };
+            }
+        }
+        1 => data.fill(0),
+        _ => memset_zero(data),
     }
 
     Ok(())
 }
 
+/// Copies a byte slice to another of the same length with the `sol_memcpy`
+/// syscall.
+#[inline(always)]
+fn memcpy(destination: &mut [u8], source: &[u8]) {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        pinocchio::syscalls::sol_memcpy_(
+            destination.as_mut_ptr(),
+            source.as_ptr(),
+            source.len() as u64,
+        );
+    }
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    {
+        destination.copy_from_slice(source);
+    }
+}
+
+/// Copies the first `len` bytes of the data of the first account to the data
+/// of the second account with a loop of volatile byte writes (`strategy` 0),
+/// `copy_from_slice` (`strategy` 1) or the `sol_memcpy` syscall (any other
+/// strategy). Both accounts are borrowed at once, so passing the same account
+/// twice fails.
+#[inline(always)]
+pub fn process_copy_account_data(
+    accounts: &[AccountInfo],
+    len: u32,
+    strategy: u8,
+) -> ProgramResult {
+    let [source_account, destination_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let source_data = source_account.try_borrow_data()?;
+    let mut destination_data = destination_account.try_borrow_mut_data()?;
+    let len = len as usize;
+    let source = source_data
+        .get(..len)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let destination = destination_data
+        .get_mut(..len)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    match strategy {
+        // Volatile writes keep the loop from being turned into a `memcpy`
+        // call.
+        0 => {
+            for (to, from) in destination.iter_mut().zip(source) {
+                unsafe { core::ptr::write_volatile(to, *from) };
+            }
+        }
+        1 => destination.copy_from_slice(source),
+        _ => memcpy(destination, source),
+    }
+
+    Ok(())
+}
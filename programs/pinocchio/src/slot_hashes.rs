@@ -0,0 +1,144 @@
+//! The `SlotHashes` lookups, through the `SlotHashes` sysvar of the pinocchio
+//! fork the program is built against by default.
+//!
+//! Released pinocchio versions have no `sysvars::slot_hashes` module, so the
+//! lookups are behind the `pinocchio-fork` feature, which the version builds
+//! of the program are built without.
+
+use crate::output::{encode_hash, encode_position, write_output};
+#[cfg(not(feature = "bench-silent"))]
+use pinocchio::msg;
+use pinocchio::sysvars::clock::Slot;
+use pinocchio::sysvars::slot_hashes::{
+    get_entry_from_slice_unchecked, get_hash_from_slice_unchecked,
+    position_from_slice_binary_search_unchecked, SlotHashes, ENTRY_SIZE,
+    MAX_ENTRIES as MAX_SLOT_HASH_ENTRIES, NUM_ENTRIES_SIZE, SLOT_SIZE,
+};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+#[inline(always)]
+pub fn process_slot_hashes_get_entry(accounts: &[AccountInfo]) -> ProgramResult {
+    let slot_hashes_account = accounts.get(0).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let slot_hashes = SlotHashes::from_account_info(slot_hashes_account)?;
+    let _ = slot_hashes.get_entry(0);
+    Ok(())
+}
+
+#[inline(always)]
+pub fn process_slot_hashes_get_hash_interpolated(accounts: &[AccountInfo]) -> ProgramResult {
+    let slot_hashes_account = accounts.get(0).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let slot_hashes = SlotHashes::from_account_info(slot_hashes_account)?;
+    let hash = slot_hashes.get_hash(0);
+    write_output(accounts, 1, &encode_hash(hash))
+}
+
+#[inline(always)]
+pub fn process_slot_hashes_position_interpolated(accounts: &[AccountInfo]) -> ProgramResult {
+    let slot_hashes_account = accounts.get(0).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let slot_hashes = SlotHashes::from_account_info(slot_hashes_account)?;
+    let position = slot_hashes.position(0);
+    write_output(accounts, 1, &encode_position(position))
+}
+
+#[inline(always)]
+pub unsafe fn process_slot_hashes_get_entry_unchecked(accounts: &[AccountInfo]) -> ProgramResult {
+    let account = &accounts[0];
+    let data = account.borrow_data_unchecked();
+    let _entry = get_entry_from_slice_unchecked(data, 0);
+    Ok(())
+}
+
+#[inline(always)]
+pub unsafe fn process_slot_hashes_get_hash_interpolated_unchecked(
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account = &accounts[0];
+    let data = account.borrow_data_unchecked();
+    let target_slot = 0;
+    let hash_opt = get_hash_from_slice_unchecked(data, target_slot, MAX_SLOT_HASH_ENTRIES);
+    // Logged by the verbose build only, outside of the measured builds
+    #[cfg(not(feature = "bench-silent"))]
+    if hash_opt.is_some() {
+        msg!("GH found");
+    } else {
+        msg!("GH not found");
+    }
+    write_output(accounts, 1, &encode_hash(hash_opt))
+}
+
+#[inline(always)]
+pub unsafe fn process_slot_hashes_position_interpolated_unchecked(
+    accounts: &[AccountInfo],
+    target_slot: Slot,
+) -> ProgramResult {
+    let account = &accounts[0];
+    let data = account.borrow_data_unchecked();
+    let pos_opt =
+        position_from_slice_binary_search_unchecked(data, target_slot, MAX_SLOT_HASH_ENTRIES);
+    // Logged by the verbose build only, outside of the measured builds
+    #[cfg(not(feature = "bench-silent"))]
+    if pos_opt.is_some() {
+        msg!("IP found");
+    } else {
+        msg!("IP not found");
+    }
+    write_output(accounts, 1, &encode_position(pos_opt))
+}
+
+#[inline(always)]
+pub unsafe fn process_slot_hashes_position_naive_unchecked(
+    accounts: &[AccountInfo],
+    target_slot: Slot,
+) -> ProgramResult {
+    let account = &accounts[0];
+    let data = account.borrow_data_unchecked();
+    let num_entries_val = MAX_SLOT_HASH_ENTRIES;
+
+    // --- Naive Binary Search Logic (directly implemented) ---
+    let result_idx: Option<usize> = {
+        if num_entries_val == 0 {
+            None
+        } else {
+            let mut low = 0;
+            let mut high = num_entries_val;
+            let mut found_idx: Option<usize> = None;
+            while low < high {
+                let mid_idx = low + (high - low) / 2;
+                // Bounds check simulation: mid_idx is always < high <= num_entries_val
+                let entry_offset = NUM_ENTRIES_SIZE + mid_idx * ENTRY_SIZE;
+                // Unchecked access relies on benchmark providing correctly sized data (>=
+                // offset + ENTRY_SIZE)
+                let entry_bytes = data.get_unchecked(entry_offset..(entry_offset + ENTRY_SIZE));
+                let mid_slot = u64::from_le_bytes(
+                    entry_bytes
+                        .get_unchecked(0..SLOT_SIZE)
+                        .try_into()
+                        .unwrap_unchecked(),
+                );
+                match mid_slot.cmp(&target_slot) {
+                    core::cmp::Ordering::Equal => {
+                        found_idx = Some(mid_idx);
+                        break;
+                    }
+                    // Remember: SlotHashes are stored in descending order
+                    core::cmp::Ordering::Less => high = mid_idx, /* mid_slot < target_slot, so
+                                                                   * target is in lower indices
+                                                                   * (left half) */
+                    core::cmp::Ordering::Greater => low = mid_idx + 1, /* mid_slot > target_slot,
+                                                                        * so target is in higher
+                                                                        * indices (right half) */
+                }
+            }
+            found_idx
+        }
+    };
+    // Logged by the verbose build only, outside of the measured builds
+    #[cfg(not(feature = "bench-silent"))]
+    if result_idx.is_some() {
+        msg!("NP found");
+    } else {
+        msg!("NP not found");
+    }
+
+    write_output(accounts, 1, &encode_position(result_idx))
+}
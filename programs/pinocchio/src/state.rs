@@ -0,0 +1,122 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+/// Program state used by the `ReadModifyWrite` benchmark.
+///
+/// Multi-byte fields are stored as byte arrays so the struct has alignment 1
+/// and can be loaded in place from any account data offset.
+#[repr(C)]
+pub struct State {
+    pub counter: [u8; 8],
+    pub timestamp: [u8; 8],
+    pub authority: Pubkey,
+    pub payload: [u8; 152],
+}
+
+impl State {
+    pub const LEN: usize = core::mem::size_of::<State>();
+
+    /// State written by the `WriteState` benchmarks.
+    #[inline(always)]
+    pub fn sample() -> Self {
+        Self {
+            counter: 42u64.to_le_bytes(),
+            timestamp: 1_700_000_000i64.to_le_bytes(),
+            authority: crate::ID,
+            payload: [7; 152],
+        }
+    }
+
+    /// Loads a mutable reference to the state from the start of `bytes`.
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() < Self::LEN {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        // SAFETY: `bytes` holds at least `LEN` bytes and `State` has
+        // alignment 1.
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+}
+
+/// Maximum number of keys in the signer set of the `CheckMultisig` benchmark,
+/// stored as consecutive pubkeys at the start of the multisig account data.
+pub const MAX_SIGNERS: usize = 11;
+
+/// Event emitted by the `EmitEvent` benchmarks.
+pub struct Event {
+    pub kind: u8,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+impl Event {
+    /// Length of the borsh serialization.
+    pub const LEN: usize = 1 + 32 + 8 + 8;
+
+    /// Length of the padded base64 encoding of the serialization.
+    pub const BASE64_LEN: usize = Self::LEN.div_ceil(3) * 4;
+
+    #[inline(always)]
+    pub fn sample() -> Self {
+        Self {
+            kind: 1,
+            user: crate::ID,
+            amount: 1_000_000_000,
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    /// Writes the borsh serialization of the event into `bytes`.
+    #[inline(always)]
+    pub fn serialize(&self, bytes: &mut [u8; Self::LEN]) {
+        bytes[0] = self.kind;
+        bytes[1..33].copy_from_slice(&self.user);
+        bytes[33..41].copy_from_slice(&self.amount.to_le_bytes());
+        bytes[41..49].copy_from_slice(&self.timestamp.to_le_bytes());
+    }
+}
+
+/// Depth of the concurrent merkle tree of the `AppendLeaf` benchmark.
+pub const MAX_DEPTH: usize = 14;
+
+/// Number of entries of the changelog buffer of the `AppendLeaf` benchmark.
+pub const BUFFER_SIZE: usize = 64;
+
+/// Root and path (leaf to root) of the tree after an append.
+#[repr(C)]
+pub struct ChangeLog {
+    pub root: [u8; 32],
+    pub path: [[u8; 32]; MAX_DEPTH],
+    pub index: [u8; 4],
+    pub _padding: [u8; 4],
+}
+
+/// Concurrent merkle tree used by the `AppendLeaf` benchmark, modeled on
+/// state compression: the number of appended leaves, the changelog entry of
+/// the last append, the rightmost left-sibling node of each level and a
+/// circular changelog buffer.
+///
+/// Multi-byte fields are stored as byte arrays so the struct has alignment 1.
+#[repr(C)]
+pub struct MerkleTree {
+    pub sequence_number: [u8; 8],
+    pub active_index: [u8; 8],
+    pub frontier: [[u8; 32]; MAX_DEPTH],
+    pub change_logs: [ChangeLog; BUFFER_SIZE],
+}
+
+impl MerkleTree {
+    pub const LEN: usize = core::mem::size_of::<MerkleTree>();
+
+    /// Loads a mutable reference to the tree from the start of `bytes`.
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() < Self::LEN {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        // SAFETY: `bytes` holds at least `LEN` bytes and `MerkleTree` has
+        // alignment 1.
+        Ok(unsafe { &mut *(bytes.as_mut_ptr() as *mut Self) })
+    }
+}
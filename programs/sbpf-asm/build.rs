@@ -0,0 +1,81 @@
+//! Assembles `src/eisodos_sbpf_asm.s` into a program binary with the `llvm-mc`
+//! and `ld.lld` of the Solana platform tools, linked like `cargo build-sbf`
+//! links Rust programs.
+//!
+//! The platform tools are looked up in `PLATFORM_TOOLS_DIR`, or else in the
+//! latest version installed by `cargo build-sbf` in `~/.cache/solana`. Without
+//! them, an empty binary is written so that the rest of the workspace still
+//! builds, and the runner reports the missing program.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SOURCE: &str = "src/eisodos_sbpf_asm.s";
+const LINKER_SCRIPT: &str = "src/sbf.ld";
+
+fn main() {
+    println!("cargo:rerun-if-changed={SOURCE}");
+    println!("cargo:rerun-if-changed={LINKER_SCRIPT}");
+    println!("cargo:rerun-if-env-changed=PLATFORM_TOOLS_DIR");
+
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let object = out_dir.join("eisodos_sbpf_asm.o");
+    let binary = out_dir.join("eisodos_sbpf_asm.so");
+
+    let Some(llvm) = platform_tools().map(|tools| tools.join("llvm").join("bin")) else {
+        println!(
+            "cargo:warning=platform tools not found, set PLATFORM_TOOLS_DIR to assemble the sBPF \
+             program"
+        );
+        std::fs::write(&binary, []).unwrap();
+        return;
+    };
+
+    run(Command::new(llvm.join("llvm-mc"))
+        .args(["--triple=sbf-solana-solana", "-filetype=obj", "-o"])
+        .arg(&object)
+        .arg(SOURCE));
+    run(Command::new(llvm.join("ld.lld"))
+        .args(["-z", "notext", "-shared", "--Bdynamic"])
+        .args(["--entry", "entrypoint"])
+        .arg("--script")
+        .arg(LINKER_SCRIPT)
+        .arg("-o")
+        .arg(&binary)
+        .arg(&object));
+}
+
+/// Returns the platform tools directory, if any.
+fn platform_tools() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("PLATFORM_TOOLS_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+
+    // Versions are installed as `~/.cache/solana/v<major>.<minor>/platform-tools`.
+    let cache = Path::new(&std::env::var_os("HOME")?).join(".cache/solana");
+    std::fs::read_dir(cache)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let version: Vec<u32> = name
+                .strip_prefix('v')?
+                .split('.')
+                .map(|part| part.parse().ok())
+                .collect::<Option<_>>()?;
+            let tools = entry.path().join("platform-tools");
+            tools
+                .join("llvm/bin/llvm-mc")
+                .exists()
+                .then_some((version, tools))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, tools)| tools)
+}
+
+fn run(command: &mut Command) {
+    let status = command
+        .status()
+        .unwrap_or_else(|error| panic!("failed to run {command:?}: {error}"));
+    assert!(status.success(), "{command:?} failed: {status}");
+}
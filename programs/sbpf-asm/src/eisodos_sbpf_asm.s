@@ -0,0 +1,206 @@
+# Entrypoint and CPI instructions in handwritten sBPF assembly: Ping, Log,
+# Account, CreateAccount, Transfer, EmptyCall and their versioned (V2) forms.
+#
+# The input is the serialized accounts followed by the instruction data and
+# the program id. Each account is either a duplicate (its index and 7 bytes of
+# padding) or, after a 0xff marker:
+#
+#   +1   is_signer
+#   +2   is_writable
+#   +3   executable
+#   +8   key
+#   +40  owner
+#   +72  lamports
+#   +80  data length
+#   +88  data, then 10240 bytes of realloc padding, aligned to 8 bytes
+#        and followed by the rent epoch
+#
+# Registers kept across the instructions:
+#
+#   r1  input
+#   r6  number of accounts
+#   r9  program id
+
+.globl entrypoint
+
+entrypoint:
+    ldxdw r6, [r1+0]
+    mov64 r2, r6
+    mov64 r3, r1
+    add64 r3, 8
+
+    # Skip the accounts to reach the instruction data
+skip_accounts:
+    jeq r2, 0, instruction_data
+    ldxb r4, [r3+0]
+    jne r4, 0xff, skip_duplicate
+    ldxdw r4, [r3+80]
+    add64 r3, r4
+    add64 r3, 10335                 # 88 + 10240 + 7
+    and64 r3, -8
+    add64 r3, 8
+    ja next_account
+skip_duplicate:
+    add64 r3, 8
+next_account:
+    sub64 r2, 1
+    ja skip_accounts
+
+instruction_data:
+    ldxdw r7, [r3+0]                # r7 = length of the instruction data
+    add64 r3, 8                     # r3 = instruction data
+    mov64 r9, r3
+    add64 r9, r7
+    jeq r7, 0, success              # EmptyCall
+    ldxb r8, [r3+0]                 # r8 = tag
+    jne r8, 0x82, dispatch
+
+    # Versioned (V2), not nested: skip the version byte
+    add64 r3, 1
+    sub64 r7, 1
+    jeq r7, 0, success
+    ldxb r8, [r3+0]
+    jeq r8, 0x82, invalid_instruction_data
+
+dispatch:
+    sub64 r7, 1                     # r7 = length of the fields
+    jeq r8, 0, ping
+    jeq r8, 1, log
+    jeq r8, 2, account
+    jeq r8, 3, create_account
+    jeq r8, 4, transfer
+    ja invalid_instruction_data
+
+ping:
+    jne r7, 0, invalid_instruction_data
+    ja success
+
+log:
+    jne r7, 0, invalid_instruction_data
+    # "Instruction: Log", written to the stack as immediates
+    stw [r10-16], 0x74736e49
+    stw [r10-12], 0x74637572
+    stw [r10-8], 0x3a6e6f69
+    stw [r10-4], 0x676f4c20
+    mov64 r1, r10
+    add64 r1, -16
+    mov64 r2, 16
+    call sol_log_
+    ja success
+
+account:
+    jne r7, 8, invalid_instruction_data
+    ldxdw r2, [r3+1]
+    jne r2, r6, invalid_argument
+    ja success
+
+create_account:
+    jne r7, 0, invalid_instruction_data
+    # System program CreateAccount: tag, lamports, space and owner
+    stw [r10-64], 0
+    stdw [r10-60], 500000000
+    stdw [r10-52], 10
+    ldxdw r2, [r9+0]
+    stxdw [r10-44], r2
+    ldxdw r2, [r9+8]
+    stxdw [r10-36], r2
+    ldxdw r2, [r9+16]
+    stxdw [r10-28], r2
+    ldxdw r2, [r9+24]
+    stxdw [r10-20], r2
+    mov64 r8, 52
+    ja invoke_system_program
+
+transfer:
+    jne r7, 0, invalid_instruction_data
+    # System program Transfer: tag and lamports
+    stw [r10-64], 2
+    stdw [r10-60], 1000000000
+    mov64 r8, 12
+
+    # Invokes the system program, the third account, with the first two
+    # accounts and the r8 bytes of instruction data at r10-64. The stack holds:
+    #
+    #   r10-96   SolAccountMeta[2]
+    #   r10-208  SolAccountInfo[2]
+    #   r10-248  SolInstruction
+invoke_system_program:
+    jlt r6, 3, not_enough_account_keys
+    add64 r1, 8
+    mov64 r2, r10
+    add64 r2, -96
+    mov64 r3, r10
+    add64 r3, -208
+    call account_info
+    mov64 r1, r0
+    mov64 r2, r10
+    add64 r2, -80
+    mov64 r3, r10
+    add64 r3, -152
+    call account_info
+    add64 r0, 8
+    stxdw [r10-248], r0             # program_id, the key of the third account
+    mov64 r2, r10
+    add64 r2, -96
+    stxdw [r10-240], r2             # accounts
+    stdw [r10-232], 2               # account_len
+    mov64 r2, r10
+    add64 r2, -64
+    stxdw [r10-224], r2             # data
+    stxdw [r10-216], r8             # data_len
+    mov64 r1, r10
+    add64 r1, -248
+    mov64 r2, r10
+    add64 r2, -208
+    mov64 r3, 2
+    mov64 r4, 0
+    mov64 r5, 0
+    call sol_invoke_signed_c
+    exit
+
+success:
+    mov64 r0, 0
+    exit
+
+invalid_argument:
+    lddw r0, 0x200000000
+    exit
+
+invalid_instruction_data:
+    lddw r0, 0x300000000
+    exit
+
+not_enough_account_keys:
+    lddw r0, 0xb00000000
+    exit
+
+    # Fills the SolAccountMeta at r2 and the SolAccountInfo at r3 with the
+    # (not duplicate) serialized account at r1, returning the next account.
+account_info:
+    mov64 r4, r1
+    add64 r4, 8
+    stxdw [r2+0], r4                # meta.pubkey
+    stxdw [r3+0], r4                # info.key
+    ldxb r5, [r1+2]
+    stxb [r2+8], r5                 # meta.is_writable
+    stxb [r3+49], r5                # info.is_writable
+    ldxb r5, [r1+1]
+    stxb [r2+9], r5                 # meta.is_signer
+    stxb [r3+48], r5                # info.is_signer
+    ldxb r5, [r1+3]
+    stxb [r3+50], r5                # info.executable
+    add64 r4, 32
+    stxdw [r3+32], r4               # info.owner
+    add64 r4, 32
+    stxdw [r3+8], r4                # info.lamports
+    ldxdw r5, [r1+80]
+    stxdw [r3+16], r5               # info.data_len
+    add64 r4, 16
+    stxdw [r3+24], r4               # info.data
+    stdw [r3+40], 0                 # info.rent_epoch
+    mov64 r0, r4
+    add64 r0, r5
+    add64 r0, 10247                 # 10240 + 7
+    and64 r0, -8
+    add64 r0, 8
+    exit
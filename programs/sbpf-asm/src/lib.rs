@@ -0,0 +1,10 @@
+//! Handwritten sBPF assembly implementation of the entrypoint and CPI
+//! instructions, the floor the frameworks are compared to.
+
+const PROG_ID_STR: &str = "Asm1111111111111111111111111111111111111111";
+
+pub const ID: [u8; 32] = const_crypto::bs58::decode_pubkey(PROG_ID_STR);
+
+/// Path of the program binary assembled by the build script, empty if the
+/// platform tools were not found.
+pub const ELF_PATH: &str = concat!(env!("OUT_DIR"), "/eisodos_sbpf_asm.so");
@@ -0,0 +1,33 @@
+use super::invoke_unchecked;
+use solana_nostd_entrypoint::{InstructionC, NoStdAccountInfo};
+use solana_program::{entrypoint::ProgramResult, pubkey::Pubkey, system_program};
+
+/// Assign an account to a program.
+///
+/// This function is a wrapper around the system program's `assign`
+/// instruction.
+///
+/// # Safety
+///
+/// This function assumes that the account is not mutably borrowed.
+pub unsafe fn assign_unchecked(account: &NoStdAccountInfo, owner: &Pubkey) -> ProgramResult {
+    // instruction data
+    // - [0..4 ]: instruction discriminator
+    // - [4..36]: owner pubkey
+    let mut instruction_data = [0; 36];
+    instruction_data[0] = 1;
+    instruction_data[4..36].copy_from_slice(owner.as_ref());
+
+    let instruction_accounts = [account.to_meta_c_signer()];
+
+    invoke_unchecked(
+        &InstructionC {
+            program_id: &system_program::ID,
+            accounts: instruction_accounts.as_ptr(),
+            accounts_len: instruction_accounts.len() as u64,
+            data: instruction_data.as_ptr(),
+            data_len: instruction_data.len() as u64,
+        },
+        &[account],
+    )
+}
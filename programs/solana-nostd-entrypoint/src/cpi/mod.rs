@@ -2,9 +2,11 @@ use solana_nostd_entrypoint::{AccountInfoC, InstructionC, NoStdAccountInfo};
 use solana_program::{entrypoint::ProgramResult, program_error::ProgramError};
 use std::mem::MaybeUninit;
 
+pub mod assign;
 pub mod create_account;
 pub mod transfer;
 
+pub use assign::*;
 pub use create_account::*;
 pub use transfer::*;
 
@@ -27,6 +29,22 @@ pub use transfer::*;
 unsafe fn invoke_unchecked<const ACCOUNTS: usize>(
     instruction: &InstructionC,
     accounts: &[&NoStdAccountInfo; ACCOUNTS],
+) -> ProgramResult {
+    invoke_signed_unchecked(instruction, accounts, &[])
+}
+
+/// Helper function to invoke a program, signing for the program derived
+/// addresses of `seeds`, with the checks of [`invoke_unchecked`].
+///
+/// # Safety
+///
+/// This function assumes that accounts are not mutably borrowed and passed
+/// in the correct order.
+#[inline(always)]
+unsafe fn invoke_signed_unchecked<const ACCOUNTS: usize>(
+    instruction: &InstructionC,
+    accounts: &[&NoStdAccountInfo; ACCOUNTS],
+    seeds: &[&[&[u8]]],
 ) -> ProgramResult {
     if (instruction.accounts_len as usize) < ACCOUNTS {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -41,8 +59,6 @@ unsafe fn invoke_unchecked<const ACCOUNTS: usize>(
             info.write(account.to_info_c());
         });
 
-    let seeds: &[&[&[u8]]] = &[];
-
     #[cfg(target_os = "solana")]
     unsafe {
         solana_program::syscalls::sol_invoke_signed_c(
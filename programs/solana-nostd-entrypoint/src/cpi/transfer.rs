@@ -1,4 +1,4 @@
-use super::invoke_unchecked;
+use super::invoke_signed_unchecked;
 use solana_nostd_entrypoint::{InstructionC, NoStdAccountInfo};
 use solana_program::{entrypoint::ProgramResult, system_program};
 
@@ -14,6 +14,21 @@ pub unsafe fn transfer_unchecked(
     from: &NoStdAccountInfo,
     to: &NoStdAccountInfo,
     lamports: u64,
+) -> ProgramResult {
+    transfer_signed_unchecked(from, to, lamports, &[])
+}
+
+/// Transfer lamports from a program derived address signed for by `seeds`.
+///
+/// # Safety
+///
+/// This function assumes that accounts are not mutably borrowed.
+#[inline(always)]
+pub unsafe fn transfer_signed_unchecked(
+    from: &NoStdAccountInfo,
+    to: &NoStdAccountInfo,
+    lamports: u64,
+    seeds: &[&[&[u8]]],
 ) -> ProgramResult {
     // instruction data
     // - [0..4  ]: instruction discriminator
@@ -24,7 +39,7 @@ pub unsafe fn transfer_unchecked(
 
     let instruction_accounts = [from.to_meta_c_signer(), to.to_meta_c_signer()];
 
-    invoke_unchecked(
+    invoke_signed_unchecked(
         &InstructionC {
             program_id: &system_program::ID,
             accounts: instruction_accounts.as_ptr(),
@@ -33,5 +48,6 @@ pub unsafe fn transfer_unchecked(
             data_len: instruction_data.len() as u64,
         },
         &[from, to],
+        seeds,
     )
 }
@@ -1,18 +1,30 @@
-use crate::processor::{process_create_account, process_transfer};
+use crate::processor::{
+    process_accrue_interest_fixed, process_accrue_interest_q64, process_append_leaf,
+    process_check_feature, process_check_multisig, process_check_rent_exempt,
+    process_copy_account_data, process_cpi_signed_seeds, process_create_account,
+    process_dedup_accounts, process_div_sqrt, process_emit_event, process_emit_event_base64,
+    process_empty_call, process_get_epoch_stake, process_match_program_id,
+    process_owner_round_trip, process_parse_metadata, process_parse_program_data,
+    process_parse_token_2022_extensions, process_pseudo_random, process_read_alignment,
+    process_read_modify_write, process_remaining, process_slot_hashes_get_entry_zerocopy,
+    process_slot_hashes_get_hash_zerocopy, process_slot_hashes_position_zerocopy, process_transfer,
+    process_validate_accounts, process_verify_merkle_proof, process_write_state_bincode,
+    process_write_state_borsh, process_write_state_manual, process_zero_data,
+};
 #[allow(unused_imports)]
 use {
     crate::{
         instruction::Instruction,
         processor::{
-            process_account, process_log, process_ping, process_slot_hashes_get_entry,
-            process_slot_hashes_get_hash_interpolated,
-            process_slot_hashes_position_interpolated,
+            process_account, process_account_fields, process_log, process_ping,
+            process_read_data_checked, process_read_data_unchecked, process_slot_hashes_get_entry,
+            process_slot_hashes_get_hash_interpolated, process_slot_hashes_position_interpolated,
         },
     },
     solana_nostd_entrypoint::{
         basic_panic_impl, entrypoint_nostd, noalloc_allocator, NoStdAccountInfo,
     },
-    solana_program::{entrypoint::ProgramResult, log, pubkey::Pubkey},
+    solana_program::{entrypoint::ProgramResult, log, program_error::ProgramError, pubkey::Pubkey},
 };
 
 entrypoint_nostd!(process_instruction, 64);
@@ -25,7 +37,8 @@ pub fn process_instruction(
     accounts: &[NoStdAccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let instruction = Instruction::unpack(instruction_data)?;
+    let instruction =
+        Instruction::unpack(instruction_data).ok_or(ProgramError::InvalidInstructionData)?;
 
     match instruction {
         Instruction::Ping => process_ping(),
@@ -40,5 +53,65 @@ pub fn process_instruction(
         Instruction::SlotHashesPositionInterpolated => {
             process_slot_hashes_position_interpolated(accounts)
         }
+        Instruction::AccountFields => process_account_fields(accounts),
+        Instruction::ReadDataChecked => process_read_data_checked(accounts),
+        Instruction::ReadDataUnchecked => unsafe { process_read_data_unchecked(accounts) },
+        Instruction::ValidateAccounts { count } => process_validate_accounts(accounts, count),
+        Instruction::CheckRentExempt => process_check_rent_exempt(accounts),
+        Instruction::ReadModifyWrite => process_read_modify_write(accounts),
+        Instruction::EmitEvent => process_emit_event(),
+        Instruction::EmitEventBase64 => process_emit_event_base64(),
+        Instruction::ParseToken2022Extensions => process_parse_token_2022_extensions(accounts),
+        Instruction::ParseMetadata => process_parse_metadata(accounts),
+        Instruction::CheckMultisig { m, n } => process_check_multisig(accounts, m, n),
+        Instruction::VerifyMerkleProof { depth } => process_verify_merkle_proof(accounts, depth),
+        Instruction::AppendLeaf { leaf } => process_append_leaf(accounts, leaf),
+        Instruction::WriteStateBorsh => process_write_state_borsh(accounts),
+        Instruction::WriteStateBincode => process_write_state_bincode(accounts),
+        Instruction::WriteStateManual => process_write_state_manual(accounts),
+        Instruction::SlotHashesGetEntryZerocopy => process_slot_hashes_get_entry_zerocopy(accounts),
+        Instruction::SlotHashesGetHashZerocopy => process_slot_hashes_get_hash_zerocopy(accounts),
+        Instruction::SlotHashesPositionZerocopy => process_slot_hashes_position_zerocopy(accounts),
+        Instruction::DivSqrt { iterations } => process_div_sqrt(iterations),
+        Instruction::AccrueInterestQ64 { periods } => process_accrue_interest_q64(periods),
+        Instruction::AccrueInterestFixed { periods } => process_accrue_interest_fixed(periods),
+        Instruction::PseudoRandom { count } => process_pseudo_random(accounts, count),
+        Instruction::DedupAccounts { count, strategy } => {
+            process_dedup_accounts(accounts, count, strategy)
+        }
+        Instruction::ProcessRemaining { fixed } => process_remaining(accounts, fixed),
+        Instruction::MatchProgramId { strategy } => process_match_program_id(accounts, strategy),
+        Instruction::ParseProgramData => process_parse_program_data(accounts),
+        Instruction::CheckFeature => process_check_feature(accounts),
+        Instruction::ReadAlignment { offset, strategy } => {
+            process_read_alignment(accounts, offset, strategy)
+        }
+        Instruction::GetEpochStake { vote_account } => {
+            process_get_epoch_stake(accounts, vote_account)
+        }
+        Instruction::EmptyCall => process_empty_call(),
+        Instruction::CpiSignedSeeds {
+            num_seeds,
+            seed_len,
+            bump,
+        } => process_cpi_signed_seeds(accounts, num_seeds, seed_len, bump),
+        Instruction::OwnerRoundTrip => process_owner_round_trip(accounts),
+        Instruction::ZeroData { len, strategy } => process_zero_data(accounts, len, strategy),
+        Instruction::CopyAccountData { len, strategy } => {
+            process_copy_account_data(accounts, len, strategy)
+        }
+        // Implemented by other programs only.
+        Instruction::SlotHashesGetEntryUnchecked
+        | Instruction::SlotHashesGetHashInterpolatedUnchecked
+        | Instruction::SlotHashesPositionInterpolatedUnchecked { .. }
+        | Instruction::SlotHashesPositionNaiveUnchecked { .. }
+        | Instruction::Dispatch { .. }
+        | Instruction::ClockGet
+        | Instruction::ClockFromAccount
+        | Instruction::RentGet
+        | Instruction::RentFromAccount
+        | Instruction::StakeHistoryPosition { .. }
+        | Instruction::SlotHashesPositionSysvar { .. }
+        | Instruction::Allocate { .. } => Err(ProgramError::InvalidInstructionData),
     }
 }
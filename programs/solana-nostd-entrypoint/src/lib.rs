@@ -1,6 +1,13 @@
 pub mod cpi;
 pub mod entrypoint;
-pub mod instruction;
+pub mod metadata;
+pub mod output;
 pub mod processor;
+pub mod state;
+pub mod token_2022;
+
+pub use eisodos_base64 as base64;
+pub use eisodos_fixed_point as fixed_point;
+pub use eisodos_instructions as instruction;
 
 solana_program::declare_id!("SoLanaNoStdEntrypoint1111111111111111111111");
@@ -0,0 +1,9 @@
+//! Token Metadata account layout, as read by the `ParseMetadata` benchmark,
+//! parsed in place by [`eisodos_metadata`].
+
+pub use eisodos_metadata::Metadata;
+use solana_program::pubkey::Pubkey;
+
+/// Token Metadata program id.
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
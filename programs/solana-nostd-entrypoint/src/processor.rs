@@ -1,12 +1,29 @@
 use bytemuck::{cast_slice, from_bytes, Pod, Zeroable};
 use core::cmp::Ordering;
+use core::hint::black_box;
 use core::mem::size_of;
+use zerocopy::{FromBytes, Immutable, KnownLayout};
 use {
-    crate::cpi::{create_account_unchecked, transfer_unchecked},
+    crate::base64,
+    crate::cpi::{
+        assign_unchecked, create_account_unchecked, transfer_signed_unchecked, transfer_unchecked,
+    },
+    crate::fixed_point::{accrue_fixed, accrue_q64},
+    crate::metadata::{Metadata, TOKEN_METADATA_PROGRAM_ID},
+    crate::output::{encode_hash, encode_position, encode_value, write_output},
+    crate::state::{Event, MerkleTree, State, BUFFER_SIZE, MAX_DEPTH, MAX_SIGNERS},
+    crate::token_2022::{Extensions, TOKEN_2022_PROGRAM_ID},
+    borsh::BorshSerialize,
     solana_nostd_entrypoint::NoStdAccountInfo,
     solana_program::sysvar::slot_hashes as solana_slot_hashes,
+    solana_program::{clock::Clock, hash::hashv, keccak, log::sol_log_data, rent::Rent, sysvar::Sysvar},
     // borsh::de::BorshDeserialize, // Removed as unused (code using it is commented out)
-    solana_program::{entrypoint::ProgramResult, program_error::ProgramError},
+    solana_program::{
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN},
+        system_program,
+    },
 };
 
 #[inline(always)]
@@ -14,8 +31,48 @@ pub fn process_ping() -> ProgramResult {
     Ok(())
 }
 
+#[inline(always)]
+pub fn process_empty_call() -> ProgramResult {
+    Ok(())
+}
+
+/// Stack buffer the message of the `Log` instruction is formatted into with
+/// the `fmt-log` feature, as the program does not allocate.
+#[cfg(feature = "fmt-log")]
+struct LogBuffer {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+#[cfg(feature = "fmt-log")]
+impl core::fmt::Write for LogBuffer {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
 #[inline(always)]
 pub fn process_log() -> ProgramResult {
+    #[cfg(feature = "fmt-log")]
+    {
+        use core::fmt::Write;
+
+        let mut buffer = LogBuffer {
+            bytes: [0; 64],
+            len: 0,
+        };
+        write!(buffer, "Instruction: Log ({})", black_box(1u64))
+            .map_err(|_| ProgramError::InvalidArgument)?;
+        // SAFETY: only `&str`s are written to the buffer.
+        let message = unsafe { core::str::from_utf8_unchecked(&buffer.bytes[..buffer.len]) };
+        solana_program::log::sol_log(message);
+    }
     Ok(())
 }
 
@@ -28,6 +85,44 @@ pub fn process_account(accounts: &[NoStdAccountInfo], expected: u64) -> ProgramR
     }
 }
 
+/// Reads every header field of each account, isolating the accessor cost.
+#[inline(always)]
+pub fn process_account_fields(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    for account in accounts {
+        black_box(account.key());
+        black_box(account.owner());
+        black_box(*account.try_borrow_lamports()?);
+        black_box(account.data_len());
+        black_box(account.is_signer());
+        black_box(account.is_writable());
+    }
+    Ok(())
+}
+
+/// Reads the first bytes of each account data through the checked borrow.
+#[inline(always)]
+pub fn process_read_data_checked(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    for account in accounts {
+        let data = account.try_borrow_data()?;
+        black_box(data.first());
+    }
+    Ok(())
+}
+
+/// Reads the first bytes of each account data without borrow tracking.
+///
+/// # Safety
+///
+/// The caller must guarantee that no account data is mutably borrowed.
+#[inline(always)]
+pub unsafe fn process_read_data_unchecked(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    for account in accounts {
+        let data = account.unchecked_borrow_data();
+        black_box(data.first());
+    }
+    Ok(())
+}
+
 #[inline(always)]
 pub fn process_create_account(accounts: &[NoStdAccountInfo]) -> ProgramResult {
     let [from, to, _remaining @ ..] = accounts else {
@@ -48,9 +143,10 @@ pub fn process_transfer(accounts: &[NoStdAccountInfo]) -> ProgramResult {
 
 // --- Bytemuck based SlotHashes access ---
 
-// Define the structure matching the account data layout
+// Define the structure matching the account data layout. It derives both the
+// bytemuck and the zerocopy traits, so both crates read the same layout.
 #[repr(C)]
-#[derive(Clone, Copy, Zeroable, Pod, Debug)]
+#[derive(Clone, Copy, Zeroable, Pod, FromBytes, KnownLayout, Immutable, Debug)]
 struct SlotHashEntry {
     slot: u64,
     hash: [u8; 32],
@@ -59,16 +155,18 @@ struct SlotHashEntry {
 const SLOT_HASH_ENTRY_SIZE: usize = size_of::<SlotHashEntry>(); // Should be 8 + 32 = 40
 const LEN_PREFIX_SIZE: usize = size_of::<u64>();
 
-// Enum to specify which operation the pure function should perform
+/// Operation performed by [`process_slot_hashes_bytes`] on the raw SlotHashes
+/// data.
 #[derive(PartialEq, Eq, Debug)]
-enum SlotHashOp {
+pub enum SlotHashOp {
     IsEmpty,          // Returns Ok(()) or Err
     GetHash(u64),     // Search operations return Ok(Option<index>) or Err
     GetPosition(u64), // Search operations return Ok(Option<index>) or Err
 }
 
-// Core logic, testable with &[u8]
-fn process_slot_hashes_bytes(
+/// Core logic of the bytemuck lookups, on the raw SlotHashes data, so that it
+/// can be tested and benchmarked on the host.
+pub fn process_slot_hashes_bytes(
     data: &[u8],
     operation: SlotHashOp,
 ) -> Result<Option<usize>, ProgramError> {
@@ -132,7 +230,11 @@ pub fn process_slot_hashes_get_hash_interpolated(accounts: &[NoStdAccountInfo])
 
     let data = slot_hashes_account.try_borrow_data()?;
 
-    process_slot_hashes_bytes(&data, SlotHashOp::GetHash(0)).map(|_| ())
+    let hash = process_slot_hashes_bytes(&data, SlotHashOp::GetHash(0))?.and_then(|position| {
+        let offset = LEN_PREFIX_SIZE + position * SLOT_HASH_ENTRY_SIZE + size_of::<u64>();
+        data.get(offset..offset + 32)?.try_into().ok()
+    });
+    write_output(accounts, 1, &encode_hash(hash))
 }
 
 #[inline(always)]
@@ -144,7 +246,945 @@ pub fn process_slot_hashes_position_interpolated(accounts: &[NoStdAccountInfo])
 
     let data = slot_hashes_account.try_borrow_data()?;
 
-    process_slot_hashes_bytes(&data, SlotHashOp::GetPosition(0)).map(|_| ())
+    let position = process_slot_hashes_bytes(&data, SlotHashOp::GetPosition(0))?;
+    write_output(accounts, 1, &encode_position(position))
+}
+
+// --- zerocopy based SlotHashes access ---
+
+/// Same as [`process_slot_hashes_bytes`], with the length prefix and the
+/// entries read through zerocopy instead of bytemuck.
+pub fn process_slot_hashes_bytes_zerocopy(
+    data: &[u8],
+    operation: SlotHashOp,
+) -> Result<Option<usize>, ProgramError> {
+    let (num_entries, entries_data) =
+        u64::read_from_prefix(data).map_err(|_| ProgramError::AccountDataTooSmall)?;
+
+    let (entries, _) =
+        <[SlotHashEntry]>::ref_from_prefix_with_elems(entries_data, num_entries as usize)
+            .map_err(|_| ProgramError::AccountDataTooSmall)?;
+
+    match operation {
+        SlotHashOp::IsEmpty => {
+            let _ = entries.is_empty();
+            Ok(None)
+        }
+        SlotHashOp::GetHash(target_slot) | SlotHashOp::GetPosition(target_slot) => {
+            let search_result =
+                entries.binary_search_by(|entry| entry.slot.cmp(&target_slot).reverse());
+            Ok(search_result.ok())
+        }
+    }
+}
+
+// Solana SDK SlotHashes Sysvar Processors (Using zerocopy)
+
+#[inline(always)]
+pub fn process_slot_hashes_get_entry_zerocopy(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let slot_hashes_account = accounts
+        .get(0)
+        .filter(|acc| acc.key() == &solana_slot_hashes::ID)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    let data = slot_hashes_account.try_borrow_data()?;
+
+    process_slot_hashes_bytes_zerocopy(&data, SlotHashOp::IsEmpty).map(|_| ())
+}
+
+#[inline(always)]
+pub fn process_slot_hashes_get_hash_zerocopy(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let slot_hashes_account = accounts
+        .get(0)
+        .filter(|acc| acc.key() == &solana_slot_hashes::ID)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    let data = slot_hashes_account.try_borrow_data()?;
+
+    let hash =
+        process_slot_hashes_bytes_zerocopy(&data, SlotHashOp::GetHash(0))?.and_then(|position| {
+            let offset = LEN_PREFIX_SIZE + position * SLOT_HASH_ENTRY_SIZE + size_of::<u64>();
+            data.get(offset..offset + 32)?.try_into().ok()
+        });
+    write_output(accounts, 1, &encode_hash(hash))
+}
+
+#[inline(always)]
+pub fn process_slot_hashes_position_zerocopy(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let slot_hashes_account = accounts
+        .get(0)
+        .filter(|acc| acc.key() == &solana_slot_hashes::ID)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    let data = slot_hashes_account.try_borrow_data()?;
+
+    let position = process_slot_hashes_bytes_zerocopy(&data, SlotHashOp::GetPosition(0))?;
+    write_output(accounts, 1, &encode_position(position))
+}
+
+/// Performs the canonical owner, signer, writable and rent-exemption checks
+/// on the first `count` accounts.
+#[inline(always)]
+pub fn process_validate_accounts(accounts: &[NoStdAccountInfo], count: u8) -> ProgramResult {
+    let accounts = accounts
+        .get(..count as usize)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let rent = Rent::get()?;
+
+    for account in accounts {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::IllegalOwner);
+        }
+        if !account.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if !account.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !rent.is_exempt(*account.try_borrow_lamports()?, account.data_len()) {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+    }
+    Ok(())
+}
+
+/// Loads `Rent` from the sysvar account (first account) and checks that every
+/// remaining account is rent exempt.
+///
+/// `NoStdAccountInfo` cannot be used with `Sysvar::from_account_info`, so the
+/// bincode layout (`u64`, `f64`, `u8`) is read directly.
+#[inline(always)]
+pub fn process_check_rent_exempt(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let [rent_account, accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if rent_account.key() != &solana_program::sysvar::rent::ID {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let data = rent_account.try_borrow_data()?;
+    if data.len() < 17 {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    let rent = Rent {
+        lamports_per_byte_year: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+        exemption_threshold: f64::from_le_bytes(data[8..16].try_into().unwrap()),
+        burn_percent: data[16],
+    };
+
+    for account in accounts {
+        if !rent.is_exempt(*account.try_borrow_lamports()?, account.data_len()) {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+    }
+    Ok(())
+}
+
+/// Copies the program state out of the account, increments the counter,
+/// stamps the current unix timestamp and copies it back.
+#[inline(always)]
+pub fn process_read_modify_write(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let [state_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if state_account.owner() != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut data = state_account.try_borrow_mut_data()?;
+    let bytes = data
+        .get_mut(..State::LEN)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let mut state: State = bytemuck::pod_read_unaligned(bytes);
+
+    state.counter = state
+        .counter
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    state.timestamp = Clock::get()?.unix_timestamp;
+
+    bytes.copy_from_slice(bytemuck::bytes_of(&state));
+    Ok(())
+}
+
+/// Borsh-serializes an `Event` and emits it with `sol_log_data`.
+#[inline(always)]
+pub fn process_emit_event() -> ProgramResult {
+    let mut event = [0u8; Event::LEN];
+    Event::sample()
+        .serialize(&mut &mut event[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    sol_log_data(&[&event]);
+    Ok(())
+}
+
+/// Same as `process_emit_event`, but base64-encodes the payload in the
+/// program before emitting it.
+#[inline(always)]
+pub fn process_emit_event_base64() -> ProgramResult {
+    let mut event = [0u8; Event::LEN];
+    Event::sample()
+        .serialize(&mut &mut event[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let mut encoded = [0u8; Event::BASE64_LEN];
+    let len = base64::encode(&event, &mut encoded);
+    sol_log_data(&[&encoded[..len]]);
+    Ok(())
+}
+
+/// Walks the Token-2022 extensions of a token account (first account) and
+/// writes its withheld transfer fee to the result account.
+#[inline(always)]
+pub fn process_parse_token_2022_extensions(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let [token_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if token_account.owner() != &TOKEN_2022_PROGRAM_ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let extensions = Extensions::parse(&token_account.try_borrow_data()?)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    black_box(extensions.count);
+    black_box(extensions.memo_required);
+
+    write_output(accounts, 1, &encode_value(extensions.withheld_amount))
+}
+
+/// Parses the Token Metadata account (first account) in place and writes its
+/// collection to the result account.
+#[inline(always)]
+pub fn process_parse_metadata(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let [metadata_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if metadata_account.owner() != &TOKEN_METADATA_PROGRAM_ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = metadata_account.try_borrow_data()?;
+    let metadata = Metadata::parse(&data).ok_or(ProgramError::InvalidAccountData)?;
+    black_box(&metadata);
+
+    let collection = metadata.collection.map(|collection| &collection.key);
+    write_output(accounts, 1, &encode_hash(collection))
+}
+
+/// Checks that at least `m` of the remaining accounts are signers from the
+/// first `n` keys of the signer set stored in the multisig account (first
+/// account), matching each stored key at most once.
+#[inline(always)]
+pub fn process_check_multisig(accounts: &[NoStdAccountInfo], m: u8, n: u8) -> ProgramResult {
+    let [multisig_account, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if m == 0 || m > n || n as usize > MAX_SIGNERS {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if multisig_account.owner() != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = multisig_account.try_borrow_data()?;
+    let signer_set = data
+        .get(..n as usize * 32)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    let mut matched = [false; MAX_SIGNERS];
+    let mut num_signers = 0u8;
+    for signer in signers {
+        for (position, key) in signer_set.chunks_exact(32).enumerate() {
+            if key == signer.key().as_ref() && !matched[position] {
+                if !signer.is_signer() {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+                matched[position] = true;
+                num_signers += 1;
+            }
+        }
+    }
+
+    if num_signers < m {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+/// Hashes a pair of merkle nodes with `hashv` (the sha256 syscall).
+#[inline(always)]
+fn hash_nodes(left: &[u8], right: &[u8]) -> [u8; 32] {
+    hashv(&[left, right]).to_bytes()
+}
+
+/// Hashes a sorted pair of merkle nodes.
+#[inline(always)]
+fn hash_pair(a: &[u8], b: &[u8]) -> [u8; 32] {
+    if a <= b {
+        hash_nodes(a, b)
+    } else {
+        hash_nodes(b, a)
+    }
+}
+
+/// Verifies that the leaf (first 32 bytes of the proof account, second
+/// account) hashes up with the `depth` proof nodes following it to the root
+/// stored in the tree account (first account). Pairs are sorted before
+/// hashing, so the proof needs no path bits.
+#[inline(always)]
+pub fn process_verify_merkle_proof(accounts: &[NoStdAccountInfo], depth: u8) -> ProgramResult {
+    let [tree_account, proof_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if tree_account.owner() != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let tree = tree_account.try_borrow_data()?;
+    let root = tree.get(..32).ok_or(ProgramError::AccountDataTooSmall)?;
+    let proof_data = proof_account.try_borrow_data()?;
+    let proof = proof_data
+        .get(..32 * (depth as usize + 1))
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    let (leaf, siblings) = proof.split_at(32);
+    let mut node = [0u8; 32];
+    node.copy_from_slice(leaf);
+    for sibling in siblings.chunks_exact(32) {
+        node = hash_pair(&node, sibling);
+    }
+
+    if node.as_slice() != root {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Appends `leaf` to the concurrent merkle tree stored in the tree account
+/// (first account): hashes it up to the new root, updating the frontier, and
+/// records the root and path in the next changelog buffer entry.
+#[inline(always)]
+pub fn process_append_leaf(accounts: &[NoStdAccountInfo], leaf: [u8; 32]) -> ProgramResult {
+    let [tree_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if tree_account.owner() != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut data = tree_account.try_borrow_mut_data()?;
+    let bytes = data
+        .get_mut(..MerkleTree::LEN)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let tree: &mut MerkleTree =
+        bytemuck::try_from_bytes_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let index = tree.sequence_number;
+    if index >= 1 << MAX_DEPTH {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let active_index = (tree.active_index as usize + 1) % BUFFER_SIZE;
+    let change_log = &mut tree.change_logs[active_index];
+
+    let mut node = leaf;
+    let mut empty = [0u8; 32];
+    for level in 0..MAX_DEPTH {
+        change_log.path[level] = node;
+        if (index >> level) & 1 == 1 {
+            node = hash_nodes(&tree.frontier[level], &node);
+        } else {
+            tree.frontier[level] = node;
+            node = hash_nodes(&node, &empty);
+        }
+        empty = hash_nodes(&empty, &empty);
+    }
+
+    change_log.root = node;
+    change_log.index = index as u32;
+    tree.active_index = active_index as u64;
+    tree.sequence_number = index + 1;
+    Ok(())
+}
+
+/// Returns the program-owned state account (first account) of the
+/// `WriteState` benchmarks.
+#[inline(always)]
+fn write_state_account(accounts: &[NoStdAccountInfo]) -> Result<&NoStdAccountInfo, ProgramError> {
+    let [state_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if state_account.owner() != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Ok(state_account)
+}
+
+/// Serializes the sample state into the state account with borsh.
+#[inline(always)]
+pub fn process_write_state_borsh(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let mut data = write_state_account(accounts)?.try_borrow_mut_data()?;
+    State::sample()
+        .serialize(&mut &mut data[..])
+        .map_err(|_| ProgramError::AccountDataTooSmall)
+}
+
+/// Serializes the sample state into the state account with bincode.
+#[inline(always)]
+pub fn process_write_state_bincode(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let mut data = write_state_account(accounts)?.try_borrow_mut_data()?;
+    bincode::serialize_into(&mut data[..], &State::sample())
+        .map_err(|_| ProgramError::AccountDataTooSmall)
+}
+
+/// Writes the sample state into the state account with hand-written
+/// little-endian writes.
+#[inline(always)]
+pub fn process_write_state_manual(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let mut data = write_state_account(accounts)?.try_borrow_mut_data()?;
+    State::sample().write(&mut data)
+}
+
+/// Integer square root (Newton's method), rounded down.
+#[inline(always)]
+fn isqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = value / 2 + (value & 1);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Performs `iterations` rounds of 64-bit division and modulo, 128-bit
+/// division and integer square root, none of which sBPF has native
+/// instructions for.
+#[inline(always)]
+pub fn process_div_sqrt(iterations: u16) -> ProgramResult {
+    let mut acc = 0u64;
+    for i in 0..iterations as u64 {
+        let dividend = black_box(u64::MAX - i);
+        let divisor = black_box(i + 3);
+
+        acc = acc.wrapping_add(dividend / divisor);
+        acc = acc.wrapping_add(dividend % divisor);
+        let wide = dividend as u128 * dividend as u128;
+        acc = acc.wrapping_add((wide / divisor as u128) as u64);
+        acc = acc.wrapping_add(isqrt(dividend));
+    }
+    black_box(acc);
+    Ok(())
+}
+
+/// Accrues `periods` periods of interest with hand-rolled Q64.64 arithmetic.
+#[inline(always)]
+pub fn process_accrue_interest_q64(periods: u8) -> ProgramResult {
+    black_box(accrue_q64(periods));
+    Ok(())
+}
+
+/// Accrues `periods` periods of interest with the `fixed` crate.
+#[inline(always)]
+pub fn process_accrue_interest_fixed(periods: u8) -> ProgramResult {
+    black_box(accrue_fixed(periods));
+    Ok(())
+}
+
+/// Draws `count` pseudo-random `u64` values, each the first 8 bytes of the
+/// keccak256 hash of the most recent slot hash (SlotHashes sysvar, first
+/// account) and the draw index, and writes the last one to the result
+/// account.
+#[inline(always)]
+pub fn process_pseudo_random(accounts: &[NoStdAccountInfo], count: u8) -> ProgramResult {
+    let slot_hashes_account = accounts
+        .first()
+        .filter(|acc| acc.key() == &solana_slot_hashes::ID)
+        .ok_or(ProgramError::InvalidArgument)?;
+    let data = slot_hashes_account.try_borrow_data()?;
+
+    let seed_start = LEN_PREFIX_SIZE + size_of::<u64>();
+    let seed = data
+        .get(seed_start..seed_start + 32)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    let mut value = None;
+    for index in 0..count {
+        let hash = keccak::hashv(&[seed, &[index]]).to_bytes();
+        value = Some(black_box(u64::from_le_bytes(hash[..8].try_into().unwrap())));
+    }
+
+    write_output(
+        accounts,
+        1,
+        &encode_position(value.map(|value| value as usize)),
+    )
+}
+
+/// Maximum number of accounts deduplicated by sorting, which sorts their keys
+/// in a stack buffer.
+const MAX_DEDUP_ACCOUNTS: usize = 64;
+
+/// Fails if any two of the first `count` accounts have the same key, comparing
+/// every pair (`strategy` 0) or sorting the keys and comparing neighbors (any
+/// other strategy).
+#[inline(always)]
+pub fn process_dedup_accounts(
+    accounts: &[NoStdAccountInfo],
+    count: u8,
+    strategy: u8,
+) -> ProgramResult {
+    let accounts = accounts
+        .get(..count as usize)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let duplicate = if strategy == 0 {
+        accounts.iter().enumerate().any(|(i, account)| {
+            accounts[i + 1..]
+                .iter()
+                .any(|other| other.key() == account.key())
+        })
+    } else {
+        let default = Pubkey::default();
+        let mut keys = [&default; MAX_DEDUP_ACCOUNTS];
+        let keys = keys
+            .get_mut(..accounts.len())
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        for (key, account) in keys.iter_mut().zip(accounts) {
+            *key = account.key();
+        }
+        keys.sort_unstable();
+        keys.windows(2).any(|pair| pair[0] == pair[1])
+    };
+
+    if duplicate {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Consumes the first `fixed` accounts, then checks that every remaining
+/// account is owned by the program, as Anchor programs iterate
+/// `remaining_accounts`.
+#[inline(always)]
+pub fn process_remaining(accounts: &[NoStdAccountInfo], fixed: u8) -> ProgramResult {
+    if accounts.len() < fixed as usize {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let (fixed_accounts, remaining_accounts) = accounts.split_at(fixed as usize);
+
+    for account in fixed_accounts {
+        black_box(account.key());
+    }
+    for account in remaining_accounts {
+        if account.owner() != &crate::ID {
+            return Err(ProgramError::IllegalOwner);
+        }
+    }
+    Ok(())
+}
+
+/// Program ids matched by the `MatchProgramId` benchmark.
+const KNOWN_PROGRAM_IDS: [Pubkey; 8] = [
+    solana_program::pubkey!("11111111111111111111111111111111"),
+    solana_program::pubkey!("ComputeBudget111111111111111111111111111111"),
+    solana_program::pubkey!("AddressLookupTab1e1111111111111111111111111"),
+    solana_program::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+    TOKEN_2022_PROGRAM_ID,
+    solana_program::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"),
+    TOKEN_METADATA_PROGRAM_ID,
+    solana_program::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"),
+];
+
+/// Compares two byte slices of the same length with the `sol_memcmp` syscall.
+#[inline(always)]
+fn memcmp_eq(a: &[u8], b: &[u8]) -> bool {
+    #[cfg(target_os = "solana")]
+    let result = {
+        let mut result = 0i32;
+        unsafe {
+            solana_program::syscalls::sol_memcmp_(
+                a.as_ptr(),
+                b.as_ptr(),
+                a.len() as u64,
+                &mut result as *mut i32,
+            );
+        }
+        result
+    };
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    let result = (a != b) as i32;
+
+    result == 0
+}
+
+/// Finds the known program id matching the key of the first account and
+/// writes its position to the result account, comparing keys with `==`
+/// (`strategy` 0), as byte slices (`strategy` 1) or with the `sol_memcmp`
+/// syscall (any other strategy).
+#[inline(always)]
+pub fn process_match_program_id(accounts: &[NoStdAccountInfo], strategy: u8) -> ProgramResult {
+    let key = accounts
+        .first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?
+        .key();
+
+    let position = match strategy {
+        0 => KNOWN_PROGRAM_IDS.iter().position(|id| key == id),
+        1 => KNOWN_PROGRAM_IDS
+            .iter()
+            .position(|id| key.as_ref() == id.as_ref()),
+        _ => KNOWN_PROGRAM_IDS
+            .iter()
+            .position(|id| memcmp_eq(key.as_ref(), id.as_ref())),
+    };
+    write_output(accounts, 1, &encode_position(position))
+}
+
+/// BPF upgradeable loader id, owner of `ProgramData` accounts.
+const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    solana_program::pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// Discriminant of `UpgradeableLoaderState::ProgramData`, a bincode `u32`.
+const PROGRAM_DATA_TAG: u32 = 3;
+
+/// Offset of the optional upgrade authority in `ProgramData` accounts, after
+/// the discriminant and the deployment slot.
+const UPGRADE_AUTHORITY_OFFSET: usize = 4 + 8;
+
+/// Reads the upgrade authority of the `ProgramData` account (first account)
+/// and writes it to the result account.
+#[inline(always)]
+pub fn process_parse_program_data(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let [program_data_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if program_data_account.owner() != &BPF_LOADER_UPGRADEABLE_ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = program_data_account.try_borrow_data()?;
+    // The authority option is followed by the program, so the header is
+    // always 45 bytes long, even without an authority.
+    let header = data
+        .get(..UPGRADE_AUTHORITY_OFFSET + 1 + 32)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    if header[..4] != PROGRAM_DATA_TAG.to_le_bytes() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let authority = match header[UPGRADE_AUTHORITY_OFFSET] {
+        0 => None,
+        1 => Some(header[UPGRADE_AUTHORITY_OFFSET + 1..].try_into().unwrap()),
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    write_output(accounts, 1, &encode_hash(authority))
+}
+
+/// Feature program id, owner of runtime feature accounts.
+const FEATURE_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("Feature111111111111111111111111111111111111");
+
+/// Parses the runtime feature account (first account), a bincode
+/// `Option<u64>` activation slot, and writes the slot to the result account
+/// if the feature is active.
+#[inline(always)]
+pub fn process_check_feature(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let [feature_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if feature_account.owner() != &FEATURE_PROGRAM_ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = feature_account.try_borrow_data()?;
+    let activated_at = match data.first() {
+        Some(0) => None,
+        Some(1) => {
+            let slot = data.get(1..9).ok_or(ProgramError::InvalidAccountData)?;
+            Some(u64::from_le_bytes(slot.try_into().unwrap()))
+        }
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    write_output(
+        accounts,
+        1,
+        &encode_position(activated_at.map(|slot| slot as usize)),
+    )
+}
+
+/// Size of the fields read by `ReadAlignment`: a `u128` followed by a `u64`.
+const ALIGNMENT_FIELDS_SIZE: usize = 16 + 8;
+
+/// Reads a `u128` field at `offset` within the data of the first account and
+/// the `u64` field following it, with `read_unaligned` (`strategy` 0), by
+/// copying their bytes (`strategy` 1) or by casting the data pointer (any
+/// other strategy), and writes the low 64 bits of their sum to the result
+/// account. Casting fails unless the `u128` field is aligned.
+#[inline(always)]
+pub fn process_read_alignment(
+    accounts: &[NoStdAccountInfo],
+    offset: u8,
+    strategy: u8,
+) -> ProgramResult {
+    let [account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let data = account.try_borrow_data()?;
+    let offset = offset as usize;
+    let fields = data
+        .get(offset..offset + ALIGNMENT_FIELDS_SIZE)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let ptr = fields.as_ptr();
+
+    let (large, small) = match strategy {
+        // SAFETY: `fields` holds both values.
+        0 => unsafe {
+            (
+                core::ptr::read_unaligned(ptr as *const u128),
+                core::ptr::read_unaligned(ptr.add(16) as *const u64),
+            )
+        },
+        1 => (
+            u128::from_le_bytes(fields[..16].try_into().unwrap()),
+            u64::from_le_bytes(fields[16..].try_into().unwrap()),
+        ),
+        _ => {
+            if ptr.align_offset(core::mem::align_of::<u128>()) != 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            // SAFETY: `fields` holds both values, and the `u64` field is
+            // aligned as it follows the aligned `u128` field.
+            unsafe { (*(ptr as *const u128), *(ptr.add(16) as *const u64)) }
+        }
+    };
+    write_output(
+        accounts,
+        1,
+        &encode_position(Some((large as u64).wrapping_add(small) as usize)),
+    )
+}
+
+#[cfg(target_os = "solana")]
+extern "C" {
+    /// Not declared by `solana-program` 1.18.
+    fn sol_get_epoch_stake(vote_address: *const u8) -> u64;
+}
+
+/// Reads the total stake of the current epoch (`vote_account` 0) or the stake
+/// delegated to the vote account (first account) through the
+/// `sol_get_epoch_stake` syscall.
+#[inline(always)]
+pub fn process_get_epoch_stake(accounts: &[NoStdAccountInfo], vote_account: u8) -> ProgramResult {
+    // A null vote address requests the total stake.
+    let vote_address = if vote_account == 0 {
+        core::ptr::null()
+    } else {
+        accounts
+            .first()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?
+            .key()
+            .as_ref()
+            .as_ptr()
+    };
+
+    #[cfg(target_os = "solana")]
+    let stake = unsafe { sol_get_epoch_stake(vote_address) };
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    let stake = {
+        black_box(vote_address);
+        0
+    };
+
+    black_box(stake);
+    Ok(())
+}
+
+/// Bytes of every seed of `CpiSignedSeeds`, whose seeds only differ by length.
+static SIGNER_SEED: [u8; MAX_SEED_LEN] = [0x5e; MAX_SEED_LEN];
+
+/// Transfers lamports from the program derived address of `num_seeds` seeds
+/// of `seed_len` bytes and the `bump` seed (first account) through CPI,
+/// signing with its seeds.
+#[inline(always)]
+pub fn process_cpi_signed_seeds(
+    accounts: &[NoStdAccountInfo],
+    num_seeds: u8,
+    seed_len: u8,
+    bump: u8,
+) -> ProgramResult {
+    let [from, to, _remaining @ ..] = accounts else {
+        return Err(ProgramError::InvalidArgument);
+    };
+    let (num_seeds, seed_len) = (num_seeds as usize, seed_len as usize);
+    // The bump seed is one of the `MAX_SEEDS`.
+    if num_seeds >= MAX_SEEDS || seed_len > MAX_SEED_LEN {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let bump = [bump];
+    let seeds: [&[u8]; MAX_SEEDS] = core::array::from_fn(|index| {
+        if index < num_seeds {
+            &SIGNER_SEED[..seed_len]
+        } else {
+            &bump[..]
+        }
+    });
+
+    unsafe { transfer_signed_unchecked(from, to, 1_000_000_000, &[&seeds[..=num_seeds]]) }
+}
+
+/// Byte written to the data of the `OwnerRoundTrip` account while the program
+/// owns it.
+const ROUND_TRIP_BYTE: u8 = 0xab;
+
+/// Assigns the system-owned first account to the program through CPI, writes
+/// its data, then zeroes it and assigns the account back to the system
+/// program, which the runtime only allows for zeroed data.
+#[inline(always)]
+pub fn process_owner_round_trip(accounts: &[NoStdAccountInfo]) -> ProgramResult {
+    let [account, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if account.owner() != &system_program::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    unsafe { assign_unchecked(account, &crate::ID)? };
+    // The owner is updated by the runtime when the CPI returns.
+    if account.owner() != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    {
+        let mut data = account.try_borrow_mut_data()?;
+        data.fill(ROUND_TRIP_BYTE);
+        // Keeps the write from being elided by the zeroing.
+        black_box(&mut *data);
+        data.fill(0);
+    }
+    // `NoStdAccountInfo` has no owner setter, so the owner is written in place
+    // in the input buffer, as the runtime reads it back. The pointer of the
+    // CPI account info is derived from the raw pointer to the account, not
+    // from the shared reference returned by `owner()`.
+    let owner = account.to_info_c().owner as *mut Pubkey;
+    unsafe { core::ptr::write(owner, system_program::ID) };
+
+    Ok(())
+}
+
+/// Zeroes a byte slice with the `sol_memset` syscall.
+#[inline(always)]
+fn memset_zero(data: &mut [u8]) {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        solana_program::syscalls::sol_memset_(data.as_mut_ptr(), 0, data.len() as u64);
+    }
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    {
+        data.fill(0);
+    }
+}
+
+/// Zeroes the first `len` bytes of the data of the first account with a loop
+/// of volatile `u64` writes (`strategy` 0), `fill` (`strategy` 1) or the
+/// `sol_memset` syscall (any other strategy).
+#[inline(always)]
+pub fn process_zero_data(accounts: &[NoStdAccountInfo], len: u32, strategy: u8) -> ProgramResult {
+    let [account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let mut data = account.try_borrow_mut_data()?;
+    let data = data
+        .get_mut(..len as usize)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    match strategy {
+        0 => {
+            // SAFETY: every bit pattern is a valid `u64`.
+            let (head, words, tail) = unsafe { data.align_to_mut::<u64>() };
+            // Volatile writes keep the loops from being turned into a
+            // `memset` call.
+            for word in words {
+                unsafe { core::ptr::write_volatile(word, 0) };
+            }
+            for byte in head.iter_mut().chain(tail) {
+                unsafe { core::ptr::write_volatile(byte, 0) };
+            }
+        }
+        1 => data.fill(0),
+        _ => memset_zero(data),
+    }
+
+    Ok(())
+}
+
+/// Copies a byte slice to another of the same length with the `sol_memcpy`
+/// syscall.
+#[inline(always)]
+fn memcpy(destination: &mut [u8], source: &[u8]) {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        solana_program::syscalls::sol_memcpy_(
+            destination.as_mut_ptr(),
+            source.as_ptr(),
+            source.len() as u64,
+        );
+    }
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    {
+        destination.copy_from_slice(source);
+    }
+}
+
+/// Copies the first `len` bytes of the data of the first account to the data
+/// of the second account with a loop of volatile byte writes (`strategy` 0),
+/// `copy_from_slice` (`strategy` 1) or the `sol_memcpy` syscall (any other
+/// strategy). Both accounts are borrowed at once, so passing the same account
+/// twice fails.
+#[inline(always)]
+pub fn process_copy_account_data(
+    accounts: &[NoStdAccountInfo],
+    len: u32,
+    strategy: u8,
+) -> ProgramResult {
+    let [source_account, destination_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let source_data = source_account.try_borrow_data()?;
+    let mut destination_data = destination_account.try_borrow_mut_data()?;
+    let len = len as usize;
+    let source = source_data
+        .get(..len)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let destination = destination_data
+        .get_mut(..len)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    match strategy {
+        // Volatile writes keep the loop from being turned into a `memcpy`
+        // call.
+        0 => {
+            for (to, from) in destination.iter_mut().zip(source) {
+                unsafe { core::ptr::write_volatile(to, *from) };
+            }
+        }
+        1 => destination.copy_from_slice(source),
+        _ => memcpy(destination, source),
+    }
+
+    Ok(())
 }
 
 // --- Unit Tests for Pure Logic ---
@@ -225,4 +1265,30 @@ mod tests {
             Err(ProgramError::AccountDataTooSmall)
         )); // Len says 1, but data too short
     }
+
+    #[test]
+    fn test_process_slot_hashes_bytes_zerocopy_matches_bytemuck() {
+        let mock_entries = [
+            (100, [1u8; 32]),
+            (98, [2u8; 32]),
+            (95, [3u8; 32]),
+            (90, [4u8; 32]),
+            (85, [5u8; 32]),
+        ];
+        let data = create_mock_slot_hashes_data(&mock_entries);
+
+        for target in [100, 98, 95, 90, 85, 99, 80] {
+            assert_eq!(
+                process_slot_hashes_bytes_zerocopy(&data, SlotHashOp::GetPosition(target)),
+                process_slot_hashes_bytes(&data, SlotHashOp::GetPosition(target))
+            );
+        }
+
+        // Len says 1, but data too short
+        let short_data = create_mock_slot_hashes_data(&[(100, [1u8; 32])]);
+        assert!(matches!(
+            process_slot_hashes_bytes_zerocopy(&short_data[0..45], SlotHashOp::IsEmpty),
+            Err(ProgramError::AccountDataTooSmall)
+        ));
+    }
 }
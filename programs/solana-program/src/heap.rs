@@ -0,0 +1,101 @@
+//! Heap usage instrumentation, enabled by the `heap-stats` feature.
+//!
+//! Replaces the default bump allocator with one that also tracks the number
+//! of live bytes, their high-water mark and the number of allocations, and
+//! reports the high-water mark and the allocation count as the instruction
+//! return data (two little-endian `u64`s).
+
+use core::alloc::{GlobalAlloc, Layout};
+use solana_account_info::AccountInfo;
+use solana_program::program::set_return_data;
+use solana_program_entrypoint::{BumpAllocator, HEAP_LENGTH, HEAP_START_ADDRESS};
+use solana_program_error::ProgramResult;
+use solana_pubkey::Pubkey;
+
+/// Usage counters, stored at the start of the (zero-initialized) heap since
+/// programs cannot have writable statics.
+#[repr(C)]
+struct HeapStats {
+    live: usize,
+    peak: usize,
+    allocations: usize,
+}
+
+const STATS_LEN: usize = core::mem::size_of::<HeapStats>();
+
+/// Bump allocator recording the heap high-water mark and the number of
+/// allocations.
+pub struct TrackingAllocator {
+    inner: BumpAllocator,
+}
+
+impl TrackingAllocator {
+    pub const fn new() -> Self {
+        Self {
+            inner: BumpAllocator {
+                start: HEAP_START_ADDRESS as usize + STATS_LEN,
+                len: HEAP_LENGTH - STATS_LEN,
+            },
+        }
+    }
+
+    fn stats() -> *mut HeapStats {
+        HEAP_START_ADDRESS as usize as *mut HeapStats
+    }
+
+    /// Returns the maximum number of bytes allocated at once so far.
+    pub fn peak() -> usize {
+        unsafe { (*Self::stats()).peak }
+    }
+
+    /// Returns the number of allocations so far, including those freed since.
+    pub fn allocations() -> usize {
+        unsafe { (*Self::stats()).allocations }
+    }
+}
+
+impl Default for TrackingAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            let stats = &mut *Self::stats();
+            stats.live += layout.size();
+            stats.peak = stats.peak.max(stats.live);
+            stats.allocations += 1;
+        }
+        ptr
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        (*Self::stats()).live -= layout.size();
+    }
+}
+
+#[cfg(target_os = "solana")]
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator::new();
+
+/// Processes the instruction and sets its return data to the heap
+/// high-water mark followed by the number of allocations, both including the
+/// allocations of the entrypoint itself.
+pub fn process_instruction_with_heap_stats(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let result = crate::entrypoint::process_instruction(program_id, accounts, instruction_data);
+    let mut stats = [0; 16];
+    stats[..8].copy_from_slice(&(TrackingAllocator::peak() as u64).to_le_bytes());
+    stats[8..].copy_from_slice(&(TrackingAllocator::allocations() as u64).to_le_bytes());
+    set_return_data(&stats);
+    result
+}
@@ -1,5 +1,15 @@
 pub mod entrypoint;
-pub mod instruction;
+#[cfg(feature = "heap-stats")]
+pub mod heap;
+pub mod metadata;
+pub mod output;
 pub mod processor;
+pub mod state;
+pub mod token_2022;
+
+pub use eisodos_base64 as base64;
+pub use eisodos_fixed_point as fixed_point;
+pub use eisodos_instructions as instruction;
+pub use eisodos_sorted_sysvar as sorted_sysvar;
 
 solana_pubkey::declare_id!("SoLanaProgram111111111111111111111111111111");
@@ -0,0 +1,60 @@
+//! Token Metadata account layout, as read by the `ParseMetadata` benchmark.
+//!
+//! Metadata accounts are borsh-encoded, with variable-length strings and
+//! creators, and deserialized into owned types.
+
+use borsh::BorshDeserialize;
+use solana_pubkey::Pubkey;
+
+/// Token Metadata program id.
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Account key of `MetadataV1` accounts.
+pub const KEY_METADATA_V1: u8 = 4;
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Creator {
+    pub address: [u8; 32],
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Data {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+}
+
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: [u8; 32],
+}
+
+/// Uses of a metadata account: the use method and the remaining and total
+/// number of uses.
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Uses {
+    pub use_method: u8,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+/// `MetadataV1` fields up to `uses`; later fields are not read.
+#[derive(BorshDeserialize, Clone, Debug)]
+pub struct Metadata {
+    pub key: u8,
+    pub update_authority: [u8; 32],
+    pub mint: [u8; 32],
+    pub data: Data,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+}
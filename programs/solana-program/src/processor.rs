@@ -1,18 +1,43 @@
-use core::cmp::Ordering;
+use crate::base64;
+use crate::fixed_point::{accrue_fixed, accrue_q64};
+use crate::metadata::{Metadata, KEY_METADATA_V1, TOKEN_METADATA_PROGRAM_ID};
+use crate::output::{encode_hash, encode_position, encode_value, write_output};
+use crate::sorted_sysvar::{
+    SlotHashes, StakeHistory, KEY_SIZE, LEN_PREFIX_SIZE, SLOT_HASHES_ENTRY_SIZE,
+};
+use crate::state::{Event, MerkleTree, State, BUFFER_SIZE, MAX_DEPTH, MAX_SIGNERS};
+use crate::token_2022::{Extensions, TOKEN_2022_PROGRAM_ID};
+use borsh::{BorshDeserialize, BorshSerialize};
+use core::hint::black_box;
 use solana_account_info::AccountInfo;
-use solana_cpi::invoke;
+use solana_cpi::{invoke, invoke_signed};
+use solana_keccak_hasher as keccak;
+use solana_program::clock::Clock;
+use solana_program::epoch_stake::{get_epoch_stake_for_vote_account, get_epoch_total_stake};
+use solana_program::hash::hashv;
+use solana_program::log::sol_log_data;
 use solana_program::msg;
+use solana_program::rent::Rent;
 use solana_program::sysvar::Sysvar;
 use solana_program::sysvar::{self, slot_hashes as solana_slot_hashes};
 use solana_program_error::{ProgramError, ProgramResult};
+use solana_pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
 
 #[inline(always)]
 pub fn process_ping() -> ProgramResult {
     Ok(())
 }
 
+#[inline(always)]
+pub fn process_empty_call() -> ProgramResult {
+    Ok(())
+}
+
 #[inline(always)]
 pub fn process_log() -> ProgramResult {
+    // `msg!` formats the message with `format!`.
+    #[cfg(feature = "fmt-log")]
+    msg!("Instruction: Log ({})", black_box(1u64));
     Ok(())
 }
 
@@ -25,6 +50,47 @@ pub fn process_account(accounts: &[AccountInfo], expected: u64) -> ProgramResult
     }
 }
 
+/// Reads every header field of each account, isolating the accessor cost.
+///
+/// `lamports()` and `data_len()` go through the `RefCell` borrow flags.
+#[inline(always)]
+pub fn process_account_fields(accounts: &[AccountInfo]) -> ProgramResult {
+    for account in accounts {
+        black_box(account.key);
+        black_box(account.owner);
+        black_box(account.lamports());
+        black_box(account.data_len());
+        black_box(account.is_signer);
+        black_box(account.is_writable);
+    }
+    Ok(())
+}
+
+/// Reads the first bytes of each account data through the checked borrow.
+#[inline(always)]
+pub fn process_read_data_checked(accounts: &[AccountInfo]) -> ProgramResult {
+    for account in accounts {
+        let data = account.try_borrow_data()?;
+        black_box(data.first());
+    }
+    Ok(())
+}
+
+/// Reads the first bytes of each account data straight through the `RefCell`
+/// pointer, skipping the borrow flag check.
+///
+/// # Safety
+///
+/// The caller must guarantee that no account data is mutably borrowed.
+#[inline(always)]
+pub unsafe fn process_read_data_unchecked(accounts: &[AccountInfo]) -> ProgramResult {
+    for account in accounts {
+        let data: &[u8] = &**account.data.as_ptr();
+        black_box(data.first());
+    }
+    Ok(())
+}
+
 #[inline(always)]
 pub fn process_create_account(accounts: &[AccountInfo]) -> ProgramResult {
     invoke(
@@ -53,41 +119,6 @@ pub fn process_transfer(accounts: &[AccountInfo]) -> ProgramResult {
 
 // Solana SDK SlotHashes Sysvar Processors (Using Manual Access again to pass
 // tests)
-const SDK_LEN_PREFIX_SIZE: usize = 8; // Assuming u64 length based on structure
-const SDK_ENTRY_SIZE: usize = 40; // u64 slot + 32 byte hash
-
-// Manual midpoint binary search on raw SlotHashes data
-fn manual_binary_search(data: &[u8], target_slot: u64) -> Result<usize, usize> {
-    if data.len() < SDK_LEN_PREFIX_SIZE {
-        return Err(0);
-    }
-    let num_entries = u64::from_le_bytes(data[0..SDK_LEN_PREFIX_SIZE].try_into().unwrap());
-    let num_entries = num_entries as usize;
-
-    let entries_data_start = SDK_LEN_PREFIX_SIZE;
-    let mut low = 0;
-    let mut high = num_entries;
-
-    while low < high {
-        let mid = low + (high - low) / 2;
-        let entry_offset = entries_data_start + mid * SDK_ENTRY_SIZE;
-
-        if entry_offset + 8 > data.len() {
-            return Err(low);
-        }
-
-        let current_slot =
-            u64::from_le_bytes(data[entry_offset..entry_offset + 8].try_into().unwrap());
-
-        match current_slot.cmp(&target_slot) {
-            Ordering::Equal => return Ok(mid), // Return INDEX if found
-            Ordering::Less => high = mid,
-            Ordering::Greater => low = mid + 1,
-        }
-    }
-
-    Err(low) // Return insertion point if not found
-}
 
 #[inline(always)]
 pub fn process_slot_hashes_get_entry(accounts: &[AccountInfo]) -> ProgramResult {
@@ -96,10 +127,9 @@ pub fn process_slot_hashes_get_entry(accounts: &[AccountInfo]) -> ProgramResult
         return Err(ProgramError::IncorrectProgramId);
     }
     let data = slot_hashes_account.try_borrow_data()?;
-    if data.len() < SDK_LEN_PREFIX_SIZE {
-        return Err(ProgramError::AccountDataTooSmall);
-    }
-    let num_entries = u64::from_le_bytes(data[0..SDK_LEN_PREFIX_SIZE].try_into().unwrap());
+    let num_entries = SlotHashes::new(&data)
+        .num_entries()
+        .ok_or(ProgramError::AccountDataTooSmall)?;
     let _is_empty = num_entries == 0;
     Ok(())
 }
@@ -111,8 +141,12 @@ pub fn process_slot_hashes_get_hash_interpolated(accounts: &[AccountInfo]) -> Pr
         return Err(ProgramError::IncorrectProgramId);
     }
     let data = slot_hashes_account.try_borrow_data()?;
-    let _search_result = manual_binary_search(&data, 0);
-    Ok(())
+    let slot_hashes = SlotHashes::new(&data);
+    let hash = slot_hashes
+        .binary_search(0)
+        .ok()
+        .and_then(|position| slot_hashes.value(position)?.try_into().ok());
+    write_output(accounts, 1, &encode_hash(hash))
 }
 
 #[inline(always)]
@@ -122,8 +156,8 @@ pub fn process_slot_hashes_position_interpolated(accounts: &[AccountInfo]) -> Pr
         return Err(ProgramError::IncorrectProgramId);
     }
     let data = slot_hashes_account.try_borrow_data()?;
-    let _search_result = manual_binary_search(&data, 0);
-    Ok(())
+    let position = SlotHashes::new(&data).binary_search(0).ok();
+    write_output(accounts, 1, &encode_position(position))
 }
 
 #[inline(always)]
@@ -136,15 +170,905 @@ pub fn process_slot_hashes_position_midpoint(accounts: &[AccountInfo]) -> Progra
     process_slot_hashes_position_interpolated(accounts)
 }
 
+/// Reads the 8 bytes at `offset` of the SlotHashes sysvar from the sysvar
+/// cache.
+#[inline(always)]
+fn get_slot_hashes_bytes(offset: usize) -> Result<[u8; 8], ProgramError> {
+    #[cfg(target_os = "solana")]
+    let bytes = {
+        let mut bytes = [0u8; 8];
+        let result = unsafe {
+            solana_define_syscall::definitions::sol_get_sysvar(
+                sysvar::slot_hashes::ID.as_ref().as_ptr(),
+                bytes.as_mut_ptr(),
+                offset as u64,
+                bytes.len() as u64,
+            )
+        };
+        if result != 0 {
+            return Err(ProgramError::from(result));
+        }
+        bytes
+    };
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    let bytes = {
+        black_box(offset);
+        [0u8; 8]
+    };
+
+    Ok(bytes)
+}
+
+/// Looks up `slot` in the SlotHashes sysvar fetched from the sysvar cache with
+/// `sol_get_sysvar` instead of being passed as an account, either entirely
+/// (`strategy` 0) or one probed slot at a time (any other strategy), and
+/// writes its position to the result account (first account).
+#[inline(always)]
+pub fn process_slot_hashes_position_sysvar(
+    accounts: &[AccountInfo],
+    slot: u64,
+    strategy: u8,
+) -> ProgramResult {
+    let position = if strategy == 0 {
+        solana_slot_hashes::PodSlotHashes::fetch()?.position(&slot)?
+    } else {
+        let num_entries = u64::from_le_bytes(get_slot_hashes_bytes(0)?) as usize;
+        let mut low = 0;
+        let mut high = num_entries;
+        let mut position = None;
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let offset = LEN_PREFIX_SIZE + mid * SLOT_HASHES_ENTRY_SIZE;
+            let current_slot = u64::from_le_bytes(get_slot_hashes_bytes(offset)?);
+            match current_slot.cmp(&slot) {
+                core::cmp::Ordering::Equal => {
+                    position = Some(mid);
+                    break;
+                }
+                core::cmp::Ordering::Less => high = mid,
+                core::cmp::Ordering::Greater => low = mid + 1,
+            }
+        }
+        position
+    };
+    write_output(accounts, 0, &encode_position(position))
+}
+
+/// Looks up `epoch` in the StakeHistory sysvar account (first account) and
+/// writes its position to the result account.
+#[inline(always)]
+pub fn process_stake_history_position(accounts: &[AccountInfo], epoch: u64) -> ProgramResult {
+    let stake_history_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if stake_history_account.key != &sysvar::stake_history::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = stake_history_account.try_borrow_data()?;
+    let position = StakeHistory::new(&data).binary_search(epoch).ok();
+    write_output(accounts, 1, &encode_position(position))
+}
+
+/// Performs the canonical owner, signer, writable and rent-exemption checks
+/// on the first `count` accounts.
+#[inline(always)]
+pub fn process_validate_accounts(accounts: &[AccountInfo], count: u8) -> ProgramResult {
+    let accounts = accounts
+        .get(..count as usize)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let rent = Rent::get()?;
+
+    for account in accounts {
+        if account.owner != &crate::ID {
+            return Err(ProgramError::IllegalOwner);
+        }
+        if !account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if !account.is_writable {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !rent.is_exempt(account.lamports(), account.data_len()) {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+    }
+    Ok(())
+}
+
+/// Loads `Rent` from the sysvar account (first account) and checks that every
+/// remaining account is rent exempt.
+#[inline(always)]
+pub fn process_check_rent_exempt(accounts: &[AccountInfo]) -> ProgramResult {
+    let [rent_account, accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let rent = Rent::from_account_info(rent_account)?;
+
+    for account in accounts {
+        if !rent.is_exempt(account.lamports(), account.data_len()) {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+    }
+    Ok(())
+}
+
+/// Deserializes the program state, increments the counter, stamps the current
+/// unix timestamp and serializes it back.
+#[inline(always)]
+pub fn process_read_modify_write(accounts: &[AccountInfo]) -> ProgramResult {
+    let [state_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if state_account.owner != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut data = state_account.try_borrow_mut_data()?;
+    let bytes = data
+        .get_mut(..State::LEN)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let mut state = State::try_from_slice(bytes).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    state.counter = state
+        .counter
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    state.timestamp = Clock::get()?.unix_timestamp;
+
+    state
+        .serialize(&mut &mut bytes[..])
+        .map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Borsh-serializes an `Event` and emits it with `sol_log_data`.
+#[inline(always)]
+pub fn process_emit_event() -> ProgramResult {
+    let mut event = [0u8; Event::LEN];
+    Event::sample()
+        .serialize(&mut &mut event[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    sol_log_data(&[&event]);
+    Ok(())
+}
+
+/// Same as `process_emit_event`, but base64-encodes the payload in the
+/// program before emitting it.
+#[inline(always)]
+pub fn process_emit_event_base64() -> ProgramResult {
+    let mut event = [0u8; Event::LEN];
+    Event::sample()
+        .serialize(&mut &mut event[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let mut encoded = [0u8; Event::BASE64_LEN];
+    let len = base64::encode(&event, &mut encoded);
+    sol_log_data(&[&encoded[..len]]);
+    Ok(())
+}
+
+/// Walks the Token-2022 extensions of a token account (first account) and
+/// writes its withheld transfer fee to the result account.
+#[inline(always)]
+pub fn process_parse_token_2022_extensions(accounts: &[AccountInfo]) -> ProgramResult {
+    let [token_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if token_account.owner != &TOKEN_2022_PROGRAM_ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let extensions = Extensions::parse(&token_account.try_borrow_data()?)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    black_box(extensions.count);
+    black_box(extensions.memo_required);
+
+    write_output(accounts, 1, &encode_value(extensions.withheld_amount))
+}
+
+/// Borsh-deserializes the Token Metadata account (first account) and writes
+/// its collection to the result account.
+#[inline(always)]
+pub fn process_parse_metadata(accounts: &[AccountInfo]) -> ProgramResult {
+    let [metadata_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if metadata_account.owner != &TOKEN_METADATA_PROGRAM_ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = metadata_account.try_borrow_data()?;
+    // Metadata accounts are zero-padded, so trailing bytes are not rejected.
+    let metadata = Metadata::deserialize(&mut &data[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if metadata.key != KEY_METADATA_V1 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    black_box(&metadata);
+
+    let collection = metadata.collection.as_ref().map(|collection| &collection.key);
+    write_output(accounts, 1, &encode_hash(collection))
+}
+
+/// Checks that at least `m` of the remaining accounts are signers from the
+/// first `n` keys of the signer set stored in the multisig account (first
+/// account), matching each stored key at most once.
+#[inline(always)]
+pub fn process_check_multisig(accounts: &[AccountInfo], m: u8, n: u8) -> ProgramResult {
+    let [multisig_account, signers @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if m == 0 || m > n || n as usize > MAX_SIGNERS {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if multisig_account.owner != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = multisig_account.try_borrow_data()?;
+    let signer_set = data
+        .get(..n as usize * 32)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    let mut matched = [false; MAX_SIGNERS];
+    let mut num_signers = 0u8;
+    for signer in signers {
+        for (position, key) in signer_set.chunks_exact(32).enumerate() {
+            if key == signer.key.as_ref() && !matched[position] {
+                if !signer.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+                matched[position] = true;
+                num_signers += 1;
+            }
+        }
+    }
+
+    if num_signers < m {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+/// Hashes a pair of merkle nodes with `hashv` (the sha256 syscall).
+#[inline(always)]
+fn hash_nodes(left: &[u8], right: &[u8]) -> [u8; 32] {
+    hashv(&[left, right]).to_bytes()
+}
+
+/// Hashes a sorted pair of merkle nodes.
+#[inline(always)]
+fn hash_pair(a: &[u8], b: &[u8]) -> [u8; 32] {
+    if a <= b {
+        hash_nodes(a, b)
+    } else {
+        hash_nodes(b, a)
+    }
+}
+
+/// Verifies that the leaf (first 32 bytes of the proof account, second
+/// account) hashes up with the `depth` proof nodes following it to the root
+/// stored in the tree account (first account). Pairs are sorted before
+/// hashing, so the proof needs no path bits.
+#[inline(always)]
+pub fn process_verify_merkle_proof(accounts: &[AccountInfo], depth: u8) -> ProgramResult {
+    let [tree_account, proof_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if tree_account.owner != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let tree = tree_account.try_borrow_data()?;
+    let root = tree.get(..32).ok_or(ProgramError::AccountDataTooSmall)?;
+    let proof_data = proof_account.try_borrow_data()?;
+    let proof = proof_data
+        .get(..32 * (depth as usize + 1))
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    let (leaf, siblings) = proof.split_at(32);
+    let mut node = [0u8; 32];
+    node.copy_from_slice(leaf);
+    for sibling in siblings.chunks_exact(32) {
+        node = hash_pair(&node, sibling);
+    }
+
+    if node.as_slice() != root {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Appends `leaf` to the concurrent merkle tree stored in the tree account
+/// (first account): hashes it up to the new root, updating the frontier, and
+/// records the root and path in the next changelog buffer entry.
+#[inline(always)]
+pub fn process_append_leaf(accounts: &[AccountInfo], leaf: [u8; 32]) -> ProgramResult {
+    let [tree_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if tree_account.owner != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut data = tree_account.try_borrow_mut_data()?;
+    let data = data
+        .get_mut(..MerkleTree::LEN)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let (header, change_logs) = data.split_at_mut(MerkleTree::CHANGE_LOGS_OFFSET);
+
+    let read_u64 =
+        |offset: usize| u64::from_le_bytes(header[offset..offset + 8].try_into().unwrap());
+    let index = read_u64(MerkleTree::SEQUENCE_NUMBER_OFFSET);
+    if index >= 1 << MAX_DEPTH {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let active_index = (read_u64(MerkleTree::ACTIVE_INDEX_OFFSET) as usize + 1) % BUFFER_SIZE;
+    let change_log =
+        &mut change_logs[active_index * MerkleTree::CHANGE_LOG_LEN..][..MerkleTree::CHANGE_LOG_LEN];
+
+    let mut node = leaf;
+    let mut empty = [0u8; 32];
+    for level in 0..MAX_DEPTH {
+        change_log[32 * (level + 1)..32 * (level + 2)].copy_from_slice(&node);
+        let offset = MerkleTree::FRONTIER_OFFSET + 32 * level;
+        let sibling = &mut header[offset..offset + 32];
+        if (index >> level) & 1 == 1 {
+            node = hash_nodes(sibling, &node);
+        } else {
+            sibling.copy_from_slice(&node);
+            node = hash_nodes(&node, &empty);
+        }
+        empty = hash_nodes(&empty, &empty);
+    }
+
+    change_log[..32].copy_from_slice(&node);
+    change_log[32 * (MAX_DEPTH + 1)..][..4].copy_from_slice(&(index as u32).to_le_bytes());
+    header[MerkleTree::ACTIVE_INDEX_OFFSET..][..8]
+        .copy_from_slice(&(active_index as u64).to_le_bytes());
+    header[MerkleTree::SEQUENCE_NUMBER_OFFSET..][..8].copy_from_slice(&(index + 1).to_le_bytes());
+    Ok(())
+}
+
+/// Returns the program-owned state account (first account) of the
+/// `WriteState` benchmarks.
+#[inline(always)]
+fn write_state_account<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a AccountInfo<'info>, ProgramError> {
+    let [state_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if state_account.owner != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Ok(state_account)
+}
+
+/// Serializes the sample state into the state account with borsh.
+#[inline(always)]
+pub fn process_write_state_borsh(accounts: &[AccountInfo]) -> ProgramResult {
+    let mut data = write_state_account(accounts)?.try_borrow_mut_data()?;
+    State::sample()
+        .serialize(&mut &mut data[..])
+        .map_err(|_| ProgramError::AccountDataTooSmall)
+}
+
+/// Serializes the sample state into the state account with bincode.
+#[inline(always)]
+pub fn process_write_state_bincode(accounts: &[AccountInfo]) -> ProgramResult {
+    let mut data = write_state_account(accounts)?.try_borrow_mut_data()?;
+    bincode::serialize_into(&mut data[..], &State::sample())
+        .map_err(|_| ProgramError::AccountDataTooSmall)
+}
+
+/// Writes the sample state into the state account with hand-written
+/// little-endian writes.
+#[inline(always)]
+pub fn process_write_state_manual(accounts: &[AccountInfo]) -> ProgramResult {
+    let mut data = write_state_account(accounts)?.try_borrow_mut_data()?;
+    State::sample().write(&mut data)
+}
+
+/// Integer square root (Newton's method), rounded down.
+#[inline(always)]
+fn isqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = value / 2 + (value & 1);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Performs `iterations` rounds of 64-bit division and modulo, 128-bit
+/// division and integer square root, none of which sBPF has native
+/// instructions for.
+#[inline(always)]
+pub fn process_div_sqrt(iterations: u16) -> ProgramResult {
+    let mut acc = 0u64;
+    for i in 0..iterations as u64 {
+        let dividend = black_box(u64::MAX - i);
+        let divisor = black_box(i + 3);
+
+        acc = acc.wrapping_add(dividend / divisor);
+        acc = acc.wrapping_add(dividend % divisor);
+        let wide = dividend as u128 * dividend as u128;
+        acc = acc.wrapping_add((wide / divisor as u128) as u64);
+        acc = acc.wrapping_add(isqrt(dividend));
+    }
+    black_box(acc);
+    Ok(())
+}
+
+/// Accrues `periods` periods of interest with hand-rolled Q64.64 arithmetic.
+#[inline(always)]
+pub fn process_accrue_interest_q64(periods: u8) -> ProgramResult {
+    black_box(accrue_q64(periods));
+    Ok(())
+}
+
+/// Accrues `periods` periods of interest with the `fixed` crate.
+#[inline(always)]
+pub fn process_accrue_interest_fixed(periods: u8) -> ProgramResult {
+    black_box(accrue_fixed(periods));
+    Ok(())
+}
+
+/// Draws `count` pseudo-random `u64` values, each the first 8 bytes of the
+/// keccak256 hash of the most recent slot hash (SlotHashes sysvar, first
+/// account) and the draw index, and writes the last one to the result
+/// account.
+#[inline(always)]
+pub fn process_pseudo_random(accounts: &[AccountInfo], count: u8) -> ProgramResult {
+    let slot_hashes_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if slot_hashes_account.key != &sysvar::slot_hashes::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = slot_hashes_account.try_borrow_data()?;
+
+    let seed_start = LEN_PREFIX_SIZE + KEY_SIZE;
+    let seed = data
+        .get(seed_start..seed_start + 32)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    let mut value = None;
+    for index in 0..count {
+        let hash = keccak::hashv(&[seed, &[index]]).to_bytes();
+        value = Some(black_box(u64::from_le_bytes(hash[..8].try_into().unwrap())));
+    }
+
+    write_output(
+        accounts,
+        1,
+        &encode_position(value.map(|value| value as usize)),
+    )
+}
+
+/// Fails if any two of the first `count` accounts have the same key, comparing
+/// every pair (`strategy` 0) or sorting the keys and comparing neighbors (any
+/// other strategy).
+#[inline(always)]
+pub fn process_dedup_accounts(accounts: &[AccountInfo], count: u8, strategy: u8) -> ProgramResult {
+    let accounts = accounts
+        .get(..count as usize)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let duplicate = if strategy == 0 {
+        accounts.iter().enumerate().any(|(i, account)| {
+            accounts[i + 1..]
+                .iter()
+                .any(|other| other.key == account.key)
+        })
+    } else {
+        let mut keys: Vec<&Pubkey> = accounts.iter().map(|account| account.key).collect();
+        keys.sort_unstable();
+        keys.windows(2).any(|pair| pair[0] == pair[1])
+    };
+
+    if duplicate {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Consumes the first `fixed` accounts, then checks that every remaining
+/// account is owned by the program, as Anchor programs iterate
+/// `remaining_accounts`.
+#[inline(always)]
+pub fn process_remaining(accounts: &[AccountInfo], fixed: u8) -> ProgramResult {
+    if accounts.len() < fixed as usize {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let (fixed_accounts, remaining_accounts) = accounts.split_at(fixed as usize);
+
+    for account in fixed_accounts {
+        black_box(account.key);
+    }
+    for account in remaining_accounts {
+        if account.owner != &crate::ID {
+            return Err(ProgramError::IllegalOwner);
+        }
+    }
+    Ok(())
+}
+
+/// Program ids matched by the `MatchProgramId` benchmark.
+const KNOWN_PROGRAM_IDS: [Pubkey; 8] = [
+    solana_pubkey::pubkey!("11111111111111111111111111111111"),
+    solana_pubkey::pubkey!("ComputeBudget111111111111111111111111111111"),
+    solana_pubkey::pubkey!("AddressLookupTab1e1111111111111111111111111"),
+    solana_pubkey::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+    TOKEN_2022_PROGRAM_ID,
+    solana_pubkey::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"),
+    TOKEN_METADATA_PROGRAM_ID,
+    solana_pubkey::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"),
+];
+
+/// Compares two byte slices of the same length with the `sol_memcmp` syscall.
+#[inline(always)]
+fn memcmp_eq(a: &[u8], b: &[u8]) -> bool {
+    #[cfg(target_os = "solana")]
+    let result = {
+        let mut result = 0i32;
+        unsafe {
+            solana_define_syscall::definitions::sol_memcmp_(
+                a.as_ptr(),
+                b.as_ptr(),
+                a.len() as u64,
+                &mut result as *mut i32,
+            );
+        }
+        result
+    };
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    let result = (a != b) as i32;
+
+    result == 0
+}
+
+/// Finds the known program id matching the key of the first account and
+/// writes its position to the result account, comparing keys with `==`
+/// (`strategy` 0), as byte slices (`strategy` 1) or with the `sol_memcmp`
+/// syscall (any other strategy).
+#[inline(always)]
+pub fn process_match_program_id(accounts: &[AccountInfo], strategy: u8) -> ProgramResult {
+    let key = accounts
+        .first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?
+        .key;
+
+    let position = match strategy {
+        0 => KNOWN_PROGRAM_IDS.iter().position(|id| key == id),
+        1 => KNOWN_PROGRAM_IDS
+            .iter()
+            .position(|id| key.as_ref() == id.as_ref()),
+        _ => KNOWN_PROGRAM_IDS
+            .iter()
+            .position(|id| memcmp_eq(key.as_ref(), id.as_ref())),
+    };
+    write_output(accounts, 1, &encode_position(position))
+}
+
+/// BPF upgradeable loader id, owner of `ProgramData` accounts.
+const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    solana_pubkey::pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// Discriminant of `UpgradeableLoaderState::ProgramData`, a bincode `u32`.
+const PROGRAM_DATA_TAG: u32 = 3;
+
+/// Offset of the optional upgrade authority in `ProgramData` accounts, after
+/// the discriminant and the deployment slot.
+const UPGRADE_AUTHORITY_OFFSET: usize = 4 + 8;
+
+/// Reads the upgrade authority of the `ProgramData` account (first account)
+/// and writes it to the result account.
+#[inline(always)]
+pub fn process_parse_program_data(accounts: &[AccountInfo]) -> ProgramResult {
+    let [program_data_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if program_data_account.owner != &BPF_LOADER_UPGRADEABLE_ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = program_data_account.try_borrow_data()?;
+    // The authority option is followed by the program, so the header is
+    // always 45 bytes long, even without an authority.
+    let header = data
+        .get(..UPGRADE_AUTHORITY_OFFSET + 1 + 32)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    if header[..4] != PROGRAM_DATA_TAG.to_le_bytes() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let authority = match header[UPGRADE_AUTHORITY_OFFSET] {
+        0 => None,
+        1 => Some(header[UPGRADE_AUTHORITY_OFFSET + 1..].try_into().unwrap()),
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    write_output(accounts, 1, &encode_hash(authority))
+}
+
+/// Feature program id, owner of runtime feature accounts.
+const FEATURE_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("Feature111111111111111111111111111111111111");
+
+/// Deserializes the runtime feature account (first account), a bincode
+/// `Option<u64>` activation slot, and writes the slot to the result account
+/// if the feature is active.
+#[inline(always)]
+pub fn process_check_feature(accounts: &[AccountInfo]) -> ProgramResult {
+    let [feature_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if feature_account.owner != &FEATURE_PROGRAM_ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = feature_account.try_borrow_data()?;
+    let activated_at: Option<u64> =
+        bincode::deserialize(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+    write_output(
+        accounts,
+        1,
+        &encode_position(activated_at.map(|slot| slot as usize)),
+    )
+}
+
+/// Size of the fields read by `ReadAlignment`: a `u128` followed by a `u64`.
+const ALIGNMENT_FIELDS_SIZE: usize = 16 + 8;
+
+/// Reads a `u128` field at `offset` within the data of the first account and
+/// the `u64` field following it, with `read_unaligned` (`strategy` 0), by
+/// copying their bytes (`strategy` 1) or by casting the data pointer (any
+/// other strategy), and writes the low 64 bits of their sum to the result
+/// account. Casting fails unless the `u128` field is aligned.
+#[inline(always)]
+pub fn process_read_alignment(accounts: &[AccountInfo], offset: u8, strategy: u8) -> ProgramResult {
+    let [account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let data = account.try_borrow_data()?;
+    let offset = offset as usize;
+    let fields = data
+        .get(offset..offset + ALIGNMENT_FIELDS_SIZE)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let ptr = fields.as_ptr();
+
+    let (large, small) = match strategy {
+        // SAFETY: `fields` holds both values.
+        0 => unsafe {
+            (
+                core::ptr::read_unaligned(ptr as *const u128),
+                core::ptr::read_unaligned(ptr.add(16) as *const u64),
+            )
+        },
+        1 => (
+            u128::from_le_bytes(fields[..16].try_into().unwrap()),
+            u64::from_le_bytes(fields[16..].try_into().unwrap()),
+        ),
+        _ => {
+            if ptr.align_offset(core::mem::align_of::<u128>()) != 0 {
+                return Err(ProgramError::InvalidArgument);
+            }
+            // SAFETY: `fields` holds both values, and the `u64` field is
+            // aligned as it follows the aligned `u128` field.
+            unsafe { (*(ptr as *const u128), *(ptr.add(16) as *const u64)) }
+        }
+    };
+    write_output(
+        accounts,
+        1,
+        &encode_position(Some((large as u64).wrapping_add(small) as usize)),
+    )
+}
+
+/// Reads the total stake of the current epoch (`vote_account` 0) or the stake
+/// delegated to the vote account (first account) through the
+/// `sol_get_epoch_stake` syscall.
+#[inline(always)]
+pub fn process_get_epoch_stake(accounts: &[AccountInfo], vote_account: u8) -> ProgramResult {
+    let stake = if vote_account == 0 {
+        get_epoch_total_stake()
+    } else {
+        let vote_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        get_epoch_stake_for_vote_account(vote_account.key)
+    };
+    black_box(stake);
+    Ok(())
+}
+
+/// Bytes of every seed of `CpiSignedSeeds`, whose seeds only differ by length.
+static SIGNER_SEED: [u8; MAX_SEED_LEN] = [0x5e; MAX_SEED_LEN];
+
+/// Transfers lamports from the program derived address of `num_seeds` seeds
+/// of `seed_len` bytes and the `bump` seed (first account) through CPI,
+/// signing with its seeds.
+#[inline(always)]
+pub fn process_cpi_signed_seeds(
+    accounts: &[AccountInfo],
+    num_seeds: u8,
+    seed_len: u8,
+    bump: u8,
+) -> ProgramResult {
+    let [from, to, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let (num_seeds, seed_len) = (num_seeds as usize, seed_len as usize);
+    // The bump seed is one of the `MAX_SEEDS`.
+    if num_seeds >= MAX_SEEDS || seed_len > MAX_SEED_LEN {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let bump = [bump];
+    let mut seeds = vec![&SIGNER_SEED[..seed_len]; num_seeds];
+    seeds.push(&bump);
+
+    invoke_signed(
+        &solana_system_interface::instruction::transfer(from.key, to.key, 1_000_000_000),
+        &[from.clone(), to.clone()],
+        &[&seeds],
+    )
+}
+
+/// Byte written to the data of the `OwnerRoundTrip` account while the program
+/// owns it.
+const ROUND_TRIP_BYTE: u8 = 0xab;
+
+/// Assigns the system-owned first account to the program through CPI, writes
+/// its data, then zeroes it and assigns the account back to the system
+/// program, which the runtime only allows for zeroed data.
+#[inline(always)]
+pub fn process_owner_round_trip(accounts: &[AccountInfo]) -> ProgramResult {
+    let [account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if account.owner != &solana_system_interface::program::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    invoke(
+        &solana_system_interface::instruction::assign(account.key, &crate::ID),
+        &[account.clone()],
+    )?;
+    // The owner is updated by the runtime when the CPI returns.
+    if account.owner != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    {
+        let mut data = account.try_borrow_mut_data()?;
+        data.fill(ROUND_TRIP_BYTE);
+        // Keeps the write from being elided by the zeroing.
+        black_box(&mut **data);
+        data.fill(0);
+    }
+    account.assign(&solana_system_interface::program::ID);
+
+    Ok(())
+}
+
+/// Zeroes a byte slice with the `sol_memset` syscall.
+#[inline(always)]
+fn memset_zero(data: &mut [u8]) {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        solana_define_syscall::definitions::sol_memset_(data.as_mut_ptr(), 0, data.len() as u64);
+    }
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    {
+        data.fill(0);
+    }
+}
+
+/// Zeroes the first `len` bytes of the data of the first account with a loop
+/// of volatile `u64` writes (`strategy` 0), `fill` (`strategy` 1) or the
+/// `sol_memset` syscall (any other strategy).
+#[inline(always)]
+pub fn process_zero_data(accounts: &[AccountInfo], len: u32, strategy: u8) -> ProgramResult {
+    let [account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let mut data = account.try_borrow_mut_data()?;
+    let data = data
+        .get_mut(..len as usize)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    match strategy {
+        0 => {
+            // SAFETY: every bit pattern is a valid `u64`.
+            let (head, words, tail) = unsafe { data.align_to_mut::<u64>() };
+            // Volatile writes keep the loops from being turned into a
+            // `memset` call.
+            for word in words {
+                unsafe { core::ptr::write_volatile(word, 0) };
+            }
+            for byte in head.iter_mut().chain(tail) {
+                unsafe { core::ptr::write_volatile(byte, 0) };
+            }
+        }
+        1 => data.fill(0),
+        _ => memset_zero(data),
+    }
+
+    Ok(())
+}
+
+/// Copies a byte slice to another of the same length with the `sol_memcpy`
+/// syscall.
+#[inline(always)]
+fn memcpy(destination: &mut [u8], source: &[u8]) {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        solana_define_syscall::definitions::sol_memcpy_(
+            destination.as_mut_ptr(),
+            source.as_ptr(),
+            source.len() as u64,
+        );
+    }
+
+    // For clippy
+    #[cfg(not(target_os = "solana"))]
+    {
+        destination.copy_from_slice(source);
+    }
+}
+
+/// Copies the first `len` bytes of the data of the first account to the data
+/// of the second account with a loop of volatile byte writes (`strategy` 0),
+/// `copy_from_slice` (`strategy` 1) or the `sol_memcpy` syscall (any other
+/// strategy). Both accounts are borrowed at once, so passing the same account
+/// twice fails.
+#[inline(always)]
+pub fn process_copy_account_data(
+    accounts: &[AccountInfo],
+    len: u32,
+    strategy: u8,
+) -> ProgramResult {
+    let [source_account, destination_account, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let source_data = source_account.try_borrow_data()?;
+    let mut destination_data = destination_account.try_borrow_mut_data()?;
+    let len = len as usize;
+    let source = source_data
+        .get(..len)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let destination = destination_data
+        .get_mut(..len)
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+
+    match strategy {
+        // Volatile writes keep the loop from being turned into a `memcpy`
+        // call.
+        0 => {
+            for (to, from) in destination.iter_mut().zip(source) {
+                unsafe { core::ptr::write_volatile(to, *from) };
+            }
+        }
+        1 => destination.copy_from_slice(source),
+        _ => memcpy(destination, source),
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use solana_program::hash::Hash;
-    use solana_program::{
-        account_info::AccountInfo,
-        pubkey::Pubkey,
-        sysvar::{self, slot_hashes::SlotHashes, Sysvar},
-    };
+    use crate::sorted_sysvar::SlotHashes;
+    use solana_program::{account_info::AccountInfo, pubkey::Pubkey, sysvar};
     use std::{cell::RefCell, rc::Rc};
 
     // Helper to create mock account info
@@ -291,7 +1215,7 @@ mod tests {
     }
 
     #[test]
-    fn test_manual_binary_search_logic() {
+    fn test_slot_hashes_binary_search_logic() {
         let mock_entries_data = [
             (100, [1u8; 32]),
             (98, [2u8; 32]),
@@ -302,18 +1226,18 @@ mod tests {
         ];
         let data = create_mock_slot_hashes_data(&mock_entries_data);
 
-        assert_eq!(manual_binary_search(&data, 100), Ok(0));
-        assert_eq!(manual_binary_search(&data, 95), Ok(2));
-        assert_eq!(manual_binary_search(&data, 80), Ok(5));
+        assert_eq!(SlotHashes::new(&data).binary_search(100), Ok(0));
+        assert_eq!(SlotHashes::new(&data).binary_search(95), Ok(2));
+        assert_eq!(SlotHashes::new(&data).binary_search(80), Ok(5));
 
-        assert_eq!(manual_binary_search(&data, 101), Err(0));
-        assert_eq!(manual_binary_search(&data, 99), Err(1));
-        assert_eq!(manual_binary_search(&data, 91), Err(3));
-        assert_eq!(manual_binary_search(&data, 79), Err(6));
+        assert_eq!(SlotHashes::new(&data).binary_search(101), Err(0));
+        assert_eq!(SlotHashes::new(&data).binary_search(99), Err(1));
+        assert_eq!(SlotHashes::new(&data).binary_search(91), Err(3));
+        assert_eq!(SlotHashes::new(&data).binary_search(79), Err(6));
 
         let empty_data = create_mock_slot_hashes_data(&[]);
-        assert_eq!(manual_binary_search(&empty_data, 100), Err(0));
+        assert_eq!(SlotHashes::new(&empty_data).binary_search(100), Err(0));
 
-        assert_eq!(manual_binary_search(&data[0..5], 100), Err(0));
+        assert_eq!(SlotHashes::new(&data[0..5]).binary_search(100), Err(0));
     }
 }
@@ -0,0 +1,128 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::ser::{Serialize, SerializeStruct, SerializeTuple, Serializer};
+use solana_program_error::ProgramError;
+
+/// Program state used by the `ReadModifyWrite` benchmark.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct State {
+    pub counter: u64,
+    pub timestamp: i64,
+    pub authority: [u8; 32],
+    pub payload: [u8; 152],
+}
+
+impl State {
+    pub const LEN: usize = 200;
+
+    /// State written by the `WriteState` benchmarks.
+    #[inline(always)]
+    pub fn sample() -> Self {
+        Self {
+            counter: 42,
+            timestamp: 1_700_000_000,
+            authority: crate::ID.to_bytes(),
+            payload: [7; 152],
+        }
+    }
+
+    /// Writes the state into the start of `bytes` with hand-written
+    /// little-endian writes, in the layout of its borsh and bincode
+    /// serializations.
+    #[inline(always)]
+    pub fn write(&self, bytes: &mut [u8]) -> Result<(), ProgramError> {
+        let bytes = bytes
+            .get_mut(..Self::LEN)
+            .ok_or(ProgramError::AccountDataTooSmall)?;
+        bytes[0..8].copy_from_slice(&self.counter.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.timestamp.to_le_bytes());
+        bytes[16..48].copy_from_slice(&self.authority);
+        bytes[48..200].copy_from_slice(&self.payload);
+        Ok(())
+    }
+}
+
+/// Serializes the state for bincode. Serde only derives implementations for
+/// arrays of up to 32 elements, so the payload is serialized as a tuple, which
+/// bincode encodes without a length prefix.
+impl Serialize for State {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("State", 4)?;
+        state.serialize_field("counter", &self.counter)?;
+        state.serialize_field("timestamp", &self.timestamp)?;
+        state.serialize_field("authority", &self.authority)?;
+        state.serialize_field("payload", &Array(&self.payload))?;
+        state.end()
+    }
+}
+
+/// Fixed-size byte array serialized as a tuple.
+struct Array<'a, const N: usize>(&'a [u8; N]);
+
+impl<const N: usize> Serialize for Array<'_, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut array = serializer.serialize_tuple(N)?;
+        for byte in self.0 {
+            array.serialize_element(byte)?;
+        }
+        array.end()
+    }
+}
+
+/// Maximum number of keys in the signer set of the `CheckMultisig` benchmark,
+/// stored as consecutive pubkeys at the start of the multisig account data.
+pub const MAX_SIGNERS: usize = 11;
+
+/// Event emitted by the `EmitEvent` benchmarks.
+#[derive(BorshSerialize, Clone, Debug)]
+pub struct Event {
+    pub kind: u8,
+    pub user: [u8; 32],
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+impl Event {
+    /// Length of the borsh serialization.
+    pub const LEN: usize = 1 + 32 + 8 + 8;
+
+    /// Length of the padded base64 encoding of the serialization.
+    pub const BASE64_LEN: usize = Self::LEN.div_ceil(3) * 4;
+
+    #[inline(always)]
+    pub fn sample() -> Self {
+        Self {
+            kind: 1,
+            user: crate::ID.to_bytes(),
+            amount: 1_000_000_000,
+            timestamp: 1_700_000_000,
+        }
+    }
+}
+
+/// Depth of the concurrent merkle tree of the `AppendLeaf` benchmark.
+pub const MAX_DEPTH: usize = 14;
+
+/// Number of entries of the changelog buffer of the `AppendLeaf` benchmark.
+pub const BUFFER_SIZE: usize = 64;
+
+/// Layout of the concurrent merkle tree used by the `AppendLeaf` benchmark,
+/// modeled on state compression and accessed in place, since deserializing
+/// the whole account would not fit the heap:
+///
+/// - `sequence_number: u64`: number of appended leaves.
+/// - `active_index: u64`: changelog entry of the last append.
+/// - `frontier: [[u8; 32]; MAX_DEPTH]`: rightmost left-sibling node of each
+///   level.
+/// - `change_logs: [ChangeLog; BUFFER_SIZE]`: circular changelog buffer, each
+///   entry holding the root, the path (leaf to root) and the `u32` leaf index
+///   of an append, padded to 8 bytes.
+pub struct MerkleTree;
+
+impl MerkleTree {
+    pub const SEQUENCE_NUMBER_OFFSET: usize = 0;
+    pub const ACTIVE_INDEX_OFFSET: usize = 8;
+    pub const FRONTIER_OFFSET: usize = 16;
+    pub const CHANGE_LOGS_OFFSET: usize = Self::FRONTIER_OFFSET + 32 * MAX_DEPTH;
+    pub const CHANGE_LOG_LEN: usize = 32 + 32 * MAX_DEPTH + 8;
+    pub const LEN: usize = Self::CHANGE_LOGS_OFFSET + Self::CHANGE_LOG_LEN * BUFFER_SIZE;
+}
@@ -0,0 +1,13 @@
+//! Token-2022 account layout, as read by the `ParseToken2022Extensions`
+//! benchmark.
+//!
+//! Extended accounts hold the 165-byte base token account, an account type
+//! byte and the extensions, each encoded as a `u16` type, a `u16` length and
+//! the value (TLV), walked by [`eisodos_token_2022`].
+
+pub use eisodos_token_2022::Extensions;
+use solana_pubkey::Pubkey;
+
+/// Token-2022 program id.
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
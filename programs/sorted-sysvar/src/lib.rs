@@ -0,0 +1,112 @@
+//! Access to sysvars holding sorted, slot-keyed entries, as read by the
+//! `SlotHashes` and `StakeHistory` benchmarks.
+//!
+//! Both sysvars are a `u64` entry count followed by fixed-size entries, each
+//! starting with a `u64` key (a slot or an epoch), in descending key order.
+//! Only the entry size differs, so the search is shared.
+//!
+//! The search does not depend on the framework, so the programs reading the
+//! raw sysvar data run the same code and the benchmarks only differ by their
+//! entrypoint and account checks.
+
+#![no_std]
+
+use core::cmp::Ordering;
+
+/// Length of the entry count prefix.
+pub const LEN_PREFIX_SIZE: usize = 8;
+
+/// Length of the key of each entry.
+pub const KEY_SIZE: usize = 8;
+
+/// Length of each `SlotHashes` entry: a slot and a 32-byte hash.
+pub const SLOT_HASHES_ENTRY_SIZE: usize = KEY_SIZE + 32;
+
+/// `SlotHashes` data.
+pub type SlotHashes<'a> = SortedSysvar<'a, SLOT_HASHES_ENTRY_SIZE>;
+
+/// `StakeHistory` data: an epoch and the effective, activating and
+/// deactivating stake per entry.
+pub type StakeHistory<'a> = SortedSysvar<'a, 32>;
+
+/// Sysvar data with entries of `ENTRY_SIZE` bytes sorted by descending key.
+pub struct SortedSysvar<'a, const ENTRY_SIZE: usize> {
+    data: &'a [u8],
+}
+
+impl<'a, const ENTRY_SIZE: usize> SortedSysvar<'a, ENTRY_SIZE> {
+    /// Wraps the raw account data of the sysvar.
+    #[inline(always)]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Returns the entry count stored in the prefix, if present.
+    #[inline(always)]
+    pub fn num_entries(&self) -> Option<usize> {
+        let prefix = self.data.get(..LEN_PREFIX_SIZE)?;
+        Some(u64::from_le_bytes(prefix.try_into().unwrap()) as usize)
+    }
+
+    /// Returns the value of the entry at `index`, i.e. the bytes following
+    /// its key.
+    #[inline(always)]
+    pub fn value(&self, index: usize) -> Option<&'a [u8]> {
+        let offset = LEN_PREFIX_SIZE + index * ENTRY_SIZE + KEY_SIZE;
+        self.data.get(offset..offset + ENTRY_SIZE - KEY_SIZE)
+    }
+
+    /// Midpoint binary search for `key`, returning the index of its entry or
+    /// the insertion point if not found.
+    pub fn binary_search(&self, key: u64) -> Result<usize, usize> {
+        let Some(num_entries) = self.num_entries() else {
+            return Err(0);
+        };
+
+        let mut low = 0;
+        let mut high = num_entries;
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let entry_offset = LEN_PREFIX_SIZE + mid * ENTRY_SIZE;
+
+            let Some(current_key) = self.data.get(entry_offset..entry_offset + KEY_SIZE) else {
+                return Err(low);
+            };
+            let current_key = u64::from_le_bytes(current_key.try_into().unwrap());
+
+            match current_key.cmp(&key) {
+                Ordering::Equal => return Ok(mid), // Return INDEX if found
+                Ordering::Less => high = mid,
+                Ordering::Greater => low = mid + 1,
+            }
+        }
+
+        Err(low) // Return insertion point if not found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stake_history_binary_search() {
+        let epochs = [500u64, 499, 498, 496];
+        let mut data = (epochs.len() as u64).to_le_bytes().to_vec();
+        for epoch in epochs {
+            data.extend_from_slice(&epoch.to_le_bytes());
+            data.extend_from_slice(&[epoch as u8; 24]);
+        }
+        let stake_history = StakeHistory::new(&data);
+
+        assert_eq!(stake_history.num_entries(), Some(4));
+        assert_eq!(stake_history.binary_search(500), Ok(0));
+        assert_eq!(stake_history.binary_search(496), Ok(3));
+        assert_eq!(stake_history.binary_search(497), Err(3));
+        assert_eq!(stake_history.value(2), Some(&[498u64 as u8; 24][..]));
+        assert_eq!(stake_history.value(4), None);
+
+        assert_eq!(StakeHistory::new(&data[..4]).binary_search(500), Err(0));
+    }
+}
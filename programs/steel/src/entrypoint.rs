@@ -0,0 +1,70 @@
+use crate::{
+    instruction::{Instruction, VERSION_2},
+    processor::{
+        process_account, process_create_account, process_empty_call, process_log, process_ping,
+        process_slot_hashes_get_entry, process_slot_hashes_get_hash_interpolated,
+        process_slot_hashes_position_interpolated, process_transfer,
+    },
+};
+use steel::*;
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction_data = match instruction_data {
+        // Empty - EmptyCall
+        [] => return process_empty_call(),
+        // V2 - Versioned (not nested)
+        [VERSION_2, versioned @ ..] if versioned.first() != Some(&VERSION_2) => versioned,
+        _ => instruction_data,
+    };
+    let (instruction, data) =
+        parse_instruction::<Instruction>(&crate::ID, program_id, instruction_data)?;
+
+    match instruction {
+        Instruction::Ping => {
+            no_args(data)?;
+            process_ping()
+        }
+        Instruction::Log => {
+            no_args(data)?;
+            process_log()
+        }
+        Instruction::Account => process_account(accounts, data),
+        Instruction::CreateAccount => {
+            no_args(data)?;
+            process_create_account(accounts)
+        }
+        Instruction::Transfer => {
+            no_args(data)?;
+            process_transfer(accounts)
+        }
+        Instruction::SlotHashesGetEntry => {
+            no_args(data)?;
+            process_slot_hashes_get_entry(accounts)
+        }
+        Instruction::SlotHashesGetHashInterpolated => {
+            no_args(data)?;
+            process_slot_hashes_get_hash_interpolated(accounts)
+        }
+        Instruction::SlotHashesPositionInterpolated => {
+            no_args(data)?;
+            process_slot_hashes_position_interpolated(accounts)
+        }
+    }
+}
+
+/// Rejects trailing data after the tag of an instruction without arguments,
+/// like the other programs do.
+#[inline(always)]
+fn no_args(data: &[u8]) -> ProgramResult {
+    if data.is_empty() {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidInstructionData)
+    }
+}
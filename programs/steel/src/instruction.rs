@@ -0,0 +1,43 @@
+use steel::*;
+
+/// Version byte of versioned (V2) instruction data, followed by the tag and the
+/// borsh-encoded fields. Versions are `0x80 | version`, above the raw tags.
+///
+/// Instruction fields are fixed-width little-endian integers, for which the
+/// raw and borsh encodings are identical.
+pub const VERSION_2: u8 = 0x80 | 2;
+
+/// Instruction tags, parsed by Steel's `parse_instruction`. Empty instruction
+/// data (`EmptyCall`) has no tag and is handled by the entrypoint.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive)]
+pub enum Instruction {
+    Ping = 0,
+    Log = 1,
+    Account = 2,
+    CreateAccount = 3,
+    Transfer = 4,
+    SlotHashesGetEntry = 5,
+    SlotHashesGetHashInterpolated = 6,
+    SlotHashesPositionInterpolated = 7,
+}
+
+/// Arguments of the `Account` instruction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Account {
+    pub expected: u64,
+}
+
+impl Account {
+    /// Unpacks the arguments following the tag.
+    #[inline(always)]
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let expected = data
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Ok(Self {
+            expected: u64::from_le_bytes(expected),
+        })
+    }
+}
@@ -0,0 +1,10 @@
+use steel::*;
+
+pub mod entrypoint;
+pub mod instruction;
+pub mod output;
+pub mod processor;
+
+pub use eisodos_sorted_sysvar as sorted_sysvar;
+
+declare_id!("Stee111111111111111111111111111111111111111");
@@ -0,0 +1,113 @@
+use crate::{
+    instruction::Account,
+    output::{encode_hash, encode_position, write_output},
+    sorted_sysvar::SlotHashes,
+};
+use solana_program::{msg, program::invoke};
+use steel::*;
+
+#[inline(always)]
+pub fn process_ping() -> ProgramResult {
+    Ok(())
+}
+
+#[inline(always)]
+pub fn process_empty_call() -> ProgramResult {
+    Ok(())
+}
+
+#[inline(always)]
+pub fn process_log() -> ProgramResult {
+    #[cfg(not(feature = "fmt-log"))]
+    msg!("Instruction: Log");
+    #[cfg(feature = "fmt-log")]
+    msg!("Instruction: Log ({})", core::hint::black_box(1u64));
+    Ok(())
+}
+
+#[inline(always)]
+pub fn process_account(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let args = Account::unpack(data)?;
+    if accounts.len() == args.expected as usize {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+#[inline(always)]
+pub fn process_create_account(accounts: &[AccountInfo]) -> ProgramResult {
+    let [payer_info, new_account_info, system_program_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    payer_info.is_signer()?.is_writable()?;
+    new_account_info.is_signer()?.is_writable()?;
+    system_program_info.is_program(&system_program::ID)?;
+
+    invoke(
+        &solana_system_interface::instruction::create_account(
+            payer_info.key,
+            new_account_info.key,
+            500_000_000,
+            10,
+            &crate::ID,
+        ),
+        &[payer_info.clone(), new_account_info.clone()],
+    )
+}
+
+#[inline(always)]
+pub fn process_transfer(accounts: &[AccountInfo]) -> ProgramResult {
+    let [from_info, to_info, system_program_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    from_info.is_signer()?.is_writable()?;
+    to_info.is_writable()?;
+    system_program_info.is_program(&system_program::ID)?;
+
+    invoke(
+        &solana_system_interface::instruction::transfer(from_info.key, to_info.key, 1_000_000_000),
+        &[from_info.clone(), to_info.clone()],
+    )
+}
+
+/// Returns the SlotHashes sysvar account, the first account, checking its
+/// address with Steel's account validation.
+#[inline(always)]
+fn slot_hashes_info<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a AccountInfo<'info>, ProgramError> {
+    let [slot_hashes_info, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    slot_hashes_info.has_address(&sysvar::slot_hashes::ID)?;
+    Ok(slot_hashes_info)
+}
+
+#[inline(always)]
+pub fn process_slot_hashes_get_entry(accounts: &[AccountInfo]) -> ProgramResult {
+    let data = slot_hashes_info(accounts)?.try_borrow_data()?;
+    let num_entries = SlotHashes::new(&data)
+        .num_entries()
+        .ok_or(ProgramError::AccountDataTooSmall)?;
+    let _is_empty = num_entries == 0;
+    Ok(())
+}
+
+#[inline(always)]
+pub fn process_slot_hashes_get_hash_interpolated(accounts: &[AccountInfo]) -> ProgramResult {
+    let data = slot_hashes_info(accounts)?.try_borrow_data()?;
+    let slot_hashes = SlotHashes::new(&data);
+    let hash = slot_hashes
+        .binary_search(0)
+        .ok()
+        .and_then(|position| slot_hashes.value(position)?.try_into().ok());
+    write_output(accounts, 1, &encode_hash(hash))
+}
+
+#[inline(always)]
+pub fn process_slot_hashes_position_interpolated(accounts: &[AccountInfo]) -> ProgramResult {
+    let data = slot_hashes_info(accounts)?.try_borrow_data()?;
+    let position = SlotHashes::new(&data).binary_search(0).ok();
+    write_output(accounts, 1, &encode_position(position))
+}
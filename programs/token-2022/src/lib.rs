@@ -0,0 +1,108 @@
+//! Token-2022 account layout, as read by the `ParseToken2022Extensions`
+//! benchmark.
+//!
+//! Extended accounts hold the 165-byte base token account, an account type
+//! byte and the extensions, each encoded as a `u16` type, a `u16` length and
+//! the value (TLV).
+//!
+//! The TLV walk does not depend on the framework, so every program runs the
+//! same code and the benchmarks only differ by their entrypoint.
+
+#![no_std]
+
+/// Length of the base token account.
+pub const BASE_ACCOUNT_LEN: usize = 165;
+
+/// Account type of token accounts, stored right after the base account.
+pub const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// `TransferFeeAmount` extension type: the withheld fee, as a `u64`.
+pub const TRANSFER_FEE_AMOUNT: u16 = 2;
+
+/// `MemoTransfer` extension type: whether incoming transfers require a memo.
+pub const MEMO_TRANSFER: u16 = 8;
+
+/// Extensions of a token account relevant to transfers.
+#[derive(Default)]
+pub struct Extensions {
+    /// Number of extensions.
+    pub count: u8,
+    /// Withheld transfer fee, if the account has a `TransferFeeAmount`.
+    pub withheld_amount: Option<u64>,
+    /// Whether incoming transfers require a memo.
+    pub memo_required: bool,
+}
+
+impl Extensions {
+    /// Walks the TLV extension data of a token account. Parsing stops at the
+    /// first uninitialized extension type (zero padding).
+    ///
+    /// Returns `None` if the data is not a token account, an extension value
+    /// is truncated or a `TransferFeeAmount` value is not a `u64`.
+    #[inline(always)]
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.get(BASE_ACCOUNT_LEN) != Some(&ACCOUNT_TYPE_ACCOUNT) {
+            return None;
+        }
+
+        let mut extensions = Self::default();
+        let mut offset = BASE_ACCOUNT_LEN + 1;
+
+        while let Some(header) = data.get(offset..offset + 4) {
+            let extension_type = u16::from_le_bytes([header[0], header[1]]);
+            if extension_type == 0 {
+                break;
+            }
+            let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+            let value = data.get(offset + 4..offset + 4 + len)?;
+
+            match extension_type {
+                TRANSFER_FEE_AMOUNT => {
+                    extensions.withheld_amount = Some(u64::from_le_bytes(value.try_into().ok()?));
+                }
+                MEMO_TRANSFER => {
+                    extensions.memo_required = value.first() == Some(&1);
+                }
+                _ => {}
+            }
+
+            extensions.count = extensions.count.saturating_add(1);
+            offset += 4 + len;
+        }
+
+        Some(extensions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(extensions: &[(u16, &[u8])]) -> [u8; 256] {
+        let mut data = [0; 256];
+        data[BASE_ACCOUNT_LEN] = ACCOUNT_TYPE_ACCOUNT;
+        let mut offset = BASE_ACCOUNT_LEN + 1;
+        for (extension_type, value) in extensions {
+            data[offset..offset + 2].copy_from_slice(&extension_type.to_le_bytes());
+            data[offset + 2..offset + 4].copy_from_slice(&(value.len() as u16).to_le_bytes());
+            data[offset + 4..offset + 4 + value.len()].copy_from_slice(value);
+            offset += 4 + value.len();
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_extensions() {
+        let data = account(&[
+            (TRANSFER_FEE_AMOUNT, &42_000u64.to_le_bytes()),
+            (MEMO_TRANSFER, &[1]),
+        ]);
+        let extensions = Extensions::parse(&data).unwrap();
+        assert_eq!(extensions.count, 2);
+        assert_eq!(extensions.withheld_amount, Some(42_000));
+        assert!(extensions.memo_required);
+
+        assert!(Extensions::parse(&data[..BASE_ACCOUNT_LEN]).is_none());
+        assert!(Extensions::parse(&account(&[(TRANSFER_FEE_AMOUNT, &[0; 4])])).is_none());
+    }
+}
@@ -0,0 +1,69 @@
+use pinocchio_system::instructions::{CreateAccount, Transfer};
+use typhoon::prelude::*;
+
+program_id!("Typhoon111111111111111111111111111111111111");
+
+// Handlers are dispatched by their index, the one-byte tag of the other
+// programs. The dispatch has no fallback, so there is no `EmptyCall` and no
+// versioned (V2) instruction data.
+handlers! {
+    ping,
+    log,
+    account,
+    create_account,
+    transfer,
+}
+
+#[context]
+pub struct CreateAccountContext {
+    pub payer: Mut<Signer>,
+    pub new_account: Mut<Signer>,
+    pub system_program: Program<System>,
+}
+
+#[context]
+pub struct TransferContext {
+    pub from: Mut<Signer>,
+    pub to: Mut<SystemAccount>,
+    pub system_program: Program<System>,
+}
+
+pub fn ping() -> ProgramResult {
+    Ok(())
+}
+
+pub fn log() -> ProgramResult {
+    #[cfg(not(feature = "fmt-log"))]
+    msg!("Instruction: Log");
+    #[cfg(feature = "fmt-log")]
+    msg!("Instruction: Log ({})", core::hint::black_box(1u64));
+    Ok(())
+}
+
+pub fn account(remaining: Remaining, expected: Arg<u64>) -> ProgramResult {
+    if remaining.len() == *expected as usize {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+pub fn create_account(ctx: CreateAccountContext) -> ProgramResult {
+    CreateAccount {
+        from: ctx.payer.as_ref(),
+        to: ctx.new_account.as_ref(),
+        lamports: 500_000_000,
+        space: 10,
+        owner: &crate::ID,
+    }
+    .invoke()
+}
+
+pub fn transfer(ctx: TransferContext) -> ProgramResult {
+    Transfer {
+        from: ctx.from.as_ref(),
+        to: ctx.to.as_ref(),
+        lamports: 1_000_000_000,
+    }
+    .invoke()
+}
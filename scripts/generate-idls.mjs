@@ -7,16 +7,23 @@ const binaryInstallDir = path.join(__dirname, '..', '.cargo');
 
 getProgramFolders().forEach((folder) => {
   const cargo = getCargo(folder);
-  const isShank = Object.keys(cargo.dependencies).includes('shank');
+  const idlCrate = cargo.package.metadata.solana['idl-crate'];
+  const isShank =
+    Object.keys(cargo.dependencies).includes('shank') || idlCrate !== undefined;
   const programDir = path.join(__dirname, '..', folder);
 
+  // Only programs annotated with shank have an IDL.
+  if (!isShank) {
+    return;
+  }
+
   generateIdl({
-    generator: isShank ? 'shank' : 'anchor',
+    generator: 'shank',
     programName: cargo.package.name.replace(/-/g, '_'),
     programId: cargo.package.metadata.solana['program-id'],
     idlDir: programDir,
     idlName: 'idl',
-    programDir,
+    programDir: idlCrate ? path.join(programDir, idlCrate) : programDir,
     binaryInstallDir,
   });
 });
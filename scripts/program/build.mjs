@@ -1,6 +1,9 @@
 #!/usr/bin/env zx
 import 'zx/globals';
+import { stringify as stringifyToml } from '@iarna/toml';
 import {
+  getCargo,
+  getCargoMetadata,
   getProgramFolders,
   workingDirectory,
 } from '../utils.mjs';
@@ -13,3 +16,75 @@ for (const folder of getProgramFolders()) {
   cd(`${path.join(workingDirectory, folder)}`);
   await $`cargo-build-sbf ${process.argv.slice(3)}`;
 }
+
+// Build the programs with the features of each of their feature builds, saved
+// next to the default builds under the name of the feature build. A feature
+// build with `default-features = false` is built without the default features.
+const outputDir = path.join(workingDirectory, 'target', 'deploy');
+for (const folder of getProgramFolders()) {
+  const builds = getCargoMetadata(folder)?.solana?.['feature-builds'] ?? [];
+  const library = getCargo(folder).package.name.replace(/-/g, '_');
+
+  for (const { name, features, 'default-features': defaultFeatures } of builds) {
+    const buildDir = path.join(outputDir, name);
+    const featureFlags = [
+      ...(defaultFeatures === false ? ['--no-default-features'] : []),
+      ...(features.length > 0 ? ['--features', features.join(',')] : []),
+    ];
+    cd(`${path.join(workingDirectory, folder)}`);
+    await $`cargo-build-sbf ${featureFlags} --sbf-out-dir ${buildDir} ${process.argv.slice(3)}`;
+    await fs.copy(
+      path.join(buildDir, `${library}.so`),
+      path.join(outputDir, `${name}.so`)
+    );
+  }
+}
+
+// Build the programs against the dependency versions of each of their version
+// builds, saved next to the default builds as `<program>@<version>`, with the
+// features of the version build if it lists any. The manifest and lockfile are
+// patched for the build and restored afterwards, even if the build fails.
+const lockPath = path.join(workingDirectory, 'Cargo.lock');
+for (const folder of getProgramFolders()) {
+  const builds = getCargoMetadata(folder)?.solana?.['version-builds'] ?? [];
+  const manifestPath = path.join(workingDirectory, folder, 'Cargo.toml');
+  const library = getCargo(folder).package.name.replace(/-/g, '_');
+
+  for (const {
+    version,
+    dependencies,
+    features = [],
+    'default-features': defaultFeatures,
+  } of builds) {
+    const name = `${library}@${version}`;
+    const manifest = await fs.readFile(manifestPath, 'utf8');
+    const lock = (await fs.pathExists(lockPath))
+      ? await fs.readFile(lockPath, 'utf8')
+      : undefined;
+    const cargo = getCargo(folder);
+    Object.assign(cargo.dependencies, dependencies);
+
+    const buildDir = path.join(outputDir, name);
+    const featureFlags = [
+      ...(defaultFeatures === false ? ['--no-default-features'] : []),
+      ...(features.length > 0 ? ['--features', features.join(',')] : []),
+    ];
+    try {
+      await fs.writeFile(manifestPath, stringifyToml(cargo));
+      cd(`${path.join(workingDirectory, folder)}`);
+      await $`cargo-build-sbf ${featureFlags} --sbf-out-dir ${buildDir} ${process.argv.slice(3)}`;
+      await fs.copy(
+        path.join(buildDir, `${library}.so`),
+        path.join(outputDir, `${name}.so`)
+      );
+    } catch (error) {
+      echo(chalk.red(`Failed to build ${name}`));
+      throw error;
+    } finally {
+      await fs.writeFile(manifestPath, manifest);
+      if (lock !== undefined) {
+        await fs.writeFile(lockPath, lock);
+      }
+    }
+  }
+}